@@ -0,0 +1,781 @@
+//! Parsing of `#[constitution(...)]` options and `#[invariant(...)]` arguments.
+
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Expr, Ident, LitStr, Path, Token, Type, WherePredicate, parse_quote};
+
+/// Options collected from every `#[constitution(...)]` attribute on the struct.
+#[derive(Default)]
+pub(crate) struct StructOptions {
+    /// Invariants that only apply when a type-state bound holds.
+    pub(crate) state_invariants: Vec<StateInvariants>,
+    /// Generate an `extern "C"` validation function (`c_export`).
+    pub(crate) c_export: bool,
+    /// Generate a `proptest::arbitrary::Arbitrary` impl (`arbitrary`).
+    pub(crate) arbitrary: bool,
+    /// Generate a checked `try_new` constructor (`constructor`).
+    pub(crate) constructor: bool,
+    /// Generate a `<Name>Builder` with a checked `build()` (`builder`).
+    pub(crate) builder: bool,
+    /// Implement `TryFrom` for a single-field struct (`try_from`).
+    pub(crate) try_from: bool,
+    /// Generate `enforce_law_or_panic()` (`panic`).
+    pub(crate) panic: bool,
+    /// Generate `validate()` returning this error type (`error = "Type"`).
+    pub(crate) error: Option<Type>,
+    /// Generate `describe_invariants()` (`introspect`).
+    pub(crate) introspect: bool,
+    /// Generate `repair_into_bounds()` (`repair`).
+    pub(crate) repair: bool,
+    /// Generate `verify_integrity_partial()` (`partial`).
+    pub(crate) partial: bool,
+    /// Generate `const fn validate_const()` (`validate_const`). Holds the
+    /// option's span, where invariants outside the const subset are reported.
+    pub(crate) validate_const: Option<proc_macro2::Span>,
+    /// Generate `field_report()` (`field_report`).
+    pub(crate) field_report: bool,
+    /// Generate `contract()` (`contract`).
+    pub(crate) contract: bool,
+    /// Generate `tautological_invariants()` (`tautology`).
+    pub(crate) tautology: bool,
+    /// Generate `enforce_law_all()` (`enforce_all`).
+    pub(crate) enforce_all: bool,
+    /// Generate `verify_integrity_blocking()` (`blocking`).
+    pub(crate) blocking: bool,
+    /// Generate `smt_script()` (`smt_script`).
+    pub(crate) smt_script: bool,
+    /// Generate `verify_report()` (`report`).
+    pub(crate) report: bool,
+    /// Generate `guard()` and `try_guard()` (`guard`).
+    pub(crate) guard: bool,
+    /// Generate `verify_all_parallel()` (`parallel`).
+    pub(crate) parallel: bool,
+    /// Generate `invariant_fn()` (`invariant_fn`).
+    pub(crate) invariant_fn: bool,
+    /// Generate `verify_batch()` (`batch`).
+    pub(crate) batch: bool,
+    /// Implement `Drop` to check the invariants in debug builds
+    /// (`verify_on_drop`). Holds the option's span, where a conflicting `Drop`
+    /// impl is reported.
+    pub(crate) verify_on_drop: Option<proc_macro2::Span>,
+    /// Generate `check_on_drop()`, called from `Drop` or by hand
+    /// (`verify_on_drop(manual)`).
+    pub(crate) check_on_drop: bool,
+    /// Reuse `enforce_law()` results while the data hash is unchanged
+    /// (`memoize`).
+    pub(crate) memoize: bool,
+    /// How integer fields reach the solver (`int_semantics = "..."`).
+    pub(crate) int_semantics: IntSemantics,
+    /// Conjoin each field's invariants into one solver goal (`combine`).
+    pub(crate) combine: bool,
+    /// Evaluate arithmetic comparisons in runtime checks as `i128`
+    /// (`widen_arithmetic`).
+    pub(crate) widen_arithmetic: bool,
+    /// Reject invariants the solver can't express instead of checking them at
+    /// runtime only (`strict_parse`).
+    pub(crate) strict_parse: bool,
+    /// Milliseconds the solver may spend on each proof (`timeout_ms = N`).
+    pub(crate) timeout_ms: Option<u64>,
+    /// The solver the generated verification methods use (`backend = "..."`).
+    pub(crate) backend: Option<LitStr>,
+    /// Retry proofs that time out over unbounded integers (`fallback_linear`).
+    pub(crate) fallback_linear: bool,
+    /// Fragment traits and invariant sets whose invariants are folded in
+    /// (`inherit = Trait`, `use = "Set"`).
+    pub(crate) inherit: Vec<Path>,
+    /// Invariants already delivered by fragment macros.
+    pub(crate) inherited: Vec<InheritedInvariants>,
+}
+
+/// The integer model the solver proves invariants in.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum IntSemantics {
+    /// Fixed-width bitvectors that wrap like the machine does (`"bitvector"`).
+    #[default]
+    Bitvector,
+    /// Unbounded mathematical integers (`"math"`).
+    Math,
+}
+
+/// Invariants delivered by a `#[constitution_fragment]` trait's macro, as
+/// `#[constitution(inherited(from = Trait, invariant(...), ...))]`.
+pub(crate) struct InheritedInvariants {
+    /// The fragment trait the invariants come from.
+    pub(crate) from: Path,
+    /// The raw invariant expressions.
+    pub(crate) invariants: Vec<Expr>,
+    /// Whether the trait is an item-less marker the struct should implement.
+    pub(crate) marker: bool,
+}
+
+/// Invariants declared with `#[constitution(when(T: Marker), invariant(...))]`.
+pub(crate) struct StateInvariants {
+    /// The bound selecting the type state, e.g. `T: Active`.
+    pub(crate) bound: WherePredicate,
+    /// The raw invariant expressions (string literals or bare expressions).
+    pub(crate) invariants: Vec<Expr>,
+}
+
+impl StructOptions {
+    /// Parses all `#[constitution(...)]` attributes, ignoring unrelated ones.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = StructOptions::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("constitution") {
+                continue;
+            }
+
+            let mut bound: Option<WherePredicate> = None;
+            let mut invariants = Vec::new();
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("when") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    bound = Some(content.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("invariant") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    invariants.push(content.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("inherit") {
+                    options.inherit.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("use") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    options.inherit.push(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("inherited") {
+                    let mut from = None;
+                    let mut invariants = Vec::new();
+                    let mut marker = false;
+                    meta.parse_nested_meta(|inner| {
+                        if inner.path.is_ident("from") {
+                            from = Some(inner.value()?.parse()?);
+                            Ok(())
+                        } else if inner.path.is_ident("marker") {
+                            marker = true;
+                            Ok(())
+                        } else if inner.path.is_ident("invariant") {
+                            let content;
+                            syn::parenthesized!(content in inner.input);
+                            invariants.push(content.parse()?);
+                            Ok(())
+                        } else {
+                            Err(inner.error("unsupported inherited option"))
+                        }
+                    })?;
+                    let from = from.ok_or_else(|| meta.error("`inherited` requires `from`"))?;
+                    options.inherited.push(InheritedInvariants {
+                        from,
+                        invariants,
+                        marker,
+                    });
+                    Ok(())
+                } else if meta.path.is_ident("error") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    options.error = Some(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("strict_parse") {
+                    options.strict_parse = true;
+                    Ok(())
+                } else if meta.path.is_ident("int_semantics") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let semantics = match lit.value().as_str() {
+                        "bitvector" => IntSemantics::Bitvector,
+                        "math" => IntSemantics::Math,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "`int_semantics` must be \"bitvector\" or \"math\"",
+                            ));
+                        }
+                    };
+                    options.int_semantics = semantics;
+                    Ok(())
+                } else if meta.path.is_ident("bitvector") {
+                    options.int_semantics = IntSemantics::Bitvector;
+                    Ok(())
+                } else if meta.path.is_ident("timeout_ms") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    let timeout_ms: u64 = lit.base10_parse()?;
+                    if timeout_ms == 0 {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "`timeout_ms` must be at least 1",
+                        ));
+                    }
+                    options.timeout_ms = Some(timeout_ms);
+                    Ok(())
+                } else if meta.path.is_ident("backend") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    if !matches!(lit.value().as_str(), "z3" | "cvc5") {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "`backend` must be \"z3\" or \"cvc5\"",
+                        ));
+                    }
+                    options.backend = Some(lit);
+                    Ok(())
+                } else if meta.path.is_ident("fallback_linear") {
+                    options.fallback_linear = true;
+                    Ok(())
+                } else if meta.path.is_ident("combine") {
+                    options.combine = true;
+                    Ok(())
+                } else if meta.path.is_ident("widen_arithmetic") {
+                    options.widen_arithmetic = true;
+                    Ok(())
+                } else if meta.path.is_ident("c_export") {
+                    options.c_export = true;
+                    Ok(())
+                } else if meta.path.is_ident("arbitrary") {
+                    options.arbitrary = true;
+                    Ok(())
+                } else if meta.path.is_ident("constructor") {
+                    options.constructor = true;
+                    Ok(())
+                } else if meta.path.is_ident("builder") {
+                    options.builder = true;
+                    Ok(())
+                } else if meta.path.is_ident("try_from") {
+                    options.try_from = true;
+                    Ok(())
+                } else if meta.path.is_ident("panic") {
+                    options.panic = true;
+                    Ok(())
+                } else if meta.path.is_ident("introspect") {
+                    options.introspect = true;
+                    Ok(())
+                } else if meta.path.is_ident("repair") {
+                    options.repair = true;
+                    Ok(())
+                } else if meta.path.is_ident("partial") {
+                    options.partial = true;
+                    Ok(())
+                } else if meta.path.is_ident("validate_const") {
+                    options.validate_const = Some(syn::spanned::Spanned::span(&meta.path));
+                    Ok(())
+                } else if meta.path.is_ident("field_report") {
+                    options.field_report = true;
+                    Ok(())
+                } else if meta.path.is_ident("contract") {
+                    options.contract = true;
+                    Ok(())
+                } else if meta.path.is_ident("tautology") {
+                    options.tautology = true;
+                    Ok(())
+                } else if meta.path.is_ident("enforce_all") {
+                    options.enforce_all = true;
+                    Ok(())
+                } else if meta.path.is_ident("blocking") {
+                    options.blocking = true;
+                    Ok(())
+                } else if meta.path.is_ident("smt_script") {
+                    options.smt_script = true;
+                    Ok(())
+                } else if meta.path.is_ident("report") {
+                    options.report = true;
+                    Ok(())
+                } else if meta.path.is_ident("guard") {
+                    options.guard = true;
+                    Ok(())
+                } else if meta.path.is_ident("parallel") {
+                    options.parallel = true;
+                    Ok(())
+                } else if meta.path.is_ident("invariant_fn") {
+                    options.invariant_fn = true;
+                    Ok(())
+                } else if meta.path.is_ident("batch") {
+                    options.batch = true;
+                    Ok(())
+                } else if meta.path.is_ident("verify_on_drop") {
+                    options.check_on_drop = true;
+                    if meta.input.peek(syn::token::Paren) {
+                        meta.parse_nested_meta(|inner| {
+                            if inner.path.is_ident("manual") {
+                                Ok(())
+                            } else {
+                                Err(inner.error("expected `manual`"))
+                            }
+                        })?;
+                    } else {
+                        options.verify_on_drop = Some(syn::spanned::Spanned::span(&meta.path));
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("memoize") {
+                    options.memoize = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported constitution option"))
+                }
+            })?;
+
+            match bound {
+                Some(bound) if !invariants.is_empty() => {
+                    options
+                        .state_invariants
+                        .push(StateInvariants { bound, invariants });
+                }
+                Some(_) => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "`when(...)` requires at least one `invariant(...)`",
+                    ));
+                }
+                None if !invariants.is_empty() => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "`invariant(...)` inside `#[constitution]` requires a `when(...)` bound",
+                    ));
+                }
+                None => {}
+            }
+        }
+
+        // These drop the values they reject, which the `Drop` check would panic on
+        if let Some(span) = options.verify_on_drop
+            && (options.constructor || options.builder || options.try_from || options.arbitrary)
+        {
+            return Err(syn::Error::new(
+                span,
+                "`verify_on_drop` panics on the invalid values `constructor`, `builder`, \
+                 `try_from` and `arbitrary` reject; use `verify_on_drop(manual)` and call \
+                 `check_on_drop()` where it applies",
+            ));
+        }
+
+        Ok(options)
+    }
+
+    /// Returns the name of the first option set that only applies to a struct.
+    pub(crate) fn struct_only_option(&self) -> Option<&'static str> {
+        [
+            (self.c_export, "c_export"),
+            (self.arbitrary, "arbitrary"),
+            (self.constructor, "constructor"),
+            (self.try_from, "try_from"),
+            (self.combine, "combine"),
+            (self.memoize, "memoize"),
+            (self.check_on_drop, "verify_on_drop"),
+            (self.fallback_linear, "fallback_linear"),
+            (self.error.is_some(), "error"),
+            (!self.state_invariants.is_empty(), "when"),
+            (!self.inherit.is_empty(), "inherit"),
+            (self.repair, "repair"),
+            (self.partial, "partial"),
+            (self.validate_const.is_some(), "validate_const"),
+            (self.field_report, "field_report"),
+            (self.contract, "contract"),
+            (self.tautology, "tautology"),
+        ]
+        .into_iter()
+        .find_map(|(set, name)| set.then_some(name))
+    }
+
+    /// Whether integer fields reach the solver as bitvectors.
+    pub(crate) fn bitvector(&self) -> bool {
+        self.int_semantics == IntSemantics::Bitvector
+    }
+
+    /// Returns the first inherited fragment whose invariants haven't been
+    /// delivered yet. Fragments are matched by trait name.
+    pub(crate) fn unresolved_inherit(&self) -> Option<&Path> {
+        self.inherit
+            .iter()
+            .find(|path| self.inherited_from(path).is_none())
+    }
+
+    /// Returns the delivered fragments in `inherit` order, paired with the path
+    /// the struct named them by.
+    pub(crate) fn inherited_in_order(&self) -> impl Iterator<Item = (&Path, &InheritedInvariants)> {
+        self.inherit
+            .iter()
+            .filter_map(|path| Some((path, self.inherited_from(path)?)))
+    }
+
+    fn inherited_from(&self, path: &Path) -> Option<&InheritedInvariants> {
+        self.inherited
+            .iter()
+            .find(|inherited| last_ident(&inherited.from) == last_ident(path))
+    }
+}
+
+fn last_ident(path: &Path) -> Option<&syn::Ident> {
+    path.segments.last().map(|segment| &segment.ident)
+}
+
+/// Parses the arguments of `#[constitution_fragment(invariant(...), ...)]`.
+pub(crate) fn parse_fragment_invariants(args: proc_macro2::TokenStream) -> syn::Result<Vec<Expr>> {
+    let mut invariants = Vec::new();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("invariant") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            invariants.push(content.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported constitution_fragment option"))
+        }
+    });
+    syn::parse::Parser::parse2(parser, args)?;
+    Ok(invariants)
+}
+
+/// One `Name = ["...", ...]` entry of `define_invariants!`.
+pub(crate) struct InvariantSet {
+    pub(crate) name: Ident,
+    pub(crate) invariants: Vec<Expr>,
+}
+
+impl Parse for InvariantSet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let invariants = content
+            .parse_terminated(Expr::parse, Token![,])?
+            .into_iter()
+            .collect();
+        Ok(Self { name, invariants })
+    }
+}
+
+/// Options collected from `#[constitution(...)]` attributes on a field.
+#[derive(Default)]
+pub(crate) struct FieldOptions {
+    /// Default check group for the field's invariants.
+    pub(crate) group: Option<LitStr>,
+    /// The field's type derives `Constitution` and is checked first (`nested`).
+    pub(crate) nested: bool,
+    /// Leave the field out of `compute_data_hash` (`skip_hash`).
+    pub(crate) skip_hash: bool,
+    /// The field is a C-like enum the solver sees as this discriminant type
+    /// (`repr(<int>)`).
+    pub(crate) repr: Option<Type>,
+    /// The integer field only uses this many low bits (`bits = N`).
+    pub(crate) bits: Option<syn::LitInt>,
+}
+
+impl FieldOptions {
+    /// Parses all `#[constitution(...)]` attributes on a field.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = FieldOptions::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("constitution") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("group") {
+                    options.group = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("nested") {
+                    options.nested = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip_hash") {
+                    options.skip_hash = true;
+                    Ok(())
+                } else if meta.path.is_ident("repr") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let ty: Type = content.parse()?;
+                    if !crate::is_integer_type(&ty) {
+                        return Err(syn::Error::new_spanned(
+                            ty,
+                            "`repr` must name an integer type",
+                        ));
+                    }
+                    options.repr = Some(ty);
+                    Ok(())
+                } else if meta.path.is_ident("bits") {
+                    let bits: syn::LitInt = meta.value()?.parse()?;
+                    if !(1..=63).contains(&bits.base10_parse::<u32>()?) {
+                        return Err(syn::Error::new_spanned(
+                            bits,
+                            "`bits` must be between 1 and 63",
+                        ));
+                    }
+                    options.bits = Some(bits);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported constitution option on a field"))
+                }
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// A quantified invariant, `forall <var> in <range> => <body>`, over a range
+/// with integer literal bounds such as `0..4`.
+pub(crate) struct Forall {
+    var: Ident,
+    range: syn::ExprRange,
+    body: Expr,
+}
+
+impl Forall {
+    /// Checks if the input starts with `forall <var>`, which no Rust
+    /// expression does.
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        input
+            .fork()
+            .parse::<Ident>()
+            .is_ok_and(|ident| ident == "forall")
+            && input.peek2(Ident)
+    }
+
+    /// The equivalent Rust expression, `(<range>).all(|<var>| <body>)`, which
+    /// the `lowering` module unrolls for the solver.
+    pub(crate) fn desugar(&self) -> Expr {
+        let Forall { var, range, body } = self;
+        parse_quote! { (#range).all(|#var| #body) }
+    }
+
+    /// The invariant as written.
+    fn text(&self) -> String {
+        let Forall { var, range, body } = self;
+        crate::render(&quote! { forall #var in #range => #body })
+    }
+}
+
+impl Parse for Forall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Ident>()?;
+        let var = input.parse()?;
+        input.parse::<Token![in]>()?;
+        let range: syn::ExprRange = input.parse()?;
+        let literal = |end: &Option<Box<Expr>>| {
+            end.as_deref()
+                .is_some_and(|end| crate::bounds::int_literal(end).is_some())
+        };
+        if !literal(&range.start) || !literal(&range.end) {
+            return Err(syn::Error::new_spanned(
+                &range,
+                "the range of `forall` must have integer literal bounds, e.g. `0..4`",
+            ));
+        }
+        input.parse::<Token![=>]>()?;
+        let body = input.parse()?;
+        Ok(Forall { var, range, body })
+    }
+}
+
+/// An unrolled invariant, `<body> for <var> in <range>`, the postfix form of
+/// `forall`. The range may be bounded by the length of a fixed-size array, as
+/// in `0..self.buf.len() - 1`.
+pub(crate) struct ForEach {
+    body: Expr,
+    var: Ident,
+    range: syn::ExprRange,
+}
+
+impl ForEach {
+    /// Parses the `for <var> in <range>` following `body`.
+    fn parse_after(body: Expr, input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let var = input.parse()?;
+        input.parse::<Token![in]>()?;
+        let range = input.parse()?;
+        Ok(ForEach { body, var, range })
+    }
+
+    /// The equivalent Rust expression, `(<range>).all(|<var>| <body>)`, as for
+    /// `forall`.
+    fn desugar(&self) -> Expr {
+        let ForEach { body, var, range } = self;
+        parse_quote! { (#range).all(|#var| #body) }
+    }
+
+    /// The invariant as written.
+    fn text(&self) -> String {
+        let ForEach { body, var, range } = self;
+        crate::render(&quote! { #body for #var in #range })
+    }
+}
+
+/// One side of an invariant: a Rust expression, or a range-membership test
+/// `<value> in <start>..=<end>` whose bounds are integer literals or
+/// associated constants.
+pub(crate) struct Clause {
+    value: Expr,
+    range: Option<syn::ExprRange>,
+}
+
+impl Clause {
+    /// The equivalent Rust expression; membership becomes
+    /// `(<range>).contains(&<value>)`, which the `lowering` module expands into
+    /// the two bounds for the solver.
+    pub(crate) fn desugar(self) -> Expr {
+        let Clause { value, range } = self;
+        match range {
+            Some(range) => parse_quote! { (#range).contains(&#value) },
+            None => value,
+        }
+    }
+}
+
+impl Parse for Clause {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let value = input.parse()?;
+        if input.parse::<Option<Token![in]>>()?.is_none() {
+            return Ok(Clause { value, range: None });
+        }
+        let range: syn::ExprRange = input.parse()?;
+        let bound = |end: &Option<Box<Expr>>| {
+            end.as_deref().is_some_and(|end| {
+                crate::bounds::int_literal(end).is_some()
+                    || crate::lowering::associated_const(end).is_some()
+            })
+        };
+        if !bound(&range.start) || !bound(&range.end) {
+            return Err(syn::Error::new_spanned(
+                &range,
+                "the range after `in` must have integer literal or `Self::CONST` bounds, e.g. `1..=100`",
+            ));
+        }
+        Ok(Clause {
+            value,
+            range: Some(range),
+        })
+    }
+}
+
+impl quote::ToTokens for Clause {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let value = &self.value;
+        match &self.range {
+            Some(range) => tokens.extend(quote! { #value in #range }),
+            None => value.to_tokens(tokens),
+        }
+    }
+}
+
+/// Parses an invariant expression, desugaring `forall`, `for`, `in` and `=>`.
+pub(crate) fn parse_invariant_expr(input: ParseStream) -> syn::Result<Expr> {
+    if Forall::peek(input) {
+        return Ok(input.parse::<Forall>()?.desugar());
+    }
+    let expr = input.parse::<Clause>()?.desugar();
+    if input.peek(Token![for]) {
+        return Ok(ForEach::parse_after(expr, input)?.desugar());
+    }
+    if input.parse::<Option<Token![=>]>>()?.is_none() {
+        return Ok(expr);
+    }
+    // `when => then` holds unless `when` does and `then` doesn't
+    let then = input.parse::<Clause>()?.desugar();
+    Ok(parse_quote! { if #expr { #then } else { true } })
+}
+
+/// The arguments of `#[invariant(...)]`: either `expr[, option = ...]` or the
+/// keyed form `name = "label", expr = "..."[, group = "name"]`, where
+/// `when = "...", then = "..."` may stand in for `expr`.
+pub(crate) struct InvariantArgs {
+    /// The invariant (string literal or bare expression).
+    pub(crate) expr: Expr,
+    /// The check group, overriding the field's default.
+    pub(crate) group: Option<LitStr>,
+    /// A human-readable label reported when the invariant is violated.
+    pub(crate) name: Option<LitStr>,
+}
+
+impl Parse for InvariantArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut expr = None;
+        let mut group = None;
+        let mut name = None;
+        let mut when: Option<LitStr> = None;
+        let mut then: Option<LitStr> = None;
+
+        // A leading `name =`, `expr =` or `when =` selects the keyed form
+        let keyed = input
+            .fork()
+            .parse::<syn::Ident>()
+            .is_ok_and(|key| key == "name" || key == "expr" || key == "when")
+            && input.peek2(Token![=])
+            && !input.peek2(Token![==]);
+        // Options are comma-separated; anything else is left for `parse_args` to reject
+        let mut more = keyed;
+        if !keyed {
+            // Quantified, unrolled, conditional and membership invariants aren't Rust, so
+            // they are carried as written and parsed like an invariant string
+            let span = input.span();
+            let written = |text: String| {
+                Expr::Lit(syn::ExprLit {
+                    attrs: Vec::new(),
+                    lit: syn::Lit::Str(LitStr::new(&text, span)),
+                })
+            };
+            expr = Some(if Forall::peek(input) {
+                written(input.parse::<Forall>()?.text())
+            } else {
+                let when: Clause = input.parse()?;
+                if input.peek(Token![for]) {
+                    written(ForEach::parse_after(when.desugar(), input)?.text())
+                } else if input.parse::<Option<Token![=>]>>()?.is_some() {
+                    let then: Clause = input.parse()?;
+                    written(crate::render(&quote! { #when => #then }))
+                } else if when.range.is_some() {
+                    written(crate::render(&when))
+                } else {
+                    when.desugar()
+                }
+            });
+            more = input.parse::<Option<Token![,]>>()?.is_some();
+        }
+
+        while more && !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if key == "group" {
+                group = Some(input.parse()?);
+            } else if key == "name" {
+                name = Some(input.parse()?);
+            } else if key == "expr" && expr.is_none() {
+                expr = Some(input.parse()?);
+            } else if key == "when" && when.is_none() {
+                when = Some(input.parse()?);
+            } else if key == "then" && then.is_none() {
+                then = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(key, "unsupported invariant option"));
+            }
+            more = input.parse::<Option<Token![,]>>()?.is_some();
+        }
+
+        // A conditional invariant is carried as the implication string `when => then`
+        match (when, then, &expr) {
+            (Some(when), Some(then), None) => {
+                let text = format!("{} => {}", when.value(), then.value());
+                expr = Some(Expr::Lit(syn::ExprLit {
+                    attrs: Vec::new(),
+                    lit: syn::Lit::Str(LitStr::new(&text, when.span())),
+                }));
+            }
+            (None, None, _) => {}
+            (Some(when), Some(_), Some(_)) => {
+                return Err(syn::Error::new_spanned(
+                    when,
+                    "`when`/`then` replace `expr` and can't be combined with it",
+                ));
+            }
+            (Some(lit), None, _) | (None, Some(lit), _) => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "`when` and `then` must be given together",
+                ));
+            }
+        }
+
+        let expr =
+            expr.ok_or_else(|| input.error("`#[invariant(...)]` requires `expr = \"...\"`"))?;
+        Ok(InvariantArgs { expr, group, name })
+    }
+}
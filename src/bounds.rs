@@ -0,0 +1,184 @@
+//! Decomposition of invariants into simple per-field integer ranges.
+//!
+//! An invariant is decomposable when it is a conjunction of comparisons between
+//! a `self.field` and an integer literal, e.g.
+//! `self.x >= 0 && self.x <= 100` or `(0..=100).contains(&self.x)`. Anything else
+//! (cross-field relations, disjunctions, method calls) is left alone.
+
+use std::collections::BTreeMap;
+
+use syn::{BinOp, Expr, ExprBinary, Lit, Member, RangeLimits, UnOp};
+
+/// Inclusive bounds on a single field. `None` means unbounded on that side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Range {
+    pub(crate) lo: Option<i128>,
+    pub(crate) hi: Option<i128>,
+}
+
+impl Range {
+    /// Narrows this range by another one (intersection).
+    pub(crate) fn intersect(&mut self, other: Range) {
+        self.lo = match (self.lo, other.lo) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        self.hi = match (self.hi, other.hi) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// Per-field ranges collected from a set of invariants, keyed by field name.
+pub(crate) type FieldConstraints = BTreeMap<String, Range>;
+
+/// Folds the decomposable invariants into per-field ranges.
+///
+/// Invariants that can't be fully decomposed contribute nothing.
+pub(crate) fn field_constraints<'a>(exprs: impl IntoIterator<Item = &'a Expr>) -> FieldConstraints {
+    let mut constraints = FieldConstraints::new();
+    for parts in exprs.into_iter().filter_map(bound_parts) {
+        for (field, range) in parts {
+            constraints.entry(field).or_default().intersect(range);
+        }
+    }
+    constraints
+}
+
+/// Decomposes a single invariant into `(field, range)` parts, or `None` if any
+/// conjunct is not a simple bound.
+pub(crate) fn bound_parts(expr: &Expr) -> Option<Vec<(String, Range)>> {
+    let mut parts = Vec::new();
+    decompose(expr, &mut parts).then_some(parts)
+}
+
+/// Decomposes an expression into `(field, range)` parts, returning `false` if
+/// any conjunct is not a simple bound.
+fn decompose(expr: &Expr, parts: &mut Vec<(String, Range)>) -> bool {
+    match expr {
+        Expr::Paren(paren) => decompose(&paren.expr, parts),
+        Expr::Binary(ExprBinary {
+            left,
+            op: BinOp::And(_),
+            right,
+            ..
+        }) => decompose(left, parts) && decompose(right, parts),
+        Expr::Binary(binary) => match comparison_bound(binary) {
+            Some(part) => {
+                parts.push(part);
+                true
+            }
+            None => false,
+        },
+        Expr::MethodCall(call) if call.method == "contains" && call.args.len() == 1 => {
+            match (range_literal(&call.receiver), &call.args[0]) {
+                (Some(range), Expr::Reference(reference)) => match self_field(&reference.expr) {
+                    Some(field) => {
+                        parts.push((crate::member_name(field), range));
+                        true
+                    }
+                    None => false,
+                },
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Extracts a bound from `self.field <op> literal` (or the mirrored form).
+fn comparison_bound(binary: &ExprBinary) -> Option<(String, Range)> {
+    let (field, value, op) = match (self_field(&binary.left), int_literal(&binary.right)) {
+        (Some(field), Some(value)) => (field, value, binary.op),
+        _ => {
+            let field = self_field(&binary.right)?;
+            let value = int_literal(&binary.left)?;
+            (field, value, mirror(binary.op)?)
+        }
+    };
+
+    let range = match op {
+        BinOp::Ge(_) => Range {
+            lo: Some(value),
+            hi: None,
+        },
+        BinOp::Gt(_) => Range {
+            lo: Some(value.checked_add(1)?),
+            hi: None,
+        },
+        BinOp::Le(_) => Range {
+            lo: None,
+            hi: Some(value),
+        },
+        BinOp::Lt(_) => Range {
+            lo: None,
+            hi: Some(value.checked_sub(1)?),
+        },
+        BinOp::Eq(_) => Range {
+            lo: Some(value),
+            hi: Some(value),
+        },
+        _ => return None,
+    };
+    Some((crate::member_name(field), range))
+}
+
+/// Flips a comparison so that the field is on the left-hand side.
+fn mirror(op: BinOp) -> Option<BinOp> {
+    Some(match op {
+        BinOp::Ge(_) => BinOp::Le(Default::default()),
+        BinOp::Gt(_) => BinOp::Lt(Default::default()),
+        BinOp::Le(_) => BinOp::Ge(Default::default()),
+        BinOp::Lt(_) => BinOp::Gt(Default::default()),
+        BinOp::Eq(_) => BinOp::Eq(Default::default()),
+        _ => return None,
+    })
+}
+
+/// Parses a literal range such as `0..=100` or `(1..10)` into inclusive bounds.
+pub(crate) fn range_literal(expr: &Expr) -> Option<Range> {
+    match expr {
+        Expr::Paren(paren) => range_literal(&paren.expr),
+        Expr::Range(range) => {
+            let lo = match &range.start {
+                Some(start) => Some(int_literal(start)?),
+                None => None,
+            };
+            let hi = match (&range.end, range.limits) {
+                (Some(end), RangeLimits::Closed(_)) => Some(int_literal(end)?),
+                (Some(end), RangeLimits::HalfOpen(_)) => Some(int_literal(end)?.checked_sub(1)?),
+                (None, _) => None,
+            };
+            Some(Range { lo, hi })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the field for a `self.field` (or tuple struct `self.0`) expression.
+pub(crate) fn self_field(expr: &Expr) -> Option<&Member> {
+    match expr {
+        Expr::Paren(paren) => self_field(&paren.expr),
+        Expr::Field(field) => match &*field.base {
+            Expr::Path(base) if base.path.is_ident("self") => Some(&field.member),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Evaluates an integer literal, including a negated one.
+pub(crate) fn int_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Paren(paren) => int_literal(&paren.expr),
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            int_literal(&unary.expr)?.checked_neg()
+        }
+        _ => None,
+    }
+}
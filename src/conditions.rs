@@ -0,0 +1,174 @@
+//! Expansion of `#[contract]` on functions.
+//!
+//! `#[requires(...)]` conditions are checked on entry and `#[ensures(...)]`
+//! conditions on return, with the body's return value bound to `result`. The
+//! function's return type `T` becomes `Result<T, ConstitutionError>`, and a
+//! failed condition is returned as a `PreconditionViolation` or
+//! `PostconditionViolation` naming the function and the condition.
+//!
+//! The body runs inside a closure, so an early `return` still passes through
+//! the postconditions. Conditions are validated with the prover's
+//! `ExpressionParser` at compile time, reading parameters (and `result`) as
+//! the parser's `self.<name>` operands. Conditions calling functions are
+//! checked at runtime without validation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Expr, ItemFn, ReturnType, parse_quote};
+
+use crate::{pseudo, render};
+
+/// A parsed `#[requires(...)]` or `#[ensures(...)]` condition.
+struct Condition {
+    /// The condition as written, used in error messages.
+    expr_str: String,
+    /// The condition evaluated at runtime.
+    expr: Expr,
+}
+
+/// Wraps the function so its conditions are checked on entry and return.
+pub(crate) fn expand(mut item: ItemFn) -> syn::Result<TokenStream> {
+    if let Some(asyncness) = &item.sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`#[contract]` does not support async functions yet",
+        ));
+    }
+
+    let mut requires = Vec::new();
+    let mut ensures = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    item.attrs.retain(|attr| {
+        let target = if attr.path().is_ident("requires") {
+            &mut requires
+        } else if attr.path().is_ident("ensures") {
+            &mut ensures
+        } else {
+            return true;
+        };
+        match attr
+            .parse_args::<Expr>()
+            .and_then(|expr| parse_condition(&expr))
+        {
+            Ok(condition) => target.push(condition),
+            Err(e) => match &mut errors {
+                Some(existing) => existing.combine(e),
+                None => errors = Some(e),
+            },
+        }
+        false
+    });
+    if let Some(e) = errors {
+        return Err(e);
+    }
+    if requires.is_empty() && ensures.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.sig.ident,
+            "`#[contract]` requires at least one `#[requires(...)]` or `#[ensures(...)]`",
+        ));
+    }
+
+    let function = item.sig.ident.to_string();
+    let preconditions = requires.iter().map(|condition| {
+        let expr = &condition.expr;
+        let expr_str = &condition.expr_str;
+        quote! {
+            if !(#expr) {
+                return Err(praborrow_core::ConstitutionError::PreconditionViolation {
+                    function: #function.to_string(),
+                    expression: #expr_str.to_string(),
+                });
+            }
+        }
+    });
+    let postconditions = ensures.iter().map(|condition| {
+        let expr = &condition.expr;
+        let expr_str = &condition.expr_str;
+        quote! {
+            if !(#expr) {
+                return Err(praborrow_core::ConstitutionError::PostconditionViolation {
+                    function: #function.to_string(),
+                    expression: #expr_str.to_string(),
+                });
+            }
+        }
+    });
+
+    let output: syn::Type = match &item.sig.output {
+        ReturnType::Default => parse_quote! { () },
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    item.sig.output = parse_quote! { -> Result<#output, praborrow_core::ConstitutionError> };
+
+    let body = &item.block;
+    let attrs = &item.attrs;
+    let vis = &item.vis;
+    let sig = &item.sig;
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #(#preconditions)*
+            #[allow(clippy::redundant_closure_call)]
+            let result = (|| -> #output #body)();
+            #(#postconditions)*
+            Ok(result)
+        }
+    })
+}
+
+/// Parses a condition given either as a string literal or as a bare
+/// expression, validating it with the prover's parser.
+fn parse_condition(expr: &Expr) -> syn::Result<Condition> {
+    let (expr_str, condition) = match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => match syn::parse_str::<Expr>(&lit_str.value()) {
+            Ok(condition) => (lit_str.value(), condition),
+            Err(err) => {
+                return Err(syn::Error::new_spanned(
+                    lit_str,
+                    format!("Syntax error in condition string: {}", err),
+                ));
+            }
+        },
+        _ => (render(expr), expr.clone()),
+    };
+
+    // User functions are opaque to the parser, as they are to the solver
+    if pseudo::uninterpreted_call(&condition, &[]).is_none() {
+        let mut operands = condition.clone();
+        SelfOperands.visit_expr_mut(&mut operands);
+        if let Err(e) = praborrow_prover::parser::ExpressionParser::parse(&render(&operands)) {
+            return Err(syn::Error::new_spanned(
+                expr,
+                format!("Invalid condition syntax: {}", e),
+            ));
+        }
+    }
+
+    Ok(Condition {
+        expr_str,
+        expr: condition,
+    })
+}
+
+/// Rewrites bare identifiers such as `x` to `self.x`, the operand form the
+/// prover's parser accepts.
+pub(crate) struct SelfOperands;
+
+impl VisitMut for SelfOperands {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if let Expr::Path(path) = node
+            && path.qself.is_none()
+            && let Some(ident) = path.path.get_ident()
+            && ident != "self"
+        {
+            let ident = ident.clone();
+            *node = parse_quote! { self.#ident };
+            return;
+        }
+        visit_mut::visit_expr_mut(self, node);
+    }
+}
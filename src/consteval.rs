@@ -0,0 +1,43 @@
+//! Detection of invariants that can be evaluated in a `const fn`.
+//!
+//! The const-evaluable subset is:
+//!
+//! - `self.field` access on integer, `bool` and `char` fields
+//! - integer, `bool` and `char` literals, and paths to constants (`N`, `Self::MAX`)
+//! - comparisons, integer arithmetic, bitwise and logical operators
+//! - unary `-` and `!`, parentheses and `as` casts
+//!
+//! Method and function calls are never const-evaluable here, even when the
+//! callee happens to be a `const fn`.
+
+use syn::{Expr, Lit, Member, Type, UnOp};
+
+use crate::bounds::self_field;
+
+/// Checks whether an invariant only uses the const-evaluable subset.
+pub(crate) fn is_const_evaluable(expr: &Expr, fields: &[(Member, Type)]) -> bool {
+    match expr {
+        Expr::Paren(paren) => is_const_evaluable(&paren.expr, fields),
+        Expr::Group(group) => is_const_evaluable(&group.expr, fields),
+        Expr::Binary(binary) => {
+            is_const_evaluable(&binary.left, fields) && is_const_evaluable(&binary.right, fields)
+        }
+        Expr::Unary(unary) => {
+            matches!(unary.op, UnOp::Neg(_) | UnOp::Not(_))
+                && is_const_evaluable(&unary.expr, fields)
+        }
+        Expr::Cast(cast) => is_const_evaluable(&cast.expr, fields),
+        Expr::Lit(lit) => matches!(lit.lit, Lit::Int(_) | Lit::Bool(_) | Lit::Char(_)),
+        Expr::Path(path) => path.qself.is_none() && !path.path.is_ident("self"),
+        Expr::Field(_) => match self_field(expr) {
+            Some(name) => fields.iter().any(|(field, ty)| {
+                field == name
+                    && (crate::is_integer_type(ty)
+                        || crate::is_char_type(ty)
+                        || crate::is_bool_type(ty))
+            }),
+            None => false,
+        },
+        _ => false,
+    }
+}
@@ -1,7 +1,9 @@
 //! Procedural macros for invariant verification.
 //!
 //! Provides `#[derive(Constitution)]` macro that generates:
-//! 1. **Runtime checks** via `CheckProtocol::enforce_law()` - panics on violation
+//! 1. **Runtime checks** via `CheckProtocol::enforce_law()` - returns the violation
+//!    as a `ConstitutionError`; `#[constitution(panic)]` adds a panicking
+//!    `enforce_law_or_panic()`
 //! 2. **Formal verification** via `FormallyVerifiable::verify_integrity()` - SMT-based proof
 //!
 //! # Example
@@ -18,8 +20,8 @@
 //!
 //! let v = BoundedValue { value: 10 };
 //!
-//! // Runtime check (panics if violated)
-//! v.enforce_law();
+//! // Runtime check (returns Err if violated)
+//! v.enforce_law()?;
 //!
 //! // Formal verification (returns Result)
 //! let token: Result<VerificationToken, ProofError> = v.verify_integrity();
@@ -27,219 +29,2800 @@
 //!
 //! # Generated Code
 //!
-//! For each struct with `#[derive(Constitution)]`, the macro generates:
+//! For each struct or enum with `#[derive(Constitution)]`, the macro generates:
 //!
-//! - `impl CheckProtocol` with `enforce_law()` - runtime panic checks
+//! - `impl CheckProtocol` with `enforce_law()` - runtime checks returning
+//!   `Result<(), ConstitutionError>`
 //! - `impl FormallyVerifiable` with:
-//!   - `INVARIANTS: &'static [&'static str]` - the invariant expressions
 //!   - `verify_integrity()` - SMT-based proof returning `Result<VerificationToken, ProofError>`
 //!   - `field_values()` - returns field name/value pairs for SMT solver
+//!
+//! Further methods are opt-in through `#[constitution(...)]` options. The
+//! README describes each option and what the solver sees of each field type.
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Data, DeriveInput, Ident, LitStr, Member, Meta, Type, parse_macro_input};
+
+mod attrs;
+mod bounds;
+mod conditions;
+mod consteval;
+mod lowering;
+mod pseudo;
+mod relation;
+mod spans;
+mod variants;
+mod widen;
+
+/// Information about a field with invariants.
+struct FieldInfo {
+    name: Member,
+    ty: Type,
+    /// Indices into the struct's invariant list.
+    invariants: Vec<usize>,
+}
+
+/// Returns the last path segment of a type, e.g. `u32` or `String`.
+fn type_ident(ty: &Type) -> Option<String> {
+    if let Type::Path(type_path) = ty {
+        return type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string());
+    }
+    None
+}
+
+/// Returns the name a field goes by in strings and solver keys: its identifier,
+/// or its index for a tuple struct field.
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(name) => name.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// How the solver sees a scalar field's type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    /// A signed integer, `i8` through `i128` and `isize`.
+    Int,
+    /// An unsigned integer, `u8` through `u128` and `usize`.
+    UInt,
+    /// `f32` or `f64`, seen as a real.
+    Real,
+    /// `bool`.
+    Bool,
+    /// `char`, seen as its unsigned codepoint.
+    Char,
+    /// Anything else. Strings, vectors, options and arrays have their own
+    /// encodings; other types are invisible to the solver.
+    Unsupported,
+}
+
+/// Classifies a field's type by how the solver sees it.
+fn classify_field_type(ty: &Type) -> FieldKind {
+    match type_ident(ty).as_deref() {
+        Some("i8" | "i16" | "i32" | "i64" | "i128" | "isize") => FieldKind::Int,
+        Some("u8" | "u16" | "u32" | "u64" | "u128" | "usize") => FieldKind::UInt,
+        Some("f32" | "f64") => FieldKind::Real,
+        Some("bool") => FieldKind::Bool,
+        Some("char") => FieldKind::Char,
+        _ => FieldKind::Unsupported,
+    }
+}
+
+/// Checks if a type is a supported integer type.
+fn is_integer_type(ty: &Type) -> bool {
+    matches!(classify_field_type(ty), FieldKind::Int | FieldKind::UInt)
+}
+
+/// Checks if a type is `char`, which the solver sees as its codepoint.
+fn is_char_type(ty: &Type) -> bool {
+    classify_field_type(ty) == FieldKind::Char
+}
+
+/// Checks if a type is `bool`.
+fn is_bool_type(ty: &Type) -> bool {
+    classify_field_type(ty) == FieldKind::Bool
+}
+
+/// Checks if a type is `String` or `&str`, which the solver sees as a string
+/// and by its length.
+fn is_string_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => type_ident(&reference.elem).as_deref() == Some("str"),
+        _ => type_ident(ty).as_deref() == Some("String"),
+    }
+}
+
+/// Checks if a type is a `Vec` or a slice reference such as `&'a [T]`, which
+/// the solver sees by its length alone.
+fn is_vec_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => matches!(&*reference.elem, Type::Slice(_)),
+        _ => type_ident(ty).as_deref() == Some("Vec"),
+    }
+}
+
+/// Checks if a type is `praborrow_core::LawCache`, the storage for
+/// `#[constitution(memoize)]`.
+fn is_law_cache(ty: &Type) -> bool {
+    type_ident(ty).as_deref() == Some("LawCache")
+}
+
+/// Splits the fields a generated constructor takes as input from its
+/// `LawCache` fields, which start out empty. Returns the inputs along with the
+/// initializers for the caches.
+fn split_caches(fields: &[(Member, Type)]) -> (Vec<(Member, Type)>, proc_macro2::TokenStream) {
+    let (caches, inputs): (Vec<_>, Vec<_>) =
+        fields.iter().cloned().partition(|(_, ty)| is_law_cache(ty));
+    let caches = caches.iter().map(|(field, _)| field);
+    (inputs, quote! { #(#caches: Default::default(),)* })
+}
+
+/// Checks if a type is `f32` or `f64`, which the solver sees as a real.
+fn is_float_type(ty: &Type) -> bool {
+    classify_field_type(ty) == FieldKind::Real
+}
+
+/// Returns `T` for an `Option<T>` type.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the element type of a `Vec<T>` or `&[T]`.
+fn vec_elem(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Slice(slice) => Some(&slice.elem),
+            _ => None,
+        },
+        Type::Path(type_path) => {
+            let segment = type_path.path.segments.last()?;
+            if segment.ident != "Vec" {
+                return None;
+            }
+            match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match &args.args[0] {
+                        syn::GenericArgument::Type(elem) => Some(elem),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Checks if a field's value can be rendered in a `FieldReport` via `ToString`.
+fn is_reportable_type(ty: &Type) -> bool {
+    classify_field_type(ty) != FieldKind::Unsupported || is_string_type(ty)
+}
+
+/// Checks if an integer type is unsigned.
+fn is_unsigned_type(ty: &Type) -> bool {
+    classify_field_type(ty) == FieldKind::UInt
+}
+
+/// Generates the `FieldValue` for an integer-typed place expression.
+///
+/// 128-bit integers take the wide variants; casting them to 64 bits would
+/// hand the solver a truncated value. With `bitvector`, the value is handed
+/// over as a bitvector of the type's width instead, so the solver's
+/// arithmetic wraps exactly where Rust's does.
+fn int_field_value(
+    ty: &Type,
+    place: proc_macro2::TokenStream,
+    bitvector: bool,
+) -> proc_macro2::TokenStream {
+    if bitvector {
+        let signed = !is_unsigned_type(ty);
+        return quote! {
+            FieldValue::BitVec {
+                bits: #place as u128,
+                width: <#ty>::BITS,
+                signed: #signed,
+            }
+        };
+    }
+    match type_ident(ty).as_deref() {
+        Some("i128") => quote! { FieldValue::Int128(#place) },
+        Some("u128") => quote! { FieldValue::UInt128(#place) },
+        _ if is_unsigned_type(ty) => quote! { FieldValue::UInt(#place as u64) },
+        _ => quote! { FieldValue::Int(#place as i64) },
+    }
+}
+
+/// Generates the `FieldValue` for an integer field narrowed with `bits = N`:
+/// a bitvector of `width` bits, so the solver reasons within the field's
+/// logical width. A value using higher bits would be truncated, so it fails
+/// the proof instead.
+fn narrowed_field_value(
+    ty: &Type,
+    place: proc_macro2::TokenStream,
+    width: u32,
+    name: &str,
+) -> proc_macro2::TokenStream {
+    let signed = !is_unsigned_type(ty);
+    let (lo, hi) = narrowed_range(signed, width);
+    let (lo, hi) = (
+        proc_macro2::Literal::i128_unsuffixed(lo),
+        proc_macro2::Literal::i128_unsuffixed(hi),
+    );
+    quote! {
+        if (#lo..=#hi).contains(&#place) {
+            FieldValue::BitVec {
+                bits: #place as u128,
+                width: #width,
+                signed: #signed,
+            }
+        } else {
+            return Err(ProofError::InvariantViolated(format!(
+                "self.{} does not fit in {} bits",
+                #name, #width
+            )));
+        }
+    }
+}
+
+/// Returns the values the low `width` bits of an integer hold.
+fn narrowed_range(signed: bool, width: u32) -> (i128, i128) {
+    if signed {
+        (-(1 << (width - 1)), (1 << (width - 1)) - 1)
+    } else {
+        (0, (1 << width) - 1)
+    }
+}
+
+/// Builds the invariant `bits = N` adds to a field, e.g. `self.flags <= 4095`
+/// for 12 bits of a `u32`. Errors unless the field is an integer type wider
+/// than `N` bits.
+fn narrowing_invariant(name: &Member, ty: &Type, bits: &syn::LitInt) -> syn::Result<syn::Expr> {
+    let width = bits.base10_parse::<u32>()?;
+    if !is_integer_type(ty) {
+        return Err(syn::Error::new_spanned(
+            bits,
+            "`bits` requires an integer field",
+        ));
+    }
+    let type_width = match type_ident(ty).as_deref() {
+        Some("i8" | "u8") => 8,
+        Some("i16" | "u16") => 16,
+        Some("i32" | "u32") => 32,
+        Some("i64" | "u64") => 64,
+        // `usize` is at least 16 bits wide, but may be wider than `N`
+        _ => u32::MAX,
+    };
+    if width >= type_width {
+        return Err(syn::Error::new_spanned(
+            bits,
+            "`bits` must be narrower than the field's type",
+        ));
+    }
+    let name = member_name(name);
+    let text = match narrowed_range(!is_unsigned_type(ty), width) {
+        (0, hi) => format!("self.{name} <= {hi}"),
+        (lo, hi) => format!("self.{name} >= {lo} && self.{name} <= {hi}"),
+    };
+    let text = LitStr::new(&text, bits.span());
+    Ok(syn::parse_quote! { #text })
+}
+
+/// Generates the `compute_data_hash` update for a field. Types without an
+/// encoding of their own are hashed through their `Hash` impl, so a field
+/// whose type has neither is a compile error until marked `skip_hash`.
+/// `contents` hashes the elements of a `Vec` or slice, not just its length.
+fn hash_update(
+    ty: &Type,
+    place: proc_macro2::TokenStream,
+    contents: bool,
+) -> proc_macro2::TokenStream {
+    match classify_field_type(ty) {
+        FieldKind::Int | FieldKind::UInt => return quote! { hasher.update(&#place.to_le_bytes()); },
+        FieldKind::Char => return quote! { hasher.update(&u32::from(#place).to_le_bytes()); },
+        FieldKind::Real => return quote! { hasher.update(&#place.to_bits().to_le_bytes()); },
+        FieldKind::Bool => return quote! { hasher.update(&[u8::from(#place)]); },
+        FieldKind::Unsupported => {}
+    }
+    if is_string_type(ty) {
+        // Length-prefixed so adjacent strings can't run together
+        quote! {
+            hasher.update(&(#place.len() as u64).to_le_bytes());
+            hasher.update(#place.as_bytes());
+        }
+    } else if is_vec_type(ty) {
+        // The solver only sees the length, so that is all a proof depends on,
+        // but a memoized result also covers runtime checks of the elements
+        let length = quote! { hasher.update(&(#place.len() as u64).to_le_bytes()); };
+        match vec_elem(ty).filter(|_| contents) {
+            Some(elem) => {
+                let update = hash_update(elem, quote! { (*element) }, contents);
+                quote! {
+                    #length
+                    for element in #place.iter() {
+                        #update
+                    }
+                }
+            }
+            None => length,
+        }
+    } else if let Some((elem, _)) = lowering::fixed_array(ty) {
+        // The length is fixed by the type, so elements are hashed back to back
+        let update = hash_update(elem, quote! { (*element) }, contents);
+        quote! {
+            for element in #place.iter() {
+                #update
+            }
+        }
+    } else if let Some(inner) = option_inner(ty) {
+        // Tagged, so `None` differs from `Some` of any value
+        let update = hash_update(inner, quote! { (*inner) }, contents);
+        quote! {
+            match &#place {
+                Some(inner) => {
+                    hasher.update(&[1]);
+                    #update
+                }
+                None => hasher.update(&[0]),
+            }
+        }
+    } else {
+        // Spanned so a missing `Hash` impl points at the field's type
+        quote_spanned! {syn::spanned::Spanned::span(ty)=>
+            praborrow_prover::hash_into(&mut hasher, &#place);
+        }
+    }
+}
+
+/// Generates the field provider's match arms for the fields the solver can
+/// see directly, reading each field through `place`. `bitvector` selects how
+/// integers are handed over, see `int_field_value`; as bitvectors, the fields
+/// in `bit_widths` take their `bits = N` width instead of their type's.
+fn provider_arms(
+    fields: &[(Member, Type)],
+    place: impl Fn(&Member) -> proc_macro2::TokenStream,
+    bitvector: bool,
+    bit_widths: &[(Member, u32)],
+) -> Vec<proc_macro2::TokenStream> {
+    let mut arms = Vec::new();
+    for (name, ty) in fields {
+        let name_str = member_name(name);
+        let place = place(name);
+        let bit_width = bit_widths
+            .iter()
+            .find(|(narrowed, _)| narrowed == name)
+            .map(|&(_, width)| width);
+        let value = match classify_field_type(ty) {
+            FieldKind::Int | FieldKind::UInt => Some(match bit_width {
+                Some(width) if bitvector => {
+                    narrowed_field_value(ty, place.clone(), width, &name_str)
+                }
+                _ => int_field_value(ty, place.clone(), bitvector),
+            }),
+            // Chars are exposed to the solver as their unsigned codepoint
+            FieldKind::Char => Some(quote! { FieldValue::UInt(#place as u64) }),
+            FieldKind::Bool => Some(quote! { FieldValue::Bool(#place) }),
+            // Floats are exposed to the solver as reals
+            FieldKind::Real => Some(quote! { FieldValue::Real(#place as f64) }),
+            FieldKind::Unsupported => None,
+        };
+        let value = if let Some(value) = value {
+            value
+        } else if is_string_type(ty) {
+            // Strings are exposed to the solver as themselves and by their
+            // length, as `name.len`
+            let symbol = format!("{}.len", name_str);
+            arms.push(quote! {
+                #symbol => {
+                    Ok(FieldValue::UInt(#place.len() as u64))
+                }
+                #name_str => {
+                    Ok(FieldValue::Str(#place.to_string()))
+                }
+            });
+            continue;
+        } else if is_vec_type(ty) {
+            // Vectors are exposed by their length alone, as `name.len`
+            let symbol = format!("{}.len", name_str);
+            arms.push(quote! {
+                #symbol => {
+                    Ok(FieldValue::UInt(#place.len() as u64))
+                }
+            });
+            continue;
+        } else if let Some(inner) = option_inner(ty)
+            && is_integer_type(inner)
+        {
+            // Optional integers are exposed as `name.is_some` plus the inner
+            // value, which reads as 0 when absent
+            let symbol = format!("{}.is_some", name_str);
+            let value = int_field_value(inner, quote! { #place.unwrap_or(0) }, bitvector);
+            arms.push(quote! {
+                #symbol => {
+                    Ok(FieldValue::Bool(#place.is_some()))
+                }
+                #name_str => {
+                    Ok(#value)
+                }
+            });
+            continue;
+        } else if option_inner(ty).is_some() {
+            // Other options are exposed by their presence alone
+            let symbol = format!("{}.is_some", name_str);
+            arms.push(quote! {
+                #symbol => {
+                    Ok(FieldValue::Bool(#place.is_some()))
+                }
+            });
+            continue;
+        } else if let Some((elem, len)) = lowering::fixed_array(ty)
+            && is_integer_type(elem)
+        {
+            // Elements of fixed-size integer arrays are exposed as `field__<index>`
+            for index in 0..len {
+                let symbol = lowering::element_symbol(name, index);
+                let value = int_field_value(elem, quote! { #place[#index] }, bitvector);
+                arms.push(quote! {
+                    #symbol => {
+                        Ok(#value)
+                    }
+                });
+            }
+            continue;
+        } else {
+            continue;
+        };
+        arms.push(quote! {
+            #name_str => {
+                Ok(#value)
+            }
+        });
+    }
+    arms
+}
+
+/// Returns the type parameter a field's type names, if the type is a bare
+/// parameter such as `T`, and whether it is bounded by `Into<i64> + Copy`,
+/// which makes the field readable as an `i64`.
+fn numeric_param<'a>(generics: &'a syn::Generics, ty: &Type) -> Option<(&'a Ident, bool)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let ident = path.path.get_ident().filter(|_| path.qself.is_none())?;
+    let param = generics.type_params().find(|param| param.ident == *ident)?;
+    let where_bounds = generics
+        .where_clause
+        .iter()
+        .flat_map(|clause| &clause.predicates)
+        .filter_map(|predicate| match predicate {
+            syn::WherePredicate::Type(predicate)
+                if matches!(&predicate.bounded_ty, Type::Path(bounded) if bounded.path.is_ident(ident)) =>
+            {
+                Some(&predicate.bounds)
+            }
+            _ => None,
+        })
+        .flatten();
+    let bounds: Vec<_> = param.bounds.iter().chain(where_bounds).collect();
+    // Bounds are matched by their last path segment, so `core::convert::Into<i64>`
+    // counts as well
+    let has_bound = |wanted: &str| {
+        bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => bound
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| render(segment).replace(' ', "") == wanted),
+            _ => false,
+        })
+    };
+    Some((&param.ident, has_bound("Into<i64>") && has_bound("Copy")))
+}
+
+/// Rewrites every `self.<field>` of the given fields into
+/// `Into::<i64>::into(self.<field>)`, so runtime checks compare generic
+/// numeric fields the way the solver does.
+fn read_as_i64(expr: &mut syn::Expr, fields: &[&Member]) {
+    struct Reader<'a>(&'a [&'a Member]);
+
+    impl syn::visit_mut::VisitMut for Reader<'_> {
+        fn visit_expr_mut(&mut self, node: &mut syn::Expr) {
+            if bounds::self_field(node).is_some_and(|name| self.0.contains(&name)) {
+                *node = syn::parse_quote! { core::convert::Into::<i64>::into(#node) };
+                return;
+            }
+            syn::visit_mut::visit_expr_mut(self, node);
+        }
+    }
+
+    syn::visit_mut::VisitMut::visit_expr_mut(&mut Reader(fields), expr);
+}
+
+/// Checks if an expression reads `self.<field>`.
+fn reads_field(expr: &syn::Expr, field: &Member) -> bool {
+    struct Finder<'a>(&'a Member, bool);
+
+    impl<'ast> syn::visit::Visit<'ast> for Finder<'_> {
+        fn visit_expr(&mut self, node: &'ast syn::Expr) {
+            self.1 |= bounds::self_field(node) == Some(self.0);
+            syn::visit::visit_expr(self, node);
+        }
+    }
+
+    let mut finder = Finder(field, false);
+    syn::visit::Visit::visit_expr(&mut finder, expr);
+    finder.1
+}
+
+/// Generates the field provider's match arms for fields whose type is one of
+/// the struct's `Into<i64> + Copy` type parameters. A field a provable
+/// invariant reads without that bound is rejected, since the solver couldn't
+/// see its value.
+fn generic_field_arms(
+    generics: &syn::Generics,
+    fields: &[(Member, Type)],
+    invariants: &[Invariant],
+    place: impl Fn(&Member) -> proc_macro2::TokenStream,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let mut arms = Vec::new();
+    for (name, ty) in fields {
+        match numeric_param(generics, ty) {
+            Some((_, true)) => {
+                let name_str = member_name(name);
+                let place = place(name);
+                arms.push(quote! {
+                    #name_str => {
+                        Ok(FieldValue::Int(core::convert::Into::<i64>::into(#place)))
+                    }
+                });
+            }
+            Some((param, false))
+                if invariants
+                    .iter()
+                    .any(|inv| inv.smt.is_some() && reads_field(&inv.expr, name)) =>
+            {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "the solver reads generic field `{}` as an `i64`; add the bound `{}: Into<i64> + Copy`",
+                        member_name(name),
+                        param
+                    ),
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(arms)
+}
+
+/// Generates the field provider's match arms for the associated constants the
+/// invariants read. The constant's type is left to `From`.
+fn const_arms<'a>(
+    input: &DeriveInput,
+    exprs: impl IntoIterator<Item = &'a syn::Expr>,
+) -> Vec<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    lowering::associated_consts(exprs)
+        .into_iter()
+        .map(|(symbol, constant)| {
+            quote! {
+                #symbol => {
+                    Ok(FieldValue::from(<#name #ty_generics>::#constant))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns the type's const generic parameters, such as `N` in
+/// `Buf<const N: usize>`.
+fn const_params(generics: &syn::Generics) -> Vec<Ident> {
+    generics
+        .const_params()
+        .map(|param| param.ident.clone())
+        .collect()
+}
+
+/// Generates the field provider's match arms for the const generic parameters
+/// the invariants read, pinned to their values. The parameter's type is left
+/// to `From`.
+fn const_param_arms<'a>(
+    const_params: &[Ident],
+    exprs: impl IntoIterator<Item = &'a syn::Expr>,
+) -> Vec<proc_macro2::TokenStream> {
+    lowering::used_const_params(exprs, const_params)
+        .into_iter()
+        .map(|param| {
+            let symbol = param.to_string();
+            quote! {
+                #symbol => {
+                    Ok(FieldValue::from(#param))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates the field provider's match arms for the `usize`/`isize` bounds
+/// the lowered invariants read, pinned to the target's `MIN`/`MAX`.
+fn pointer_bound_arms<'a>(
+    smt: impl IntoIterator<Item = &'a str>,
+    bitvector: bool,
+) -> Vec<proc_macro2::TokenStream> {
+    lowering::pointer_bounds(smt)
+        .into_iter()
+        .map(|(symbol, ty, bound)| {
+            let value = int_field_value(&ty, quote! { <#ty>::#bound }, bitvector);
+            quote! {
+                #symbol => {
+                    Ok(#value)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns the distinct solver symbols of the compared variants, whose
+/// positions are the indices the solver sees them as.
+pub(crate) fn variant_symbols(compared: &[(Member, syn::Path)]) -> Vec<String> {
+    let mut symbols: Vec<String> = Vec::new();
+    for (_, path) in compared {
+        let symbol = lowering::variant_symbol(path);
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    symbols
+}
+
+/// Generates the field provider's match arms for the variants fields are
+/// compared with, such as `Color::Red` in `self.color != Color::Red`. Each
+/// variant is exposed as its index among those the invariants name.
+fn variant_symbol_arms(symbols: &[String]) -> Vec<proc_macro2::TokenStream> {
+    symbols
+        .iter()
+        .enumerate()
+        .map(|(index, symbol)| {
+            let index = index as u64;
+            quote! {
+                #symbol => {
+                    Ok(FieldValue::UInt(#index))
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates the field provider's match arm for a field compared with its
+/// type's variants. The field is exposed as the index of the variant it
+/// equals, or one past the last if it equals none, which needs the type to
+/// be `PartialEq` just as the runtime check does. `symbols` are the indexed
+/// variants, from `variant_symbols`.
+fn variant_field_arm(
+    field: &Member,
+    compared: &[(Member, syn::Path)],
+    symbols: &[String],
+    place: proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    let tests: Vec<_> = compared
+        .iter()
+        .filter(|(compared, _)| compared == field)
+        .map(|(_, path)| {
+            let symbol = lowering::variant_symbol(path);
+            let index = symbols
+                .iter()
+                .position(|known| *known == symbol)
+                .unwrap_or_default() as u64;
+            quote! { if #place == #path { #index } else }
+        })
+        .collect();
+    if tests.is_empty() {
+        return None;
+    }
+    let name_str = member_name(field);
+    let none = symbols.len() as u64;
+    Some(quote! {
+        #name_str => {
+            Ok(FieldValue::UInt(#(#tests)* { #none }))
+        }
+    })
+}
+
+/// A parsed invariant condition.
+struct Invariant {
+    /// The expression as written, used in error messages.
+    expr_str: String,
+    /// The condition evaluated by the runtime check.
+    expr: syn::Expr,
+    /// The expression handed to the solver, or `None` for runtime-only invariants.
+    smt: Option<String>,
+    /// A compile-time warning emitted alongside the generated code.
+    warning: Option<proc_macro2::TokenStream>,
+    /// The check group the invariant belongs to, if any.
+    group: Option<String>,
+    /// The human-readable label given with `name = "..."`, if any.
+    name: Option<String>,
+    /// Reportable fields the invariant reads, recorded when it is violated.
+    referenced: Vec<Member>,
+}
+
+/// Parses an invariant given either as a string literal or as a bare expression.
+///
+/// `fields` are the struct's fields, used to lower constructs the prover's
+/// parser doesn't understand (see the `lowering` module), and `const_params`
+/// its const generic parameters. Calls to user functions, and to methods the
+/// lowering doesn't model, make the invariant runtime-only with a warning, or
+/// are rejected when `strict` is set.
+fn parse_invariant(
+    expr: &syn::Expr,
+    fields: &[(Member, Type)],
+    const_params: &[Ident],
+    strict: bool,
+) -> syn::Result<Invariant> {
+    // Extract the invariant string and the expression to check at runtime
+    let (expr_str, mut runtime_expr) = if let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit_str),
+        ..
+    }) = expr
+    {
+        let s = lit_str.value();
+        // For string literals, we must parse the content to get tokens for runtime check
+        match syn::parse::Parser::parse_str(attrs::parse_invariant_expr, &s) {
+            Ok(e) => (s, e),
+            Err(err) => {
+                return Err(syn::Error::new_spanned(
+                    lit_str,
+                    format!("Syntax error in invariant string: {}", err),
+                ));
+            }
+        }
+    } else {
+        (render(expr), expr.clone())
+    };
+
+    // Bare field names read as `self.<field>`; the solver sees the prefixed form
+    let prefixed = prefix_bare_fields(&mut runtime_expr, fields);
+
+    // A misspelt field would otherwise only surface when the solver asks for it
+    if let Some(field) = unknown_field(&runtime_expr, fields) {
+        let message = format!("unknown field `{}` in invariant", render(field));
+        return Err(match expr {
+            syn::Expr::Lit(_) => syn::Error::new_spanned(expr, message),
+            _ => syn::Error::new_spanned(field, message),
+        });
+    }
+
+    // User functions are opaque to the solver
+    let mut warning = None;
+    if let Some(call) = pseudo::uninterpreted_call(&runtime_expr, fields) {
+        let message = format!(
+            "invariant `{}` calls a function the solver can't see into and is checked at runtime only",
+            expr_str
+        );
+        if strict {
+            return Err(syn::Error::new_spanned(
+                call,
+                format!("{} (rejected by `strict_parse`)", message),
+            ));
+        }
+        warning = Some(runtime_only_warning(call, &message));
+    }
+
+    // Pseudo-functions (e.g. `matches_regex`) are lowered to plain Rust; those
+    // the solver can't express make the invariant runtime-only
+    let written_expr = runtime_expr.clone();
+    let runtime_only = pseudo::rewrite(&mut runtime_expr, fields)? || warning.is_some();
+
+    let smt = if runtime_only {
+        None
+    } else {
+        let lowered = lowering::lower(&written_expr, fields, const_params)?;
+        let smt = match &lowered {
+            Some(lowered) => render(lowered),
+            None if prefixed => render(&written_expr),
+            None => expr_str.clone(),
+        };
+
+        // Validate invariant syntax at compile time using Prover Parser.
+        // Runtime-only invariants are never handed to the solver.
+        match praborrow_prover::parser::ExpressionParser::parse(&smt) {
+            Ok(_) => Some(smt),
+            Err(e) => {
+                // A method the lowering doesn't know is opaque, like a user function
+                let Some(call) = pseudo::method_call(lowered.as_ref().unwrap_or(&written_expr))
+                else {
+                    let err_msg = format!("Invalid invariant syntax: {}", e);
+                    // Lowered tokens have no source location to point at
+                    let written = lowered.is_none().then_some(&written_expr);
+                    return Err(spans::parse_error(expr, written, &smt, err_msg));
+                };
+                let message = format!(
+                    "invariant `{}` calls `{}`, which the solver can't model, and is checked at runtime only",
+                    expr_str, call.method
+                );
+                if strict {
+                    return Err(syn::Error::new_spanned(
+                        &call.method,
+                        format!("{} (rejected by `strict_parse`)", message),
+                    ));
+                }
+                warning = Some(runtime_only_warning(&call.method, &message));
+                None
+            }
+        }
+    };
+
+    Ok(Invariant {
+        expr_str,
+        referenced: referenced_fields(&runtime_expr, fields),
+        expr: runtime_expr,
+        smt,
+        warning,
+        group: None,
+        name: None,
+    })
+}
+
+/// Returns the reportable fields accessed as `self.<field>` in an expression,
+/// in declaration order.
+fn referenced_fields(expr: &syn::Expr, fields: &[(Member, Type)]) -> Vec<Member> {
+    let read = fields_read(expr, fields);
+    fields
+        .iter()
+        .filter(|(field, ty)| is_reportable_type(ty) && read.contains(field))
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+/// Returns the fields accessed as `self.<field>` in an expression, in
+/// declaration order.
+fn fields_read(expr: &syn::Expr, fields: &[(Member, Type)]) -> Vec<Member> {
+    struct Collector<'a>(Vec<&'a Member>, &'a [(Member, Type)]);
+
+    impl<'ast> syn::visit::Visit<'ast> for Collector<'_> {
+        fn visit_expr(&mut self, node: &'ast syn::Expr) {
+            if let Some(name) = bounds::self_field(node)
+                && let Some((field, _)) = self.1.iter().find(|(field, _)| field == name)
+            {
+                self.0.push(field);
+            }
+            syn::visit::visit_expr(self, node);
+        }
+    }
+
+    let mut collector = Collector(Vec::new(), fields);
+    syn::visit::Visit::visit_expr(&mut collector, expr);
+    fields
+        .iter()
+        .filter(|(field, _)| collector.0.contains(&field))
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+/// Generates the hidden constants `assert_invariants_equivalent!` compares two
+/// types by: every field paired with whether a solver invariant reads it, and
+/// the runtime-only invariants, which the solver can't compare.
+fn equivalence_consts<'a>(
+    fields: &[(Member, Type)],
+    invariants: impl IntoIterator<Item = &'a Invariant>,
+) -> proc_macro2::TokenStream {
+    let mut read = Vec::new();
+    let mut runtime_only = Vec::new();
+    for invariant in invariants {
+        match invariant.smt {
+            Some(_) => read.extend(fields_read(&invariant.expr, fields)),
+            None => runtime_only.push(&invariant.expr_str),
+        }
+    }
+    let mut names: Vec<(String, bool)> = Vec::new();
+    for (field, _) in fields {
+        let name = member_name(field);
+        let is_read = read.contains(field);
+        match names.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, known_read)) => *known_read |= is_read,
+            None => names.push((name, is_read)),
+        }
+    }
+    let (names, read): (Vec<_>, Vec<_>) = names.into_iter().unzip();
+    quote! {
+        #[doc(hidden)]
+        pub const __CONSTITUTION_FIELDS: &'static [(&'static str, bool)] = &[#((#names, #read)),*];
+
+        #[doc(hidden)]
+        pub const __CONSTITUTION_RUNTIME_ONLY: &'static [&'static str] = &[#(#runtime_only),*];
+    }
+}
+
+/// Rewrites bare identifiers naming one of `fields`, as in `start <= end`, to
+/// `self.start <= self.end`. Closure parameters shadow fields of the same
+/// name. Returns whether anything was rewritten.
+fn prefix_bare_fields(expr: &mut syn::Expr, fields: &[(Member, Type)]) -> bool {
+    struct Prefixer<'f> {
+        fields: &'f [(Member, Type)],
+        shadowed: Vec<Ident>,
+        rewritten: bool,
+    }
+
+    impl syn::visit_mut::VisitMut for Prefixer<'_> {
+        fn visit_expr_mut(&mut self, node: &mut syn::Expr) {
+            if let syn::Expr::Path(path) = node
+                && path.qself.is_none()
+                && let Some(ident) = path.path.get_ident()
+                && !self.shadowed.contains(ident)
+                && self
+                    .fields
+                    .iter()
+                    .any(|(field, _)| matches!(field, Member::Named(name) if name == ident))
+            {
+                let ident = ident.clone();
+                *node = syn::parse_quote! { self.#ident };
+                self.rewritten = true;
+                return;
+            }
+            syn::visit_mut::visit_expr_mut(self, node);
+        }
+
+        fn visit_expr_call_mut(&mut self, call: &mut syn::ExprCall) {
+            // A function named like a field is still the function
+            for arg in &mut call.args {
+                self.visit_expr_mut(arg);
+            }
+        }
+
+        fn visit_expr_closure_mut(&mut self, closure: &mut syn::ExprClosure) {
+            let depth = self.shadowed.len();
+            for input in &closure.inputs {
+                let pat = match input {
+                    syn::Pat::Type(typed) => &*typed.pat,
+                    pat => pat,
+                };
+                if let syn::Pat::Ident(pat) = pat {
+                    self.shadowed.push(pat.ident.clone());
+                }
+            }
+            syn::visit_mut::visit_expr_closure_mut(self, closure);
+            self.shadowed.truncate(depth);
+        }
+    }
+
+    let mut prefixer = Prefixer {
+        fields,
+        shadowed: Vec::new(),
+        rewritten: false,
+    };
+    syn::visit_mut::VisitMut::visit_expr_mut(&mut prefixer, expr);
+    prefixer.rewritten
+}
+
+/// Returns the first `self.<field>` access naming a field that doesn't exist.
+/// Only the base field is checked, so `self.value.abs()` and
+/// `self.limits.max` are accepted as long as `value` and `limits` exist.
+fn unknown_field<'a>(expr: &'a syn::Expr, fields: &[(Member, Type)]) -> Option<&'a syn::Expr> {
+    struct Finder<'ast, 'f>(Option<&'ast syn::Expr>, &'f [(Member, Type)]);
+
+    impl<'ast> syn::visit::Visit<'ast> for Finder<'ast, '_> {
+        fn visit_expr(&mut self, node: &'ast syn::Expr) {
+            if self.0.is_some() {
+                return;
+            }
+            match bounds::self_field(node) {
+                Some(name) if !self.1.iter().any(|(field, _)| field == name) => {
+                    self.0 = Some(node);
+                }
+                _ => syn::visit::visit_expr(self, node),
+            }
+        }
+    }
+
+    let mut finder = Finder(None, fields);
+    syn::visit::Visit::visit_expr(&mut finder, expr);
+    finder.0
+}
+
+/// Emits a compile-time warning at `span` on stable Rust by referencing a
+/// deprecated item.
+fn runtime_only_warning(span: impl quote::ToTokens, message: &str) -> proc_macro2::TokenStream {
+    let ident = Ident::new(
+        "invariant_is_runtime_only",
+        syn::spanned::Spanned::span(&span),
+    );
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #ident: () = ();
+            #ident
+        };
+    }
+}
+
+/// Renders tokens as a single-line string. The compiler wraps long token
+/// streams across lines, which would leak into invariant strings.
+fn render(tokens: &impl quote::ToTokens) -> String {
+    tokens.to_token_stream().to_string().replace('\n', " ")
+}
+
+/// Generates the early-returning runtime check for an invariant.
+fn runtime_check(invariant: &Invariant) -> proc_macro2::TokenStream {
+    let condition_tokens = &invariant.expr;
+    let violation = violation(invariant);
+    quote! {
+        if !(#condition_tokens) {
+            return Err(#violation);
+        }
+    }
+}
+
+/// Joins boolean conditions with `&&`, or yields `true` for none.
+fn conjunction(conditions: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    if conditions.is_empty() {
+        quote! { true }
+    } else {
+        quote! { #((#conditions))&&* }
+    }
+}
+
+/// Generates the runtime check for an invariant that records a violation in
+/// `violations` and carries on.
+fn collecting_check(invariant: &Invariant) -> proc_macro2::TokenStream {
+    let condition_tokens = &invariant.expr;
+    let violation = violation(invariant);
+    quote! {
+        if !(#condition_tokens) {
+            violations.push(#violation);
+        }
+    }
+}
+
+/// Generates the `ConstitutionError` describing a violated invariant.
+fn violation(invariant: &Invariant) -> proc_macro2::TokenStream {
+    let condition_str = &invariant.expr_str;
+    let values = if invariant.referenced.is_empty() {
+        quote! { std::collections::BTreeMap::new() }
+    } else {
+        let names = invariant.referenced.iter().map(member_name);
+        let fields = &invariant.referenced;
+        quote! {
+            {
+                let mut values = std::collections::BTreeMap::new();
+                #(values.insert(#names.to_string(), self.#fields.to_string());)*
+                values
+            }
+        }
+    };
+    let name = match &invariant.name {
+        Some(name) => quote! { Some(#name.to_string()) },
+        None => quote! { None },
+    };
+    quote! {
+        praborrow_core::ConstitutionError::InvariantViolation {
+            name: #name,
+            expression: #condition_str.to_string(),
+            values: #values,
+        }
+    }
+}
+
+/// The type's generics with the `Send + Sync` bound `ProveInvariant` requires,
+/// which generic parameters don't guarantee.
+fn prove_generics(input: &DeriveInput) -> syn::Generics {
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #name #ty_generics: Send + Sync });
+    generics
+}
+
+/// The generics of the nested field provider struct, which must redeclare the
+/// type's generics alongside the borrow lifetime `'__a`.
+fn provider_generics(input: &DeriveInput) -> syn::Generics {
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, syn::parse_quote! { '__a });
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #name #ty_generics: Sync });
+    generics
+}
+
+/// Derives the inherent method name for a type-state bound, e.g.
+/// `T: Active` becomes `enforce_law_when_active`.
+fn state_method_name(bound: &syn::WherePredicate) -> Ident {
+    let mut name = String::from("enforce_law_when");
+    if let syn::WherePredicate::Type(predicate) = bound {
+        for trait_bound in &predicate.bounds {
+            #[allow(clippy::collapsible_if)]
+            if let syn::TypeParamBound::Trait(trait_bound) = trait_bound {
+                if let Some(segment) = trait_bound.path.segments.last() {
+                    name.push('_');
+                    name.push_str(&to_snake_case(&segment.ident.to_string()));
+                }
+            }
+        }
+    }
+    Ident::new(&name, proc_macro2::Span::call_site())
+}
+
+/// Generates `bench_enforce` when the `bench` feature is enabled.
+#[cfg(feature = "bench")]
+fn bench_fn() -> proc_macro2::TokenStream {
+    quote! {
+        /// Times `iters` runs of `enforce_law()` on `sample`.
+        pub fn bench_enforce(iters: u64, sample: &Self) -> std::time::Duration {
+            let start = std::time::Instant::now();
+            for _ in 0..iters {
+                let _ = core::hint::black_box(CheckProtocol::enforce_law(core::hint::black_box(
+                    sample,
+                )));
+            }
+            start.elapsed()
+        }
+    }
+}
+
+#[cfg(not(feature = "bench"))]
+fn bench_fn() -> proc_macro2::TokenStream {
+    quote! {}
+}
+
+/// Generates `verify_integrity_cached` when the `cache` feature is enabled.
+#[cfg(feature = "cache")]
+fn cached_verify_fn(backend: Option<&LitStr>) -> proc_macro2::TokenStream {
+    let verification = match backend {
+        Some(_) => {
+            let context = smt_context(backend);
+            quote! {
+                match #context {
+                    Ok(ctx) => ProveInvariant::verify_with_context(self, &ctx).await,
+                    Err(e) => Err(e),
+                }
+            }
+        }
+        None => quote! { ProveInvariant::verify(self).await },
+    };
+    quote! {
+        /// Verifies like `ProveInvariant::verify()`, but consults `cache` first,
+        /// keyed by the type, `compute_data_hash()` and
+        /// `invariant_expressions()`. The solver only runs on a miss, and its
+        /// verdict is stored for the next call.
+        pub async fn verify_integrity_cached(
+            &self,
+            cache: &praborrow_prover::VerificationCache,
+        ) -> Result<praborrow_prover::VerificationToken, praborrow_prover::ProofError> {
+            use praborrow_prover::{CacheResult, ProveInvariant, VerificationCache};
+
+            let type_name = core::any::type_name::<Self>();
+            let key = VerificationCache::compute_key(
+                type_name,
+                &self.compute_data_hash(),
+                <Self as ProveInvariant>::invariant_expressions(),
+            );
+            match cache.lookup(&key) {
+                CacheResult::Hit => Ok(praborrow_prover::VerificationToken::new()),
+                CacheResult::Failed => Err(praborrow_prover::ProofError::InvariantViolated(
+                    format!("{} failed verification (cached)", type_name),
+                )),
+                CacheResult::Miss => {
+                    let result = #verification;
+                    // A hit reads as an exact proof, so relaxed ones aren't kept
+                    match &result {
+                        Ok(token) if !token.is_exact() => {}
+                        _ => cache.store(key, result.is_ok()),
+                    }
+                    result
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "cache"))]
+fn cached_verify_fn(_backend: Option<&LitStr>) -> proc_macro2::TokenStream {
+    quote! {}
+}
+
+/// Generates the early return that turns `enforce_law()` into a no-op in
+/// release builds when the `debug-only` feature is enabled.
+#[cfg(feature = "debug-only")]
+fn release_skip() -> proc_macro2::TokenStream {
+    quote! {
+        if !cfg!(debug_assertions) {
+            return Ok(());
+        }
+    }
+}
 
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Data, DeriveInput, Fields, Ident, Meta, Type, parse_macro_input};
+#[cfg(not(feature = "debug-only"))]
+fn release_skip() -> proc_macro2::TokenStream {
+    quote! {}
+}
 
-/// Information about a field with invariants.
-#[allow(dead_code)] // Reserved for future Z3 backend integration
-struct FieldInfo {
-    name: Ident,
-    ty: Type,
-    invariants: Vec<String>,
+/// Reads `field` of `base`, as its discriminant if it's a `repr` enum field.
+fn repr_place(
+    repr_fields: &[(Member, Type)],
+    base: proc_macro2::TokenStream,
+    field: &Member,
+) -> proc_macro2::TokenStream {
+    match repr_fields.iter().find(|(repr, _)| repr == field) {
+        Some((_, repr)) => quote! { (#base.#field as #repr) },
+        None => quote! { #base.#field },
+    }
 }
 
-/// Checks if a type is a supported integer type.
-fn is_integer_type(ty: &Type) -> bool {
-    if let Type::Path(type_path) = ty {
-        #[allow(clippy::collapsible_if)]
-        if let Some(segment) = type_path.path.segments.last() {
-            let ident = segment.ident.to_string();
-            return matches!(
-                ident.as_str(),
-                "i8" | "i16"
-                    | "i32"
-                    | "i64"
-                    | "i128"
-                    | "isize"
-                    | "u8"
-                    | "u16"
-                    | "u32"
-                    | "u64"
-                    | "u128"
-                    | "usize"
-            );
+/// Checks whether the struct is declared `#[repr(C)]`.
+fn has_repr_c(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .any(|attr| {
+            let mut repr_c = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                repr_c |= meta.path.is_ident("C");
+                Ok(())
+            });
+            repr_c
+        })
+}
+
+/// Generates the `extern "C"` validator for `#[constitution(c_export)]`.
+///
+/// Returns `0` when every invariant holds, `i + 1` when invariant `i` (in
+/// declaration order) is the first one violated, and `-1` for a null pointer.
+fn c_export_fn(
+    input: &DeriveInput,
+    invariants: &[Invariant],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`c_export` does not support generic structs",
+        ));
+    }
+    if !has_repr_c(&input.attrs) {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`c_export` requires the struct to be `#[repr(C)]`",
+        ));
+    }
+
+    let function = Ident::new(
+        &format!("validate_{}", to_snake_case(&name.to_string())),
+        name.span(),
+    );
+    let checks = invariants.iter().enumerate().map(|(index, inv)| {
+        let condition = &inv.expr;
+        let code = index as i32 + 1;
+        quote! {
+            if !(#condition) {
+                return #code;
+            }
+        }
+    });
+    let doc = format!(
+        "Checks the invariants of a `{}` for C callers.\n\n\
+         Returns `0` if every invariant holds, `i + 1` if invariant `i` (in declaration \
+         order) is the first one violated, or `-1` if `ptr` is null.\n\n\
+         # Safety\n\n\
+         `ptr` must be null or point to a valid, properly aligned `{}`.",
+        name, name
+    );
+
+    Ok(quote! {
+        #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+        impl #name {
+            fn __constitution_violation_code(&self) -> i32 {
+                #(#checks)*
+                0
+            }
+        }
+
+        #[doc = #doc]
+        #[inline]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn #function(ptr: *const #name) -> i32 {
+            // SAFETY: the caller guarantees `ptr` is null or valid
+            match unsafe { ptr.as_ref() } {
+                Some(value) => value.__constitution_violation_code(),
+                None => -1,
+            }
+        }
+    })
+}
+
+/// Generates `proptest::arbitrary::Arbitrary` for `#[constitution(arbitrary)]`.
+///
+/// Integer fields whose invariants decompose into simple bounds are sampled
+/// directly from the valid range. Values are filtered through `enforce_law()`
+/// only if some invariant can't be decomposed, e.g. a cross-field relation.
+fn arbitrary_fn(
+    input: &DeriveInput,
+    fields: &[(Member, Type)],
+    invariants: &[Invariant],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`arbitrary` does not support generic structs",
+        ));
+    }
+
+    let integer_field = |field: &str| {
+        fields
+            .iter()
+            .any(|(name, ty)| member_name(name) == field && is_integer_type(ty))
+    };
+    let mut ranges = bounds::FieldConstraints::new();
+    let mut needs_filter = false;
+    for inv in invariants {
+        match bounds::bound_parts(&inv.expr) {
+            Some(parts) if parts.iter().all(|(field, _)| integer_field(field)) => {
+                for (field, range) in parts {
+                    ranges.entry(field).or_default().intersect(range);
+                }
+            }
+            _ => needs_filter = true,
+        }
+    }
+
+    let (fields, caches) = split_caches(fields);
+    let mut strategies = Vec::new();
+    for (field, ty) in &fields {
+        let suffix = type_ident(ty).unwrap_or_default();
+        let bounds = lowering::sized_integer_bounds(&suffix, 64);
+        // `usize`/`isize` bounds past their 16-bit range are clamped by the
+        // target's `MIN`/`MAX` when the strategy is built
+        let narrowest = lowering::sized_integer_bounds(&suffix, 16);
+        let bound = |value: i128| match narrowest {
+            Some((min, max)) if value < min || value > max => {
+                let value = proc_macro2::Literal::i128_suffixed(value);
+                quote! { (i128::clamp(#value, <#ty>::MIN as i128, <#ty>::MAX as i128) as #ty) }
+            }
+            _ => typed_int(value, &suffix),
+        };
+        let strategy = match (ranges.get(&member_name(field)), bounds) {
+            (Some(range), Some((min, max))) => {
+                let lo = range.lo.unwrap_or(min).max(min);
+                let hi = range.hi.unwrap_or(max).min(max);
+                if lo <= hi {
+                    let lo = match range.lo {
+                        Some(_) => bound(lo),
+                        None => quote! { <#ty>::MIN },
+                    };
+                    let hi = match range.hi {
+                        Some(_) => bound(hi),
+                        None => quote! { <#ty>::MAX },
+                    };
+                    Some(quote! { (#lo..=#hi) })
+                } else {
+                    // Contradictory bounds; let the filter report it
+                    needs_filter = true;
+                    None
+                }
+            }
+            _ => None,
+        };
+        strategies.push(strategy.unwrap_or_else(|| quote! { ::proptest::arbitrary::any::<#ty>() }));
+    }
+
+    // Right-nested pairs sidestep proptest's limit on tuple strategy arity
+    let nest = |strategies: Vec<proc_macro2::TokenStream>| {
+        fields.iter().zip(strategies).rev().fold(
+            (quote! { ::proptest::strategy::Just(()) }, quote! { () }),
+            |(strategy, pattern), ((field, _), field_strategy)| {
+                let binding = binding_name(field);
+                (
+                    quote! { (#field_strategy, #strategy) },
+                    quote! { (#binding, #pattern) },
+                )
+            },
+        )
+    };
+    let unconstrained = fields
+        .iter()
+        .map(|(_, ty)| quote! { ::proptest::arbitrary::any::<#ty>() })
+        .collect();
+    let (strategy, pattern) = nest(strategies);
+    let (any_strategy, any_pattern) = nest(unconstrained);
+    let members: Vec<_> = fields.iter().map(|(field, _)| field).collect();
+    let bindings: Vec<_> = fields
+        .iter()
+        .map(|(field, _)| binding_name(field))
+        .collect();
+    let filter = needs_filter.then(|| {
+        quote! {
+            .prop_filter("invariants must hold", |value| {
+                praborrow_core::CheckProtocol::enforce_law(value).is_ok()
+            })
+        }
+    });
+
+    Ok(quote! {
+        impl ::proptest::arbitrary::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                use ::proptest::strategy::Strategy;
+                #strategy
+                    .prop_map(|#pattern| Self { #(#members: #bindings,)* #caches })
+                    #filter
+                    .boxed()
+            }
+        }
+
+        impl #name {
+            /// A strategy yielding only values that pass `enforce_law()`; the
+            /// same as `proptest::arbitrary::any::<Self>()`.
+            pub fn arbitrary_valid() -> impl ::proptest::strategy::Strategy<Value = Self> {
+                <Self as ::proptest::arbitrary::Arbitrary>::arbitrary()
+            }
+
+            /// A strategy yielding only values that violate at least one
+            /// invariant, for testing error paths. It never yields a value if
+            /// every combination of field values is valid.
+            pub fn arbitrary_invalid() -> impl ::proptest::strategy::Strategy<Value = Self> {
+                use ::proptest::strategy::Strategy;
+                #any_strategy
+                    .prop_map(|#any_pattern| Self { #(#members: #bindings,)* #caches })
+                    .prop_filter("some invariant must be violated", |value| {
+                        praborrow_core::CheckProtocol::enforce_law(value).is_err()
+                    })
+            }
+        }
+    })
+}
+
+/// Generates `try_new` for `#[constitution(constructor)]`, taking one argument
+/// per field in declaration order.
+fn constructor_fn(input: &DeriveInput, fields: &[(Member, Type)]) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (fields, caches) = split_caches(fields);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let members = fields.iter().map(|(field, _)| field);
+    let bindings: Vec<_> = fields
+        .iter()
+        .map(|(field, _)| binding_name(field))
+        .collect();
+    let types = fields.iter().map(|(_, ty)| ty);
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Builds the value from its fields, in declaration order, returning it
+            /// only if every invariant holds.
+            #[allow(clippy::too_many_arguments)]
+            pub fn try_new(
+                #(#bindings: #types),*
+            ) -> Result<Self, praborrow_core::ConstitutionError> {
+                let value = Self { #(#members: #bindings,)* #caches };
+                CheckProtocol::enforce_law(&value)?;
+                Ok(value)
+            }
+        }
+    }
+}
+
+/// Generates `<Name>Builder` for `#[constitution(builder)]`: one setter per
+/// field, named after it (`field_<index>` on a tuple struct), and a `build()`
+/// that checks the invariants. `Option` fields default to `None`; any other
+/// field left unset makes `build()` fail with `ConstitutionError::MissingField`.
+fn builder_fns(input: &DeriveInput, fields: &[(Member, Type)]) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let builder = quote::format_ident!("{}Builder", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (fields, caches) = split_caches(fields);
+    let members = fields.iter().map(|(field, _)| field);
+    let bindings: Vec<_> = fields
+        .iter()
+        .map(|(field, _)| binding_name(field))
+        .collect();
+    let types: Vec<_> = fields.iter().map(|(_, ty)| ty).collect();
+    let values = fields.iter().zip(&bindings).map(|((field, ty), binding)| {
+        if option_inner(ty).is_some() {
+            quote! { self.#binding.unwrap_or(None) }
+        } else {
+            let field_str = member_name(field);
+            quote! {
+                self.#binding.ok_or_else(|| praborrow_core::ConstitutionError::MissingField {
+                    field: #field_str.to_string(),
+                })?
+            }
+        }
+    });
+    let struct_doc = format!(
+        "Builds a [`{}`] one field at a time; `build()` checks its invariants.",
+        name
+    );
+    let setter_docs = fields
+        .iter()
+        .map(|(field, _)| format!("Sets `{}`.", member_name(field)));
+
+    quote! {
+        #[doc = #struct_doc]
+        #vis struct #builder #impl_generics #where_clause {
+            #(#bindings: Option<#types>),*
+        }
+
+        impl #impl_generics Default for #builder #ty_generics #where_clause {
+            fn default() -> Self {
+                Self { #(#bindings: None),* }
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(
+                #[doc = #setter_docs]
+                pub fn #bindings(mut self, #bindings: #types) -> Self {
+                    self.#bindings = Some(#bindings);
+                    self
+                }
+            )*
+
+            /// Builds the value, returning it only if every required field was
+            /// set and every invariant holds.
+            pub fn build(self) -> Result<#name #ty_generics, praborrow_core::ConstitutionError> {
+                let value = #name { #(#members: #values,)* #caches };
+                CheckProtocol::enforce_law(&value)?;
+                Ok(value)
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Starts building a value field by field.
+            pub fn builder() -> #builder #ty_generics {
+                Default::default()
+            }
+        }
+    }
+}
+
+/// Generates `impl TryFrom<Inner>` for `#[constitution(try_from)]` on a struct
+/// with exactly one field.
+fn try_from_impl(
+    input: &DeriveInput,
+    fields: &[(Member, Type)],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let [(member, ty)] = fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`try_from` requires a struct with exactly one field",
+        ));
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics core::convert::TryFrom<#ty> for #name #ty_generics #where_clause {
+            type Error = praborrow_core::ConstitutionError;
+
+            /// Wraps the value, returning it only if every invariant holds.
+            fn try_from(inner: #ty) -> Result<Self, Self::Error> {
+                let value = Self { #member: inner };
+                CheckProtocol::enforce_law(&value)?;
+                Ok(value)
+            }
+        }
+    })
+}
+
+/// Returns a variable name for binding a field's value, e.g. `field_0` for the
+/// first field of a tuple struct.
+fn binding_name(field: &Member) -> Ident {
+    match field {
+        Member::Named(name) => name.clone(),
+        Member::Unnamed(index) => quote::format_ident!("field_{}", index.index),
+    }
+}
+
+/// Generates an integer literal with a type suffix, e.g. `-5i32`.
+fn typed_int(value: i128, suffix: &str) -> proc_macro2::TokenStream {
+    let magnitude: proc_macro2::Literal = format!("{}{}", value.unsigned_abs(), suffix)
+        .parse()
+        .expect("integer literal");
+    if value < 0 {
+        quote! { -#magnitude }
+    } else {
+        quote! { #magnitude }
+    }
+}
+
+/// Converts a `CamelCase` identifier to `snake_case`.
+fn to_snake_case(ident: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
         }
     }
-    false
+    out
 }
 
 /// Derives the Constitution trait for a struct.
 ///
-/// Generates both runtime (panic-based) and formal (SMT-based) verification.
-#[proc_macro_derive(Constitution, attributes(invariant))]
+/// Generates both runtime (`Result`-based) and formal (SMT-based) verification.
+///
+/// `#[invariant(...)]` goes on fields, or on the struct for invariants relating
+/// several fields, and takes a bare expression or a string literal, optionally
+/// with `name = "..."` and `group = "..."`. `#[constitution(...)]` on the struct
+/// enables further generated methods, such as `blocking` for
+/// `verify_integrity_blocking()`, and on a field marks it `nested`,
+/// `skip_hash`, `repr(..)` or `bits = N`.
+#[proc_macro_derive(Constitution, attributes(invariant, constitution))]
 pub fn derive_constitution(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
+    expand_constitution(input)
+}
+
+/// Re-entry point for `#[constitution(inherit = Trait)]` and
+/// `#[constitution(use = "Set")]`.
+///
+/// The macros generated by `#[constitution_fragment]` and `define_invariants!`
+/// call back into this with the struct and an added
+/// `#[constitution(inherited(...))]`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __constitution_inherit(input: TokenStream) -> TokenStream {
+    // The input passed through a `macro_rules!` expansion, so `self` in the struct's
+    // and the fragment's invariants would resolve in different hygiene contexts
+    let input = resolve_at_call_site(input.into());
+    let input = match syn::parse2::<DeriveInput>(input) {
+        Ok(input) => input,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    expand_constitution(input)
+}
+
+/// Re-resolves every token at the call site, keeping its source location.
+fn resolve_at_call_site(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    use proc_macro2::{Group, Span, TokenTree};
 
-    let mut runtime_checks = Vec::new();
-    let mut invariant_strings = Vec::new();
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut resolved =
+                    Group::new(group.delimiter(), resolve_at_call_site(group.stream()));
+                resolved.set_span(group.span().resolved_at(Span::call_site()));
+                return TokenTree::Group(resolved);
+            }
+            token.set_span(token.span().resolved_at(Span::call_site()));
+            token
+        })
+        .collect()
+}
+
+fn expand_constitution(input: DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let options = match attrs::StructOptions::from_attrs(&input.attrs) {
+        Ok(options) => options,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+
+    // Inherited invariants are only visible to the fragment's or set's macro,
+    // which calls back with them attached to the struct
+    if let Some(fragment) = options.unresolved_inherit() {
+        return TokenStream::from(quote! { #fragment! { #input } });
+    }
+
+    // Enums check whichever variant is active and share little with structs
+    if let Data::Enum(data) = &input.data {
+        return match variants::expand(&input, data, &options) {
+            Ok(tokens) => TokenStream::from(tokens),
+            Err(e) => TokenStream::from(e.to_compile_error()),
+        };
+    }
+
+    // Reading a union field is only sound once its tag is known, which lives
+    // outside the union
+    if let Data::Union(data) = &input.data {
+        return TokenStream::from(
+            syn::Error::new_spanned(
+                data.union_token,
+                "`Constitution` can't be derived for a union, whose active field is unknown; \
+                 derive it on a struct holding the union and its tag instead",
+            )
+            .to_compile_error(),
+        );
+    }
+
+    let const_params = const_params(&input.generics);
+    let mut invariants: Vec<Invariant> = Vec::new();
     let mut field_infos: Vec<FieldInfo> = Vec::new();
-    let mut all_fields: Vec<(Ident, Type)> = Vec::new();
+    let mut all_fields: Vec<(Member, Type)> = Vec::new();
+    // As the solver sees them: `repr` enum fields have their discriminant type
+    let mut solver_fields: Vec<(Member, Type)> = Vec::new();
+    let mut nested_fields: Vec<Member> = Vec::new();
+    let mut unhashed_fields: Vec<Member> = Vec::new();
+    let mut repr_fields: Vec<(Member, Type)> = Vec::new();
+    // Integer fields narrowed with `bits = N`, with their logical width
+    let mut bit_widths: Vec<(Member, u32)> = Vec::new();
 
-    if let Data::Struct(syn::DataStruct {
-        fields: Fields::Named(fields),
-        ..
-    }) = &input.data
-    {
-        for field in &fields.named {
-            let field_name = field.ident.clone().expect("Named field must have ident");
-            let field_type = field.ty.clone();
-            all_fields.push((field_name.clone(), field_type.clone()));
+    if let Data::Struct(data) = &input.data {
+        // Collect every field first: invariants may reference fields declared later.
+        // Tuple struct fields are addressed by index, e.g. `self.0`.
+        let mut fields_options = Vec::new();
+        for (index, field) in data.fields.iter().enumerate() {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            let field_options = match attrs::FieldOptions::from_attrs(&field.attrs) {
+                Ok(field_options) => field_options,
+                Err(e) => return TokenStream::from(e.to_compile_error()),
+            };
+            solver_fields.push((
+                member.clone(),
+                field_options
+                    .repr
+                    .clone()
+                    .unwrap_or_else(|| field.ty.clone()),
+            ));
+            if let Some(repr) = &field_options.repr {
+                repr_fields.push((member.clone(), repr.clone()));
+            }
+            all_fields.push((member, field.ty.clone()));
+            fields_options.push(field_options);
+        }
 
+        for ((field, (field_name, field_type)), field_options) in
+            data.fields.iter().zip(&all_fields).zip(fields_options)
+        {
             let mut field_invariants = Vec::new();
 
+            // `bits = N` is checked like an invariant of the field's own
+            if let Some(bits) = &field_options.bits {
+                let invariant =
+                    narrowing_invariant(field_name, field_type, bits).and_then(|expr| {
+                        parse_invariant(&expr, &solver_fields, &const_params, options.strict_parse)
+                    });
+                match invariant {
+                    Ok(invariant) => {
+                        field_invariants.push(invariants.len());
+                        invariants.push(invariant);
+                    }
+                    Err(e) => return TokenStream::from(e.to_compile_error()),
+                }
+                let width = bits.base10_parse().expect("validated when parsed");
+                bit_widths.push((field_name.clone(), width));
+            }
+
             for attr in &field.attrs {
                 if let Meta::List(meta_list) = &attr.meta {
                     #[allow(clippy::collapsible_if)]
                     if meta_list.path.is_ident("invariant") {
                         // Parse the invariant condition expression directly
-                        // Parse the invariant condition expression directly
-                        match meta_list.parse_args::<syn::Expr>() {
-                            Ok(expr) => {
-                                // Extract the invariant string and tokens
-                                let (condition_str, condition_tokens) =
-                                    if let syn::Expr::Lit(syn::ExprLit {
-                                        lit: syn::Lit::Str(lit_str),
-                                        ..
-                                    }) = &expr
-                                    {
-                                        let s = lit_str.value();
-                                        // For string literals, we must parse the content to get tokens for runtime check
-                                        match syn::parse_str::<syn::Expr>(&s) {
-                                            Ok(e) => (s, quote! { #e }),
-                                            Err(err) => {
-                                                return syn::Error::new_spanned(
-                                                    lit_str,
-                                                    format!(
-                                                        "Syntax error in invariant string: {}",
-                                                        err
-                                                    ),
-                                                )
-                                                .to_compile_error()
-                                                .into();
-                                            }
-                                        }
-                                    } else {
-                                        let tokens = quote! { #expr };
-                                        (tokens.to_string(), tokens)
-                                    };
-
-                                // Validate invariant syntax at compile time using Prover Parser
-                                if let Err(e) = praborrow_prover::parser::ExpressionParser::parse(
-                                    &condition_str,
-                                ) {
-                                    let err_msg = format!("Invalid invariant syntax: {}", e);
-                                    return syn::Error::new_spanned(&expr, err_msg)
-                                        .to_compile_error()
-                                        .into();
-                                }
-
-                                field_invariants.push(condition_str.clone());
-                                invariant_strings.push(condition_str.clone());
-
-                                // Correctly construct the new ConstitutionError structure
-                                runtime_checks.push(quote! {
-                                        if !(#condition_tokens) {
-                                            return Err(praborrow_core::ConstitutionError::InvariantViolation {
-                                                expression: #condition_str.to_string(),
-                                                values: std::collections::BTreeMap::new(),
-                                            });
-                                        }
-                                    });
-                            }
-                            Err(e) => {
-                                return TokenStream::from(e.to_compile_error());
+                        let mut invariant = match meta_list
+                            .parse_args::<attrs::InvariantArgs>()
+                            .and_then(|args| {
+                                let mut invariant = parse_invariant(
+                                    &args.expr,
+                                    &solver_fields,
+                                    &const_params,
+                                    options.strict_parse,
+                                )?;
+                                invariant.group = args.group.map(|group| group.value());
+                                invariant.name = args.name.map(|name| name.value());
+                                Ok(invariant)
+                            }) {
+                            Ok(invariant) => invariant,
+                            Err(e) => return TokenStream::from(e.to_compile_error()),
+                        };
+                        if invariant.group.is_none() {
+                            invariant.group = field_options.group.as_ref().map(LitStr::value);
+                        }
+
+                        field_invariants.push(invariants.len());
+                        invariants.push(invariant);
+                    }
+                }
+            }
+
+            if field_options.nested {
+                nested_fields.push(field_name.clone());
+            }
+            if field_options.skip_hash {
+                unhashed_fields.push(field_name.clone());
+            }
+
+            if !field_invariants.is_empty() {
+                field_infos.push(FieldInfo {
+                    name: field_name.clone(),
+                    ty: field_type.clone(),
+                    invariants: field_invariants,
+                });
+            }
+        }
+    }
+
+    // Memoized results live in a `LawCache` field the struct declares itself
+    let memo_cache = match all_fields.iter().find(|(_, ty)| is_law_cache(ty)) {
+        Some((cache, _)) if options.memoize => Some(cache.clone()),
+        None if options.memoize => {
+            return TokenStream::from(
+                syn::Error::new_spanned(
+                    name,
+                    "`memoize` requires a `praborrow_core::LawCache` field to hold the result",
+                )
+                .to_compile_error(),
+            );
+        }
+        _ => None,
+    };
+
+    // Struct-level invariants relate several fields and run after the field ones
+    for attr in &input.attrs {
+        if !attr.path().is_ident("invariant") {
+            continue;
+        }
+        let invariant = attr.parse_args::<attrs::InvariantArgs>().and_then(|args| {
+            let mut invariant = parse_invariant(
+                &args.expr,
+                &solver_fields,
+                &const_params,
+                options.strict_parse,
+            )?;
+            invariant.group = args.group.map(|group| group.value());
+            invariant.name = args.name.map(|name| name.value());
+            Ok(invariant)
+        });
+        match invariant {
+            Ok(invariant) => invariants.push(invariant),
+            Err(e) => return TokenStream::from(e.to_compile_error()),
+        }
+    }
+
+    // Invariants inherited from fragment traits apply to the struct as a whole
+    let mut marker_impls = Vec::new();
+    for (path, inherited) in options.inherited_in_order() {
+        if inherited.marker {
+            marker_impls.push(quote! {
+                impl #impl_generics #path for #name #ty_generics #where_clause {}
+            });
+        }
+        for expr in &inherited.invariants {
+            match parse_invariant(expr, &solver_fields, &const_params, options.strict_parse) {
+                Ok(invariant) => invariants.push(invariant),
+                Err(e) => return TokenStream::from(e.to_compile_error()),
+            }
+        }
+    }
+
+    // Enum fields don't implement `Display`, so a violation leaves them out
+    for invariant in &mut invariants {
+        invariant
+            .referenced
+            .retain(|field| !repr_fields.iter().any(|(repr, _)| repr == field));
+    }
+
+    if options.widen_arithmetic {
+        for invariant in &mut invariants {
+            widen::widen_arithmetic(&mut invariant.expr, &all_fields);
+        }
+    }
+
+    // Generic fields bounded by `Into<i64> + Copy` are compared as `i64`
+    let numeric_fields: Vec<&Member> = all_fields
+        .iter()
+        .filter(|(_, ty)| numeric_param(&input.generics, ty).is_some_and(|(_, bounded)| bounded))
+        .map(|(name, _)| name)
+        .collect();
+    if !numeric_fields.is_empty() {
+        for invariant in &mut invariants {
+            read_as_i64(&mut invariant.expr, &numeric_fields);
+        }
+    }
+
+    // Nested fields are checked before the struct's own invariants, and their
+    // errors name the field they came from
+    let nested_checks: Vec<_> = nested_fields
+        .iter()
+        .map(|field| {
+            let field_str = member_name(field);
+            quote! {
+                CheckProtocol::enforce_law(&self.#field).map_err(|e| {
+                    praborrow_core::ConstitutionError::NestedViolation {
+                        field: #field_str.to_string(),
+                        source: alloc::boxed::Box::new(e),
+                    }
+                })
+            }
+        })
+        .collect();
+    let nested_proofs: Vec<_> = nested_fields
+        .iter()
+        .map(|field| {
+            let field_str = member_name(field);
+            quote! {
+                exact &= praborrow_prover::ProveInvariant::verify_with_context(&self.#field, ctx)
+                    .await
+                    .map_err(|e| match e {
+                        praborrow_prover::ProofError::InvariantViolated(message) => {
+                            praborrow_prover::ProofError::InvariantViolated(
+                                format!("in field '{}': {}", #field_str, message),
+                            )
+                        }
+                        // Re-keyed by path, so `spent` reads as `budget.spent`
+                        praborrow_prover::ProofError::CounterExample { expression, assignment } => {
+                            praborrow_prover::ProofError::CounterExample {
+                                expression: format!("in field '{}': {}", #field_str, expression),
+                                assignment: assignment
+                                    .into_iter()
+                                    .map(|(name, value)| (format!("{}.{}", #field_str, name), value))
+                                    .collect(),
                             }
                         }
+                        other => other,
+                    })?
+                    .is_exact();
+            }
+        })
+        .collect();
+    // A relaxed proof of a nested field makes the whole proof relaxed
+    let (nested_exact, relax) = if nested_proofs.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(quote! { let mut exact = true; }),
+            Some(quote! {
+                .map(|token| {
+                    if exact {
+                        token
+                    } else {
+                        praborrow_prover::VerificationToken::over_approximated()
+                    }
+                })
+            }),
+        )
+    };
+    let runtime_checks: Vec<_> = invariants.iter().map(runtime_check).collect();
+    let enforce_all = options.enforce_all.then(|| {
+        let collecting_checks = invariants.iter().map(collecting_check);
+        quote! {
+            /// Checks every invariant like `enforce_law()`, but reports all violations
+            /// in declaration order instead of stopping at the first.
+            pub fn enforce_law_all(&self) -> Result<(), Vec<praborrow_core::ConstitutionError>> {
+                let mut violations = Vec::new();
+                #(
+                    if let Err(e) = #nested_checks {
+                        violations.push(e);
+                    }
+                )*
+                #(#collecting_checks)*
+                if violations.is_empty() {
+                    Ok(())
+                } else {
+                    Err(violations)
+                }
+            }
+        }
+    });
+
+    let warnings: Vec<_> = invariants
+        .iter()
+        .filter_map(|inv| inv.warning.as_ref())
+        .collect();
+
+    // The field declaring each invariant, if any
+    let mut invariant_fields: Vec<Option<&Member>> = vec![None; invariants.len()];
+    for info in &field_infos {
+        for &index in &info.invariants {
+            invariant_fields[index] = Some(&info.name);
+        }
+    }
+
+    // Generate the invariant strings as a static array
+    // Runtime-only invariants are not SMT obligations. With `combine`, a field's
+    // invariants are conjoined into a single goal.
+    let mut solver_goals: Vec<(Vec<&Invariant>, Option<&Member>)> = Vec::new();
+    for (inv, &field) in invariants.iter().zip(&invariant_fields) {
+        if inv.smt.is_none() {
+            continue;
+        }
+        match solver_goals.last_mut() {
+            Some((parts, last)) if options.combine && field.is_some() && *last == field => {
+                parts.push(inv);
+            }
+            _ => solver_goals.push((vec![inv], field)),
+        }
+    }
+    let mut invariant_literals = Vec::new();
+    let mut invariant_labels = Vec::new();
+    for (parts, field) in &solver_goals {
+        let (expression, label) = match parts.as_slice() {
+            [inv] => (inv.smt.clone().unwrap_or_default(), inv.name.as_deref()),
+            _ => {
+                let conjunction = parts
+                    .iter()
+                    .filter_map(|inv| inv.smt.as_deref())
+                    .map(|smt| format!("({})", smt))
+                    .collect::<Vec<_>>()
+                    .join(" && ");
+                if let Err(e) = praborrow_prover::parser::ExpressionParser::parse(&conjunction) {
+                    let message = format!("Invalid combined invariant syntax: {}", e);
+                    return TokenStream::from(
+                        syn::Error::new_spanned(field, message).to_compile_error(),
+                    );
+                }
+                (conjunction, None)
+            }
+        };
+        invariant_literals.push(syn::LitStr::new(
+            &expression,
+            proc_macro2::Span::call_site(),
+        ));
+        invariant_labels.push(match label {
+            Some(name) => quote! { Some(#name) },
+            None => quote! { None },
+        });
+    }
+    // Only labelled invariants get a label listing
+    let labels = invariants.iter().any(|inv| inv.name.is_some()).then(|| {
+        quote! {
+            /// The label of each invariant in `invariant_expressions()`, in the same
+            /// order, or `None` where no `name = "..."` was given.
+            pub fn invariant_labels() -> &'static [Option<&'static str>] {
+                &[#(#invariant_labels),*]
+            }
+        }
+    });
+
+    // Generate field value extraction for hash computation
+    // Every field not marked `skip_hash` is included; a `LawCache` holds a
+    // result derived from the hash, not state of its own
+    let hash_fields: Vec<_> = solver_fields
+        .iter()
+        .filter(|(name, ty)| !unhashed_fields.contains(name) && !is_law_cache(ty))
+        .map(|(name, ty)| {
+            if nested_fields.contains(name) {
+                quote! {
+                    hasher.update(praborrow_prover::ProveInvariant::compute_data_hash(&self.#name));
+                }
+            } else if numeric_fields.contains(&name) {
+                quote! { hasher.update(&Into::<i64>::into(self.#name).to_le_bytes()); }
+            } else {
+                hash_update(
+                    ty,
+                    repr_place(&repr_fields, quote! { self }, name),
+                    options.memoize,
+                )
+            }
+        })
+        .collect();
+
+    // Generate field provider implementation
+    // Maps field names to Z3 AST values
+    let mut field_match_arms = provider_arms(
+        &solver_fields,
+        |name| repr_place(&repr_fields, quote! { self.0 }, name),
+        options.bitvector(),
+        &bit_widths,
+    );
+
+    // Generic fields are read through their `Into<i64>` bound
+    match generic_field_arms(
+        &input.generics,
+        &all_fields,
+        &invariants,
+        |name| quote! { self.0.#name },
+    ) {
+        Ok(arms) => field_match_arms.extend(arms),
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    }
+
+    // Newtype projections such as `self.p.0` are exposed as `p__0`. The inner
+    // type isn't visible to the macro, so the conversion is left to `From`;
+    // runtime-only invariants may read types that have none.
+    let provable = || {
+        invariants
+            .iter()
+            .filter(|inv| inv.smt.is_some())
+            .map(|inv| &inv.expr)
+    };
+    for (symbol, place) in lowering::projections(provable()) {
+        field_match_arms.push(quote! {
+            #symbol => {
+                Ok(FieldValue::from(self.0.#place))
+            }
+        });
+    }
+
+    // Nested accesses such as `self.limits.max` are exposed by their dotted
+    // path. As with projections, the inner type is left to `From`.
+    for (symbol, place) in lowering::nested_fields(provable()) {
+        field_match_arms.push(quote! {
+            #symbol => {
+                Ok(FieldValue::from(self.0.#place))
+            }
+        });
+    }
+
+    // Associated constants such as `Self::MAX` are exposed as `Self__MAX`, and
+    // const generic parameters by their name
+    field_match_arms.extend(const_arms(&input, invariants.iter().map(|inv| &inv.expr)));
+    field_match_arms.extend(const_param_arms(
+        &const_params,
+        invariants.iter().map(|inv| &inv.expr),
+    ));
+    field_match_arms.extend(pointer_bound_arms(
+        invariants.iter().filter_map(|inv| inv.smt.as_deref()),
+        options.bitvector(),
+    ));
+
+    // Fields compared with their type's variants, as in `self.color != Color::Red`,
+    // are exposed by the index of the variant they hold
+    let compared =
+        lowering::compared_variants(invariants.iter().map(|inv| &inv.expr), &solver_fields);
+    let variants = variant_symbols(&compared);
+    for (field, _) in &solver_fields {
+        let place = quote! { self.0.#field };
+        field_match_arms.extend(variant_field_arm(field, &compared, &variants, place));
+    }
+    field_match_arms.extend(variant_symbol_arms(&variants));
+
+    // Clamp fields into the ranges their invariants decompose into
+    let repairs: Vec<_> = bounds::field_constraints(invariants.iter().map(|inv| &inv.expr))
+        .into_iter()
+        .filter_map(|(field, range)| {
+            let (name, _) = all_fields
+                .iter()
+                .find(|(name, ty)| member_name(name) == field && is_integer_type(ty))?;
+            let lo = range.lo.map(proc_macro2::Literal::i128_unsuffixed);
+            let hi = range.hi.map(proc_macro2::Literal::i128_unsuffixed);
+            match (lo, hi) {
+                (Some(lo), Some(hi)) if range.lo <= range.hi => {
+                    Some(quote! { self.#name = self.#name.clamp(#lo, #hi); })
+                }
+                (Some(_), Some(_)) => None, // Contradictory bounds can't be repaired
+                (Some(lo), None) => Some(quote! { self.#name = self.#name.max(#lo); }),
+                (None, Some(hi)) => Some(quote! { self.#name = self.#name.min(#hi); }),
+                (None, None) => None,
+            }
+        })
+        .collect();
+
+    // Type-state invariants become bounded inherent methods; a second, conditional
+    // `impl CheckProtocol` would overlap with the unconditional one.
+    let mut state_methods = Vec::new();
+    for state in &options.state_invariants {
+        let mut checks = Vec::new();
+        for expr in &state.invariants {
+            // State invariants are runtime-only regardless, so calls aren't worth a warning
+            match parse_invariant(expr, &all_fields, &const_params, false) {
+                Ok(mut invariant) => {
+                    if options.widen_arithmetic {
+                        widen::widen_arithmetic(&mut invariant.expr, &all_fields);
                     }
+                    checks.push(runtime_check(&invariant));
+                }
+                Err(e) => return TokenStream::from(e.to_compile_error()),
+            }
+        }
+
+        let method = state_method_name(&state.bound);
+        let bound = &state.bound;
+        let mut generics = input.generics.clone();
+        generics.make_where_clause().predicates.push(bound.clone());
+        let state_where_clause = &generics.where_clause;
+        let doc = format!(
+            "Enforces the base invariants plus those that apply when `{}`.",
+            quote! { #bound }
+        );
+
+        state_methods.push(quote! {
+            #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+            impl #impl_generics #name #ty_generics #state_where_clause {
+                #[doc = #doc]
+                pub fn #method(&self) -> Result<(), praborrow_core::ConstitutionError> {
+                    CheckProtocol::enforce_law(self)?;
+                    #(#checks)*
+                    Ok(())
+                }
+            }
+        });
+    }
+
+    let prove_generics = prove_generics(&input);
+    let prove_where_clause = &prove_generics.where_clause;
+    let provider_generics = provider_generics(&input);
+    let (provider_impl_generics, provider_ty_generics, provider_where_clause) =
+        provider_generics.split_for_impl();
+
+    // A const validator is only possible when every invariant stays in the const
+    // subset, and nested fields can't be checked in a const context at all
+    let const_validator = match options.validate_const {
+        Some(span)
+            if !nested_fields.is_empty()
+                || !invariants
+                    .iter()
+                    .all(|inv| consteval::is_const_evaluable(&inv.expr, &all_fields)) =>
+        {
+            return TokenStream::from(
+                syn::Error::new(
+                    span,
+                    "`validate_const` requires every invariant to be const-evaluable and no `nested` fields",
+                )
+                .to_compile_error(),
+            );
+        }
+        Some(_) => {
+            let conditions = invariants.iter().map(|inv| &inv.expr);
+            quote! {
+                /// Evaluates every invariant in a const context.
+                ///
+                /// Invariants use only const-evaluable operators (comparisons,
+                /// integer arithmetic, bitwise and logical operators on integer,
+                /// `bool` and `char` fields). Usable in static assertions:
+                /// `const _: () = assert!(VALUE.validate_const());`
+                pub const fn validate_const(&self) -> bool {
+                    true #(&& (#conditions))*
                 }
             }
+        }
+        None => quote! {},
+    };
 
-            if !field_invariants.is_empty() {
-                field_infos.push(FieldInfo {
-                    name: field_name,
-                    ty: field_type,
-                    invariants: field_invariants,
-                });
-            }
+    // Check groups in order of first appearance
+    let mut groups: Vec<&str> = Vec::new();
+    for group in invariants.iter().filter_map(|inv| inv.group.as_deref()) {
+        if !groups.contains(&group) {
+            groups.push(group);
         }
     }
+    // Only types declaring a group get the group methods
+    let group_fns = (!groups.is_empty()).then(|| {
+        let arms = groups.iter().map(|&group| {
+            let checks = invariants
+                .iter()
+                .filter(|inv| inv.group.as_deref() == Some(group))
+                .map(runtime_check);
+            quote! { #group => { #(#checks)* } }
+        });
+        quote! {
+            /// Runs only the invariants in the named check group.
+            ///
+            /// Ungrouped invariants never run here, and an unknown group checks
+            /// nothing; see `groups()` for the declared names.
+            pub fn enforce_group(&self, group: &str) -> Result<(), praborrow_core::ConstitutionError> {
+                match group {
+                    #(#arms)*
+                    _ => {}
+                }
+                Ok(())
+            }
 
-    // Generate the invariant strings as a static array
-    let invariant_count = invariant_strings.len();
-    let invariant_literals: Vec<_> = invariant_strings
-        .iter()
-        .map(|s| syn::LitStr::new(s, proc_macro2::Span::call_site()))
-        .collect();
+            /// The declared check groups, in order of first appearance.
+            pub fn groups() -> &'static [&'static str] {
+                &[#(#groups),*]
+            }
+        }
+    });
 
-    // Generate field value extraction for hash computation
-    // Only include integer fields for now
-    let hash_fields: Vec<_> = all_fields
+    // Group the runtime evaluation of each invariant by the field declaring it
+    let field_reports: Vec<_> = field_infos
         .iter()
-        .filter(|(_, ty)| is_integer_type(ty))
-        .map(|(name, _)| {
+        .map(|info| {
+            let field = &info.name;
+            let field_str = member_name(field);
+            let value = if is_reportable_type(&info.ty) {
+                quote! { Some(self.#field.to_string()) }
+            } else {
+                quote! { None }
+            };
+            let evaluations = info.invariants.iter().map(|&index| {
+                let condition = &invariants[index].expr;
+                let condition_str = &invariants[index].expr_str;
+                quote! {
+                    if #condition {
+                        satisfied.push(#condition_str);
+                    } else {
+                        violated.push(#condition_str);
+                    }
+                }
+            });
             quote! {
-                hasher.update(&self.#name.to_le_bytes());
+                {
+                    let mut satisfied = Vec::new();
+                    let mut violated = Vec::new();
+                    #(#evaluations)*
+                    reports.push(praborrow_core::FieldReport {
+                        name: #field_str,
+                        value: #value,
+                        satisfied,
+                        violated,
+                    });
+                }
             }
         })
         .collect();
 
-    // Generate field provider implementation
-    // Maps field names to Z3 AST values
-    let field_match_arms: Vec<_> = all_fields
+    let field_report = options.field_report.then(|| {
+        quote! {
+            /// Evaluates every invariant and groups the results by the field declaring it.
+            ///
+            /// Only fields with invariants are reported. `value` is the field rendered
+            /// with `ToString` for integer, float, `char`, `bool` and `String` fields,
+            /// and `None` otherwise.
+            pub fn field_report(&self) -> Vec<praborrow_core::FieldReport> {
+                let mut reports = Vec::new();
+                #(#field_reports)*
+                reports
+            }
+        }
+    });
+
+    // Reflection metadata for `contract()`
+    let name_str = name.to_string();
+    let contract_fields = all_fields.iter().map(|(field, ty)| {
+        let field_str = member_name(field);
+        let ty_str = quote! { #ty }.to_string();
+        quote! {
+            praborrow_core::ContractField { name: #field_str, ty: #ty_str }
+        }
+    });
+    let contract_invariants = invariants
         .iter()
-        .filter(|(_, ty)| is_integer_type(ty))
-        .map(|(name, ty)| {
-            let name_str = name.to_string();
-            let is_unsigned = if let Type::Path(tp) = ty {
-                tp.path
-                    .segments
-                    .last()
-                    .map(|s| s.ident.to_string().starts_with('u'))
-                    .unwrap_or(false)
-            } else {
-                false
+        .zip(&invariant_fields)
+        .map(|(inv, field)| {
+            let expr_str = &inv.expr_str;
+            let group = match &inv.group {
+                Some(group) => quote! { Some(#group) },
+                None => quote! { None },
+            };
+            let field = match field.map(member_name) {
+                Some(field) => quote! { Some(#field) },
+                None => quote! { None },
+            };
+            let runtime_only = inv.smt.is_none();
+            let name = match &inv.name {
+                Some(name) => quote! { Some(#name) },
+                None => quote! { None },
             };
+            quote! {
+                praborrow_core::ContractInvariant {
+                    name: #name,
+                    expression: #expr_str,
+                    group: #group,
+                    field: #field,
+                    runtime_only: #runtime_only,
+                }
+            }
+        });
+    let contract_hash_inputs = invariants.iter().map(|inv| &inv.expr_str);
+    let contract = options.contract.then(|| {
+        quote! {
+            /// Describes the type's full contract for tooling: every field, every
+            /// invariant, and a SHA-256 hash of the invariant set.
+            ///
+            /// `version` is the version of the crate defining the type. Inherited
+            /// invariants have no `field`; type-state invariants are not part of
+            /// the contract.
+            pub fn contract() -> praborrow_core::Contract {
+                use praborrow_prover::sha2::{Digest, Sha256};
 
-            if is_unsigned {
-                quote! {
-                    #name_str => {
-                        Ok(FieldValue::UInt(self.0.#name as u64))
+                let mut hasher = Sha256::new();
+                #(
+                    hasher.update(#contract_hash_inputs.as_bytes());
+                    hasher.update(b"\n");
+                )*
+
+                praborrow_core::Contract {
+                    type_name: #name_str,
+                    version: env!("CARGO_PKG_VERSION"),
+                    invariants: vec![#(#contract_invariants),*],
+                    fields: vec![#(#contract_fields),*],
+                    invariant_set_hash: hasher.finalize().to_vec(),
+                }
+            }
+        }
+    });
+
+    let bench = bench_fn();
+    let context = smt_context(options.backend.as_ref());
+    let smt_script = options.smt_script.then(|| {
+        quote! {
+            /// Renders the SMT-LIB2 script the solver is given for this value: the
+            /// fields pinned to their current values and one assertion per entry of
+            /// `invariant_expressions()`. Purely diagnostic; it can be fed to `z3`
+            /// directly. Nested fields are not included.
+            pub fn smt_script(&self) -> Result<String, praborrow_prover::ProofError> {
+                let provider = praborrow_prover::ProveInvariant::get_field_provider(self);
+                praborrow_prover::smtlib::script(
+                    &*provider,
+                    <Self as praborrow_prover::ProveInvariant>::invariant_expressions(),
+                )
+            }
+        }
+    });
+    let blocking_verify = options
+        .blocking
+        .then(|| blocking_verify_fn(options.backend.as_ref()));
+    let batch_verify = options.batch.then(batch_verify_fn);
+    let cached_verify = cached_verify_fn(options.backend.as_ref());
+    let runtime_only = invariants
+        .iter()
+        .filter(|inv| inv.smt.is_none())
+        .map(|inv| &inv.expr_str);
+    let verify_report = options.report.then(|| {
+        verify_report_fn(
+            quote! { <Self as praborrow_prover::ProveInvariant>::invariant_expressions() },
+            quote! { &[#(#runtime_only),*] },
+            options.timeout_ms,
+            options.fallback_linear,
+        )
+    });
+    let parallel_verify = options.parallel.then(|| {
+        parallel_verify_fn(
+            quote! { <Self as praborrow_prover::ProveInvariant>::invariant_expressions() },
+            &nested_proofs,
+            options.timeout_ms,
+            options.fallback_linear,
+            options.backend.as_ref(),
+        )
+    });
+    let verification = verify_call(
+        quote! { Self::invariant_expressions() },
+        options.timeout_ms,
+        options.fallback_linear,
+    );
+    let guard = options.guard.then(guard_fns);
+    let is_valid = is_valid_fn();
+    let equivalence = equivalence_consts(&solver_fields, &invariants);
+    let or_panic = options.panic.then(|| {
+        quote! {
+            /// Runs `enforce_law()`, panicking with the violation's message instead
+            /// of returning it.
+            #[track_caller]
+            pub fn enforce_law_or_panic(&self) {
+                if let Err(e) = CheckProtocol::enforce_law(self) {
+                    panic!("{}", e);
+                }
+            }
+        }
+    });
+    let check_on_drop = options.check_on_drop.then(|| {
+        quote! {
+            /// Panics if a value is about to be dropped in a violating state. Checks
+            /// only in debug builds, and not while the thread is already panicking.
+            /// Called from the generated `Drop`, or from the type's own `drop()`
+            /// with `verify_on_drop(manual)`.
+            #[track_caller]
+            pub fn check_on_drop(&self) {
+                if cfg!(debug_assertions)
+                    && !std::thread::panicking()
+                    && let Err(e) = CheckProtocol::enforce_law(self)
+                {
+                    panic!("dropped in a violating state: {}", e);
+                }
+            }
+        }
+    });
+    // Spanned so a conflicting `Drop` impl is reported at `verify_on_drop`
+    let drop_impl = options.verify_on_drop.map(|span| {
+        quote_spanned! {span=>
+            impl #impl_generics Drop for #name #ty_generics #where_clause {
+                fn drop(&mut self) {
+                    self.check_on_drop();
+                }
+            }
+        }
+    });
+    let validate = options.error.as_ref().map(|error| {
+        quote! {
+            /// Runs `enforce_law()`, converting a violation into the application's
+            /// error type.
+            pub fn validate(&self) -> Result<(), #error> {
+                CheckProtocol::enforce_law(self).map_err(Into::into)
+            }
+        }
+    });
+    let invariant_conditions: Vec<_> = nested_fields
+        .iter()
+        .map(|field| quote! { self.#field.invariant_fn() })
+        .chain(invariants.iter().map(|inv| {
+            let condition = &inv.expr;
+            quote! { #condition }
+        }))
+        .collect();
+    let invariant_fn = options
+        .invariant_fn
+        .then(|| invariant_fn(conjunction(&invariant_conditions)));
+    let describe = options.introspect.then(|| {
+        let entries = invariants.iter().map(|inv| {
+            let expr_str = &inv.expr_str;
+            let condition = &inv.expr;
+            quote! { (#expr_str.to_string(), #condition) }
+        });
+        quote! {
+            /// Evaluates every invariant, pairing each expression as written with
+            /// whether it currently holds. Unlike `enforce_law()`, nothing
+            /// short-circuits. Nested fields are not included.
+            pub fn describe_invariants(&self) -> Vec<(String, bool)> {
+                vec![#(#entries),*]
+            }
+        }
+    });
+    let repair = options.repair.then(|| {
+        quote! {
+            /// Clamps every field with a decomposable range invariant into its bounds.
+            ///
+            /// Invariants that don't decompose into simple ranges are left alone and
+            /// may still fail a subsequent `enforce_law()`.
+            pub fn repair_into_bounds(&mut self) {
+                #(#repairs)*
+            }
+        }
+    });
+    let release_skip = release_skip();
+    let checks = quote! {
+        #(#nested_checks?;)*
+        #(#runtime_checks)*
+        Ok(())
+    };
+    let enforce_body = match memo_cache {
+        Some(cache) => quote! {
+            self.#cache.check(
+                praborrow_prover::ProveInvariant::compute_data_hash(self),
+                || { #checks },
+            )
+        },
+        None => checks,
+    };
+
+    // Type-width domains for detecting invariants that hold for every value
+    let tautology = options.tautology.then(|| {
+        let domain = domain_slice(&solver_fields);
+        let implied_by_domain = block_on(quote! { ctx.verify_implication(domain, &conclusion) });
+        quote! {
+            /// Finds invariants implied by the field types' domains alone, such as
+            /// `self.x <= 255` on a `u8`. These always hold and likely indicate a
+            /// mistake in the contract.
+            ///
+            /// Returns indices into `invariant_expressions()`; runtime-only
+            /// invariants are never reported.
+            pub fn tautological_invariants() -> Result<Vec<usize>, praborrow_prover::ProofError> {
+                use core::future::Future;
+                use praborrow_prover::ProofError;
+
+                let ctx = #context?;
+                let domain: &[&str] = #domain;
+                let mut tautological = Vec::new();
+                for (index, invariant) in
+                    <Self as praborrow_prover::ProveInvariant>::invariant_expressions().iter().enumerate()
+                {
+                    let conclusion = [*invariant];
+                    match #implied_by_domain {
+                        Ok(_) => tautological.push(index),
+                        Err(ProofError::InvariantViolated(_)) => {}
+                        Err(e) => return Err(e),
                     }
                 }
-            } else {
-                quote! {
-                    #name_str => {
-                        Ok(FieldValue::Int(self.0.#name as i64))
+                Ok(tautological)
+            }
+        }
+    });
+
+    let constructor = options
+        .constructor
+        .then(|| constructor_fn(&input, &all_fields));
+    let builder = options.builder.then(|| builder_fns(&input, &all_fields));
+
+    let try_from = if options.try_from {
+        match try_from_impl(&input, &all_fields) {
+            Ok(tokens) => tokens,
+            Err(e) => return TokenStream::from(e.to_compile_error()),
+        }
+    } else {
+        quote! {}
+    };
+
+    let arbitrary = if options.arbitrary {
+        match arbitrary_fn(&input, &all_fields, &invariants) {
+            Ok(tokens) => tokens,
+            Err(e) => return TokenStream::from(e.to_compile_error()),
+        }
+    } else {
+        quote! {}
+    };
+
+    let c_export = if options.c_export {
+        match c_export_fn(&input, &invariants) {
+            Ok(tokens) => tokens,
+            Err(e) => return TokenStream::from(e.to_compile_error()),
+        }
+    } else {
+        quote! {}
+    };
+
+    // Each invariant is proven formally when it has an SMT form, otherwise checked at runtime
+    let partial_outcomes: Vec<_> = invariants
+        .iter()
+        .map(|inv| {
+            let expr_str = &inv.expr_str;
+            match &inv.smt {
+                Some(smt) => {
+                    let proof = block_on(verify_call(
+                        quote! { &[#smt] },
+                        options.timeout_ms,
+                        options.fallback_linear,
+                    ));
+                    quote! {
+                        praborrow_prover::InvariantOutcome {
+                            expression: #expr_str,
+                            mode: praborrow_prover::VerificationMode::Formal,
+                            result: match &ctx {
+                                Ok(ctx) => #proof.map(|_| ()),
+                                Err(e) => Err(e.clone()),
+                            },
+                        }
+                    }
+                }
+                None => {
+                    let condition = &inv.expr;
+                    quote! {
+                        praborrow_prover::InvariantOutcome {
+                            expression: #expr_str,
+                            mode: praborrow_prover::VerificationMode::Runtime,
+                            result: if #condition {
+                                Ok(())
+                            } else {
+                                Err(praborrow_prover::ProofError::InvariantViolated(
+                                    #expr_str.to_string(),
+                                ))
+                            },
+                        }
                     }
                 }
             }
         })
         .collect();
+    // Only a formal proof needs a solver, so a type whose invariants are all
+    // runtime-only doesn't set one up
+    let partial_setup = invariants.iter().any(|inv| inv.smt.is_some()).then(|| {
+        quote! {
+            use core::future::Future;
+
+            let ctx = #context;
+            let provider = praborrow_prover::ProveInvariant::get_field_provider(self);
+        }
+    });
+
+    let partial = options.partial.then(|| {
+        quote! {
+            /// Verifies each invariant with the strongest available method.
+            ///
+            /// Invariants the solver can express are proven formally; runtime-only
+            /// invariants fall back to a runtime check. The result reports which mode
+            /// was used for every invariant.
+            pub fn verify_integrity_partial(&self) -> praborrow_prover::PartialProof {
+                #partial_setup
+                praborrow_prover::PartialProof {
+                    outcomes: vec![#(#partial_outcomes),*],
+                }
+            }
+        }
+    });
 
     let expanded = quote! {
-        // Runtime check implementation - returns Result instead of panicking
-        impl CheckProtocol for #name {
+        // Runtime check implementation - returns Result instead of panicking.
+        // Invariants are checked as written, which may compare floats exactly or
+        // test `len() > 0` (the form the solver understands).
+        #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+        impl #impl_generics CheckProtocol for #name #ty_generics #where_clause {
             fn enforce_law(&self) -> Result<(), praborrow_core::ConstitutionError> {
-                #(#runtime_checks)*
-                Ok(())
+                #release_skip
+                #enforce_body
             }
         }
 
+        #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #repair
+
+            #contract
+
+            #group_fns
+
+            /// The invariant expressions handed to the solver, in declaration order.
+            /// Runtime-only invariants are not included.
+            pub const INVARIANTS: &'static [&'static str] = &[#(#invariant_literals),*];
+
+            #labels
+
+            #enforce_all
+
+            #const_validator
+
+            #field_report
+
+            #is_valid
+
+            #invariant_fn
+
+            #guard
+
+            #or_panic
+
+            #validate
+
+            #check_on_drop
+
+            #describe
+
+            #bench
+
+            #equivalence
+        }
+
+        #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+        impl #impl_generics #name #ty_generics #prove_where_clause {
+            #tautology
+
+            #blocking_verify
+
+            #batch_verify
+
+            #cached_verify
+
+            #smt_script
+
+            #verify_report
+
+            #parallel_verify
+
+            #partial
+        }
+
+        #(#state_methods)*
+
+        #c_export
+        #arbitrary
+        #constructor
+        #builder
+        #try_from
+        #drop_impl
+
+        #(#marker_impls)*
+
+        #(#warnings)*
+
         // Formal verification implementation
-        impl praborrow_prover::ProveInvariant for #name {
+        impl #impl_generics praborrow_prover::ProveInvariant for #name #ty_generics #prove_where_clause {
             fn invariant_expressions() -> &'static [&'static str] {
-                static INVARIANTS: [&str; #invariant_count] = [#(#invariant_literals),*];
-                &INVARIANTS
+                Self::INVARIANTS
             }
 
             fn compute_data_hash(&self) -> Vec<u8> {
@@ -253,9 +2836,9 @@ pub fn derive_constitution(input: TokenStream) -> TokenStream {
                  use praborrow_prover::backend::{FieldValueProvider, FieldValue};
                  use praborrow_prover::ProofError;
 
-                 struct FieldProvider<'a>(&'a #name);
+                 struct FieldProvider #provider_impl_generics (&'__a #name #ty_generics) #provider_where_clause;
 
-                 impl<'a> FieldValueProvider for FieldProvider<'a> {
+                 impl #provider_impl_generics FieldValueProvider for FieldProvider #provider_ty_generics #provider_where_clause {
                     fn get_field_value(&self, name: &str) -> Result<FieldValue, ProofError> {
                         match name {
                             #(#field_match_arms)*
@@ -272,10 +2855,561 @@ pub fn derive_constitution(input: TokenStream) -> TokenStream {
                 ctx: &praborrow_prover::SmtContext
             ) -> impl core::future::Future<Output = Result<praborrow_prover::VerificationToken, praborrow_prover::ProofError>> + Send {
                 async move {
+                    #nested_exact
+                    #(#nested_proofs)*
                     let provider = self.get_field_provider();
-                    ctx.verify_invariants(&*provider, Self::invariant_expressions()).await
+                    #verification.await #relax
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Input for `assert_invariants_equivalent!`: two comma-separated types.
+struct TypePair {
+    old: Type,
+    new: Type,
+}
+
+impl syn::parse::Parse for TypePair {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let old = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let new = input.parse()?;
+        let _ = input.parse::<Option<syn::Token![,]>>()?;
+        Ok(Self { old, new })
+    }
+}
+
+/// Generates `verify_integrity_blocking()`, the synchronous counterpart of
+/// `ProveInvariant::verify`.
+fn blocking_verify_fn(backend: Option<&LitStr>) -> proc_macro2::TokenStream {
+    let context = smt_context(backend);
+    let verification =
+        block_on(quote! { praborrow_prover::ProveInvariant::verify_with_context(self, &ctx) });
+    quote! {
+        /// Verifies every invariant like `ProveInvariant::verify()`, but on the
+        /// current thread, for callers without an async runtime such as build
+        /// scripts and plain tests.
+        pub fn verify_integrity_blocking(
+            &self,
+        ) -> Result<praborrow_prover::VerificationToken, praborrow_prover::ProofError> {
+            use core::future::Future;
+
+            let ctx = #context?;
+            #verification
+        }
+    }
+}
+
+/// Generates `verify_batch()`, which proves a slice of values against one
+/// caller-provided context.
+fn batch_verify_fn() -> proc_macro2::TokenStream {
+    quote! {
+        /// Verifies each of `items` like `verify_with_context()`, in order, all
+        /// against the one `ctx` rather than a context per element. Only the
+        /// context is shared: each item's invariants are translated and solved
+        /// from scratch. Returns one result per item.
+        pub async fn verify_batch(
+            ctx: &praborrow_prover::SmtContext,
+            items: &[Self],
+        ) -> Vec<Result<praborrow_prover::VerificationToken, praborrow_prover::ProofError>> {
+            let mut results = Vec::with_capacity(items.len());
+            for item in items {
+                let result = praborrow_prover::ProveInvariant::verify_with_context(item, ctx);
+                results.push(result.await);
+            }
+            results
+        }
+    }
+}
+
+/// Generates the `Result<SmtContext, ProofError>` the generated verification
+/// methods prove with: the default solver, or the one named by
+/// `#[constitution(backend = "...")]`.
+fn smt_context(backend: Option<&LitStr>) -> proc_macro2::TokenStream {
+    match backend {
+        Some(backend) => quote! { praborrow_prover::SmtContext::with_backend(#backend) },
+        None => quote! { praborrow_prover::SmtContext::new() },
+    }
+}
+
+/// How long the exact encoding may run under `fallback_linear` when the
+/// struct sets no `timeout_ms`.
+const FALLBACK_TIMEOUT_MS: u64 = 1000;
+
+/// Generates the solver call proving `invariants` (a `&[&str]` expression)
+/// against a `provider` in scope, bounded by `timeout_ms` when the struct sets
+/// one. With `fallback_linear`, a proof that times out is retried over
+/// unbounded integers.
+fn verify_call(
+    invariants: proc_macro2::TokenStream,
+    timeout_ms: Option<u64>,
+    fallback_linear: bool,
+) -> proc_macro2::TokenStream {
+    match timeout_ms {
+        _ if fallback_linear => {
+            let timeout_ms = timeout_ms.unwrap_or(FALLBACK_TIMEOUT_MS);
+            quote! { ctx.verify_invariants_with_fallback(&*provider, #invariants, #timeout_ms) }
+        }
+        Some(timeout_ms) => {
+            quote! { ctx.verify_invariants_with_timeout(&*provider, #invariants, #timeout_ms) }
+        }
+        None => quote! { ctx.verify_invariants(&*provider, #invariants) },
+    }
+}
+
+/// Generates `verify_report()`, which proves each of `expressions` (a
+/// `&[&'static str]` expression) with its own solver call and reports each of
+/// `runtime_only` as not formally verifiable.
+fn verify_report_fn(
+    expressions: proc_macro2::TokenStream,
+    runtime_only: proc_macro2::TokenStream,
+    timeout_ms: Option<u64>,
+    fallback_linear: bool,
+) -> proc_macro2::TokenStream {
+    let verification = verify_call(quote! { &[expression] }, timeout_ms, fallback_linear);
+    quote! {
+        /// Proves each invariant on its own instead of as one conjunction, so a
+        /// failure names the exact invariant that broke. Runtime-only invariants
+        /// follow as `ProofError::Unsupported`. Nested fields are not included.
+        pub async fn verify_report(
+            &self,
+            ctx: &praborrow_prover::SmtContext,
+        ) -> praborrow_prover::VerificationReport {
+            let provider = praborrow_prover::ProveInvariant::get_field_provider(self);
+            let expressions: &[&'static str] = #expressions;
+            let mut results = Vec::new();
+            for &expression in expressions {
+                let status = #verification.await.into();
+                results.push(praborrow_prover::InvariantResult { expression, status });
+            }
+            let runtime_only: &[&'static str] = #runtime_only;
+            for &expression in runtime_only {
+                results.push(praborrow_prover::InvariantResult {
+                    expression,
+                    status: praborrow_prover::InvariantStatus::Error(
+                        praborrow_prover::ProofError::Unsupported(expression.to_string()),
+                    ),
+                });
+            }
+            praborrow_prover::VerificationReport { results }
+        }
+    }
+}
+
+/// Generates `verify_all_parallel()`, which proves each of `expressions` (a
+/// `&[&'static str]` expression) on a thread of its own after running
+/// `nested_proofs` in turn.
+fn parallel_verify_fn(
+    expressions: proc_macro2::TokenStream,
+    nested_proofs: &[proc_macro2::TokenStream],
+    timeout_ms: Option<u64>,
+    fallback_linear: bool,
+    backend: Option<&LitStr>,
+) -> proc_macro2::TokenStream {
+    let context = smt_context(backend);
+    let nested = (!nested_proofs.is_empty()).then(|| {
+        let nested = block_on(quote! {
+            async {
+                let mut exact = true;
+                #(#nested_proofs)*
+                Ok::<bool, praborrow_prover::ProofError>(exact)
+            }
+        });
+        quote! {
+            let ctx = &#context?;
+            exact = #nested?;
+        }
+    });
+    let verification = block_on(verify_call(
+        quote! { &invariant },
+        timeout_ms,
+        fallback_linear,
+    ));
+    quote! {
+        /// Verifies every invariant like `ProveInvariant::verify()`, but proves
+        /// each one on its own thread with its own `SmtContext`. Nested fields
+        /// are verified first, in turn. Blocks until every proof is done; the first failing invariant in
+        /// declaration order is the one reported.
+        pub fn verify_all_parallel(
+            &self,
+        ) -> Result<praborrow_prover::VerificationToken, praborrow_prover::ProofError> {
+            use core::future::Future;
+
+            let mut exact = true;
+            #nested
+            let provider = praborrow_prover::ProveInvariant::get_field_provider(self);
+            let provider: &dyn praborrow_prover::backend::FieldValueProvider = &*provider;
+            let expressions: &[&'static str] = #expressions;
+            std::thread::scope(|scope| {
+                let proofs: Vec<_> = expressions
+                    .iter()
+                    .map(|&expression| {
+                        scope.spawn(move || {
+                            // A solver context can't be shared between threads
+                            let ctx = #context?;
+                            let invariant = [expression];
+                            #verification
+                        })
+                    })
+                    .collect();
+                for proof in proofs {
+                    exact &= proof
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))?
+                        .is_exact();
+                }
+                Ok(if exact {
+                    praborrow_prover::VerificationToken::new()
+                } else {
+                    praborrow_prover::VerificationToken::over_approximated()
+                })
+            })
+        }
+    }
+}
+
+/// Generates `invariant_fn()`, which evaluates `condition`, the conjunction of
+/// every runtime check.
+fn invariant_fn(condition: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        /// Returns whether every invariant holds, as a single boolean expression
+        /// that builds no `ConstitutionError`, for hot filters such as
+        /// `items.iter().filter(|x| x.invariant_fn())`. Nested fields are
+        /// included. Always `true` for a type without invariants.
+        #[inline]
+        pub fn invariant_fn(&self) -> bool {
+            #condition
+        }
+    }
+}
+
+/// Generates `is_valid()`, `enforce_law()` reduced to whether it passed.
+fn is_valid_fn() -> proc_macro2::TokenStream {
+    quote! {
+        /// Returns whether every invariant holds, as `enforce_law().is_ok()`.
+        pub fn is_valid(&self) -> bool {
+            CheckProtocol::enforce_law(self).is_ok()
+        }
+    }
+}
+
+/// Generates `guard()` and `try_guard()`, the mutation entry points that
+/// re-check the invariants once an edit is done.
+fn guard_fns() -> proc_macro2::TokenStream {
+    quote! {
+        /// Borrows `self` mutably, checking the invariants when the guard is
+        /// dropped. A violation panics; see `try_guard()` to get it back as an
+        /// error instead.
+        pub fn guard(&mut self) -> praborrow_core::ConstitutionGuard<'_, Self> {
+            praborrow_core::ConstitutionGuard::new(self)
+        }
+
+        /// Runs `edit` on `self`, then checks the invariants, returning the
+        /// violation instead of panicking. The edit is not rolled back.
+        pub fn try_guard<R>(
+            &mut self,
+            edit: impl FnOnce(&mut Self) -> R,
+        ) -> Result<R, praborrow_core::ConstitutionError> {
+            let mut guard = praborrow_core::ConstitutionGuard::new(self);
+            let result = edit(&mut guard);
+            guard.commit().map(|()| result)
+        }
+    }
+}
+
+/// Generates the `&[&str]` of `fields`' type-width domains. The widths of
+/// `usize`/`isize` and string lengths follow `target_pointer_width`, which is
+/// only known where the generated code is compiled.
+fn domain_slice(fields: &[(Member, Type)]) -> proc_macro2::TokenStream {
+    let [domain16, domain32, domain64] =
+        [16, 32, 64].map(|width| lowering::domain_constraints(fields, width));
+    if domain16 == domain64 && domain32 == domain64 {
+        return quote! { &[#(#domain64),*] };
+    }
+    quote! {
+        if cfg!(target_pointer_width = "16") {
+            &[#(#domain16),*]
+        } else if cfg!(target_pointer_width = "32") {
+            &[#(#domain32),*]
+        } else {
+            &[#(#domain64),*]
+        }
+    }
+}
+
+/// Generates code that polls a solver future to completion on the current thread.
+///
+/// Solver backends usually finish inside the first `poll`, so no runtime is
+/// needed. A future that does return `Pending` parks the thread until its
+/// waker unparks it, instead of spinning.
+fn block_on(future: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        {
+            struct ThreadWaker(std::thread::Thread);
+
+            impl std::task::Wake for ThreadWaker {
+                fn wake(self: std::sync::Arc<Self>) {
+                    self.0.unpark();
+                }
+
+                fn wake_by_ref(self: &std::sync::Arc<Self>) {
+                    self.0.unpark();
+                }
+            }
+
+            let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(
+                std::thread::current(),
+            )));
+            let mut cx = core::task::Context::from_waker(&waker);
+            let mut future = core::pin::pin!(#future);
+            loop {
+                if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+                    break result;
+                }
+                std::thread::park();
+            }
+        }
+    }
+}
+
+/// Declares a trait as a reusable fragment of invariants.
+///
+/// Structs fold the fragment's invariants into their own checks with
+/// `#[constitution(inherit = Trait)]`. The invariants are written against the
+/// inheriting struct's fields:
+///
+/// ```ignore
+/// #[constitution_fragment(invariant(self.created <= self.updated))]
+/// trait Timestamped {}
+///
+/// #[derive(Constitution)]
+/// #[constitution(inherit = Timestamped)]
+/// struct Post {
+///     created: u64,
+///     updated: u64,
+/// }
+/// ```
+///
+/// Item-less, non-generic fragment traits are markers: the derive implements
+/// them for every inheriting struct, so `Post: Timestamped` holds.
+///
+/// The fragment is delivered through a `macro_rules!` macro imported under the
+/// trait's name, so it can only be inherited within the defining crate, and
+/// the trait must be in scope (or named by path) at the `inherit` site.
+#[proc_macro_attribute]
+pub fn constitution_fragment(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(item as syn::ItemTrait);
+    let invariants = match attrs::parse_fragment_invariants(args.into()) {
+        Ok(invariants) => invariants,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+
+    let trait_name = &item.ident;
+    let fragment = quote::format_ident!("__constitution_fragment_{}", trait_name);
+    // Item-less markers are implemented by every inheriting struct
+    let marker =
+        (item.items.is_empty() && item.generics.params.is_empty()).then(|| quote! { marker, });
+    if marker.is_some() {
+        // A marker that is only inherited is never named anywhere else
+        item.attrs.push(syn::parse_quote! { #[allow(dead_code)] });
+    }
+    TokenStream::from(quote! {
+        #item
+
+        #[doc(hidden)]
+        macro_rules! #fragment {
+            ($($input:tt)*) => {
+                ::praborrow_defense::__constitution_inherit! {
+                    #[constitution(inherited(from = #trait_name, #marker #(invariant(#invariants)),*))]
+                    $($input)*
+                }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #fragment as #trait_name;
+    })
+}
+
+/// Declares named invariant sets that structs fold in with
+/// `#[constitution(use = "Name")]`.
+///
+/// Each set is written against the using struct's fields, in the same forms
+/// `#[invariant(...)]` accepts:
+///
+/// ```ignore
+/// define_invariants!(
+///     PositiveId = ["self.id > 0"],
+///     Versioned = [self.version >= 1, "self.version < 100"],
+/// );
+///
+/// #[derive(Constitution)]
+/// #[constitution(use = "PositiveId", use = "Versioned")]
+/// struct User {
+///     id: u64,
+///     version: u32,
+/// }
+/// ```
+///
+/// Like fragments, each set is delivered through a `macro_rules!` macro
+/// imported under its name, so it can only be used within the defining crate,
+/// and must be in scope (or named by path) at the `use` site.
+#[proc_macro]
+pub fn define_invariants(input: TokenStream) -> TokenStream {
+    let sets = parse_macro_input!(
+        input with syn::punctuated::Punctuated::<attrs::InvariantSet, syn::Token![,]>::parse_terminated
+    );
+
+    let delivery = sets.iter().map(|set| {
+        let name = &set.name;
+        let invariants = &set.invariants;
+        let set_macro = quote::format_ident!("__constitution_invariants_{}", name);
+        quote! {
+            #[doc(hidden)]
+            macro_rules! #set_macro {
+                ($($input:tt)*) => {
+                    ::praborrow_defense::__constitution_inherit! {
+                        #[constitution(inherited(from = #name, #(invariant(#invariants)),*))]
+                        $($input)*
+                    }
+                };
+            }
+
+            #[allow(unused_imports)]
+            pub(crate) use #set_macro as #name;
+        }
+    });
+    TokenStream::from(quote! { #(#delivery)* })
+}
+
+/// Checks a function's `#[requires(...)]` conditions on entry and its
+/// `#[ensures(...)]` conditions on return, with the return value bound to
+/// `result`.
+///
+/// The return type `T` becomes `Result<T, ConstitutionError>`. Place
+/// `#[contract]` above the conditions:
+///
+/// ```ignore
+/// #[contract]
+/// #[requires("x > 0")]
+/// #[ensures("result >= x")]
+/// fn double(x: u32) -> u32 {
+///     x * 2
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn contract(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = proc_macro2::TokenStream::from(args);
+        return TokenStream::from(
+            syn::Error::new_spanned(args, "`#[contract]` takes no arguments").to_compile_error(),
+        );
+    }
+    let item = parse_macro_input!(item as syn::ItemFn);
+    match conditions::expand(item) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
+/// Checks a condition relating two `Constitution` values, read as `a` and `b`.
+///
+/// The condition is evaluated with `a` and `b` borrowing the values, then
+/// proven with both values' fields pinned, as `a.<field>` and `b.<field>`.
+/// Evaluates to `Result<VerificationToken, ProofError>`, failing with
+/// `ProofError::InvariantViolated` if the condition doesn't hold. Only integer
+/// fields are supported for now.
+///
+/// ```ignore
+/// verify_relation!(inputs, outputs, "b.total <= a.total")?;
+/// ```
+#[proc_macro]
+pub fn verify_relation(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as relation::RelationArgs);
+    match relation::expand(args) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
+/// Asserts that two `Constitution` types have equivalent invariant sets.
+///
+/// Asks the solver whether each type's invariant conjunction implies the other's.
+/// Fields are matched by name, so a refactoring that splits or renames types can
+/// prove it neither weakened nor strengthened the original contract.
+///
+/// Panics naming the direction of implication that fails. Also panics if
+/// either type has runtime-only invariants, which the solver can't compare,
+/// or constrains a field the other type doesn't have, which the other side
+/// would leave unconstrained.
+///
+/// ```ignore
+/// assert_invariants_equivalent!(OldAccount, NewAccount);
+/// ```
+#[proc_macro]
+pub fn assert_invariants_equivalent(input: TokenStream) -> TokenStream {
+    let TypePair { old, new } = parse_macro_input!(input as TypePair);
+    let old_str = quote! { #old }.to_string();
+    let new_str = quote! { #new }.to_string();
+
+    let forward = block_on(quote! { ctx.verify_implication(old, new) });
+    let backward = block_on(quote! { ctx.verify_implication(new, old) });
+
+    let expanded = quote! {
+        {
+            use core::future::Future;
+
+            for (name, runtime_only) in [
+                (#old_str, <#old>::__CONSTITUTION_RUNTIME_ONLY),
+                (#new_str, <#new>::__CONSTITUTION_RUNTIME_ONLY),
+            ] {
+                if !runtime_only.is_empty() {
+                    panic!(
+                        "Invariants of `{}` can't be compared, as some are runtime-only: {:?}",
+                        name, runtime_only
+                    );
+                }
+            }
+            for (name, fields, other_name, other_fields) in [
+                (#old_str, <#old>::__CONSTITUTION_FIELDS, #new_str, <#new>::__CONSTITUTION_FIELDS),
+                (#new_str, <#new>::__CONSTITUTION_FIELDS, #old_str, <#old>::__CONSTITUTION_FIELDS),
+            ] {
+                for &(field, read) in fields {
+                    if read && !other_fields.iter().any(|&(other, _)| other == field) {
+                        panic!(
+                            "Invariants of `{}` constrain `{}`, which `{}` has no field for",
+                            name, field, other_name
+                        );
+                    }
                 }
             }
+
+            let ctx = praborrow_prover::SmtContext::new().expect("Failed to create SMT context");
+            let old = <#old as praborrow_prover::ProveInvariant>::invariant_expressions();
+            let new = <#new as praborrow_prover::ProveInvariant>::invariant_expressions();
+
+            let forward = #forward;
+            let backward = #backward;
+
+            match (forward, backward) {
+                (Ok(_), Ok(_)) => {}
+                (Err(e), Ok(_)) => panic!(
+                    "Invariants of `{}` do not imply those of `{}` (contract was strengthened): {}",
+                    #old_str, #new_str, e
+                ),
+                (Ok(_), Err(e)) => panic!(
+                    "Invariants of `{}` do not imply those of `{}` (contract was weakened): {}",
+                    #new_str, #old_str, e
+                ),
+                (Err(forward), Err(backward)) => panic!(
+                    "Invariants of `{}` and `{}` are unrelated: {}; {}",
+                    #old_str, #new_str, forward, backward
+                ),
+            }
         }
     };
 
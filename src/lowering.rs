@@ -0,0 +1,1344 @@
+//! Lowering of invariant expressions into the prover's expression syntax.
+//!
+//! The runtime check evaluates an invariant as ordinary Rust, but the prover's
+//! `ExpressionParser` only understands `self.field` operands, literals and
+//! operators. Constructs with a known meaning over the struct's fields are
+//! rewritten here into that subset:
+//!
+//! - `self.arr.iter().sum()` on a fixed-size array `[T; N]` expands to
+//!   `(self.arr__0 + self.arr__1 + ... + self.arr__{N-1})`
+//! - `self.c.is_ascii_digit()` (and the other `is_ascii_*` classes) on a `char`
+//!   or `u8` field expands to the matching codepoint ranges
+//! - `self.x.rem_euclid(n)` and `self.x.div_euclid(n)` on an integer field, with
+//!   a positive integer literal `n`, become `(x % n)` and `(x / n)` when the
+//!   field is unsigned. On a signed field they expand to
+//!   `r = (((x % n) + n) % n)` and `((x / n) - ((r - (x % n)) / n))`, which
+//!   agree with SMT-LIB's Euclidean `mod`/`div` whether the backend's `%`
+//!   truncates or not, and never leave the field's type for `n <= MAX / 2`.
+//!   Larger divisors are rejected, taking `isize` at its narrowest, 16 bits
+//! - `self.x.is_power_of_two()` on an unsigned integer field expands to
+//!   `(self.x != 0 && (self.x & (self.x - 1)) == 0)`
+//! - `u8::try_from(x).is_ok()` (for any integer target) expands to the target's
+//!   range, e.g. `(x >= 0 && x <= 255)`. Bounds beyond the prover's 64-bit
+//!   literals are dropped, which only holds while `x` fits in 64 bits, so a
+//!   conversion that drops one on an `i128` or `u128` field is rejected.
+//!   Bounds of `usize` and `isize` that depend on the pointer width become
+//!   symbols such as `self.usize__MAX`, pinned to the target's value
+//! - `self.arr[1]` on a fixed-size integer array, with a literal index, becomes
+//!   the element symbol `self.arr__1`. Indices past the end are rejected
+//! - `is_sorted(self.arr)` / `is_sorted_unique(self.arr)` on a fixed-size integer
+//!   array expand to `(self.arr__0 <= self.arr__1 && ...)` (`<` for unique)
+//! - `(0..4).all(|i| ..)` over a literal range, which is what
+//!   `forall i in 0..4 => ..` and `.. for i in 0..4` desugar to, unrolls into
+//!   one conjunct per value of `i`, so `self.buf[i]` becomes `self.buf__0`,
+//!   `self.buf__1`, ... Bounds may use a fixed-size array's `self.buf.len()`
+//! - `self.name.len()` on a `String`, `&str`, `Vec` or `&[T]` field becomes
+//!   the integer symbol `self.name.len`, and `self.name.is_empty()` becomes
+//!   `(self.name.len == 0)`. Comparisons of the field with a string literal and
+//!   `self.name.starts_with("..")` need no lowering; the prover models them
+//!   with its string theory
+//! - Character literals such as `'A'` become their codepoint, `65`, matching
+//!   how `char` fields are handed to the solver
+//! - Binary, octal, suffixed and `_`-separated integer literals such as
+//!   `0b1111_0000` become plain decimal, `240`
+//! - On an `Option` field, `self.limit.is_some()` and `is_none()` become
+//!   `self.limit.is_some == true` / `== false` over the boolean symbol
+//!   `self.limit.is_some`. On an `Option<integer>` field the closure forms
+//!   guard the inner value, which the solver sees as `self.limit`: `map_or(true, |l| ..)` and
+//!   `is_none_or(|l| ..)` become `(self.limit.is_some == false || ..)`, while
+//!   `map_or(false, |l| ..)` and `is_some_and(|l| ..)` become
+//!   `(self.limit.is_some == true && ..)`
+//! - `self.p.0` on a newtype (or tuple) field becomes the symbol `self.p__0`;
+//!   nested newtypes unwrap recursively, e.g. `self.p.0.0` becomes `self.p__0__0`
+//! - Associated constants such as `Self::MAX` become the symbol `self.Self__MAX`,
+//!   which the field provider pins to the constant's value
+//! - Const generic parameters such as `N` become the symbol `self.N`, likewise
+//!   pinned to the parameter's value
+//! - `(1..=100).contains(&x)` over a range with both bounds, which is what
+//!   `x in 1..=100` desugars to, becomes `(x >= 1 && x <= 100)` (`x < end` for
+//!   an exclusive range)
+//! - `if a { b } else { true }`, which is what a conditional invariant
+//!   `a => b` desugars to, becomes the implication `(!(a) || (b))`
+//! - `self.x as T` and `T::from(self.x)` on an integer field, with an integer
+//!   `T` that holds every value of the field's type, become `self.x`. The
+//!   solver compares integers by value, so `(self.small as u32) < self.big`
+//!   needs no common width. Narrowing casts are left alone
+//! - An invariant dividing by anything but a nonzero literal, as in
+//!   `self.offset % self.align == 0`, becomes
+//!   `(self.align != 0 && (self.offset % self.align == 0))`. Rust panics on a
+//!   zero divisor, so the invariant can't hold there, whereas the solver's
+//!   division is total. A signed field divided by a divisor that may be `-1`
+//!   is likewise guarded with `(divisor != -1 || self.x > MIN)`. The guards
+//!   apply to the whole invariant, so one that only divides behind `&&`, `||`
+//!   or `=>` fails to prove where the division is undefined. `/` and `%`
+//!   themselves keep Rust's truncating meaning
+//!
+//! A tuple struct field keeps its index in the field's own symbol and in dotted
+//! ones, as in `self.0` and `self.0.is_some`, while symbols joined with `__`
+//! start from `_<index>`, since they must be identifiers: the elements of
+//! `self.0` are `self._0__0`, `self._0__1`, ...
+
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Expr, Ident, Member, Type, parse_quote};
+
+use crate::bounds::{int_literal, self_field};
+
+/// Returns the name a field's derived solver symbols start from. Tuple struct
+/// fields become `_<index>`, since symbols must be identifiers.
+fn symbol_root(field: &Member) -> String {
+    match field {
+        Member::Named(name) => name.to_string(),
+        Member::Unnamed(index) => format!("_{}", index.index),
+    }
+}
+
+/// Returns the solver symbol for an element of a fixed-size array field.
+pub(crate) fn element_symbol(field: &Member, index: usize) -> String {
+    format!("{}__{}", symbol_root(field), index)
+}
+
+/// Makes a `self.<symbol>` expression for a derived solver symbol.
+fn symbol_place(symbol: &str) -> Expr {
+    let symbol = Ident::new(symbol, proc_macro2::Span::call_site());
+    parse_quote! { self.#symbol }
+}
+
+/// Returns the solver symbol and the runtime place for a tuple-index projection
+/// such as `self.p.0.1`, or `None` if the expression isn't one.
+fn projection(expr: &Expr) -> Option<(String, TokenStream)> {
+    let Expr::Field(field) = expr else {
+        return None;
+    };
+    let Member::Unnamed(index) = &field.member else {
+        return None;
+    };
+    let (base_symbol, base_place) = match self_field(&field.base) {
+        Some(name) => (symbol_root(name), quote! { #name }),
+        None => projection(&field.base)?,
+    };
+    Some((
+        format!("{}__{}", base_symbol, index.index),
+        quote! { #base_place.#index },
+    ))
+}
+
+/// Collects the tuple-index projections used by the invariants, keyed by solver
+/// symbol. The place is relative to the struct, e.g. `p.0` for `self.p.0`.
+pub(crate) fn projections<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+) -> BTreeMap<String, TokenStream> {
+    struct Collector(BTreeMap<String, TokenStream>);
+
+    impl<'ast> Visit<'ast> for Collector {
+        fn visit_expr(&mut self, node: &'ast Expr) {
+            match projection(node) {
+                Some((symbol, place)) => {
+                    self.0.insert(symbol, place);
+                }
+                None => visit::visit_expr(self, node),
+            }
+        }
+    }
+
+    let mut collector = Collector(BTreeMap::new());
+    for expr in exprs {
+        collector.visit_expr(expr);
+    }
+    collector.0
+}
+
+/// Returns the associated constant named by a `Self::NAME` path.
+pub(crate) fn associated_const(expr: &Expr) -> Option<&Ident> {
+    let Expr::Path(path) = expr else {
+        return None;
+    };
+    let segments = &path.path.segments;
+    if path.qself.is_some() || segments.len() != 2 || segments[0].ident != "Self" {
+        return None;
+    }
+    segments
+        .iter()
+        .all(|segment| segment.arguments.is_none())
+        .then_some(&segments[1].ident)
+}
+
+/// Returns the solver symbol for an associated constant, e.g. `Self__MAX` for
+/// `Self::MAX`.
+fn const_symbol(name: &Ident) -> String {
+    format!("Self__{}", name)
+}
+
+/// Collects the associated constants used by the invariants, keyed by solver
+/// symbol.
+pub(crate) fn associated_consts<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+) -> BTreeMap<String, Ident> {
+    struct Collector(BTreeMap<String, Ident>);
+
+    impl<'ast> Visit<'ast> for Collector {
+        fn visit_expr(&mut self, node: &'ast Expr) {
+            match associated_const(node) {
+                Some(name) => {
+                    self.0.insert(const_symbol(name), name.clone());
+                }
+                None => visit::visit_expr(self, node),
+            }
+        }
+    }
+
+    let mut collector = Collector(BTreeMap::new());
+    for expr in exprs {
+        collector.visit_expr(expr);
+    }
+    collector.0
+}
+
+/// Returns the const generic parameter named by a bare path such as `N`.
+fn const_param<'a>(expr: &Expr, const_params: &'a [Ident]) -> Option<&'a Ident> {
+    let Expr::Path(path) = expr else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let ident = path.path.get_ident()?;
+    const_params.iter().find(|param| *param == ident)
+}
+
+/// Collects the const generic parameters used by the invariants, in order of
+/// first mention.
+pub(crate) fn used_const_params<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+    const_params: &[Ident],
+) -> Vec<Ident> {
+    struct Collector<'p>(Vec<Ident>, &'p [Ident]);
+
+    impl<'ast> Visit<'ast> for Collector<'_> {
+        fn visit_expr(&mut self, node: &'ast Expr) {
+            match const_param(node, self.1) {
+                Some(param) if !self.0.contains(param) => self.0.push(param.clone()),
+                Some(_) => {}
+                None => visit::visit_expr(self, node),
+            }
+        }
+    }
+
+    let mut collector = Collector(Vec::new(), const_params);
+    for expr in exprs {
+        collector.visit_expr(expr);
+    }
+    collector.0
+}
+
+/// Returns the field and the path of a comparison such as
+/// `self.color != Color::Red`, either way round, whose path names a constant
+/// of the field's own type: its second-last segment is the type's name.
+fn variant_comparison<'a>(
+    binary: &'a syn::ExprBinary,
+    fields: &[(Member, Type)],
+) -> Option<(&'a Member, &'a syn::Path)> {
+    if !matches!(binary.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+        return None;
+    }
+    let (field, path) = match (&*binary.left, &*binary.right) {
+        (side, Expr::Path(path)) | (Expr::Path(path), side) => (self_field(side)?, path),
+        _ => return None,
+    };
+    let (_, ty) = fields.iter().find(|(name, _)| name == field)?;
+    if crate::is_integer_type(ty)
+        || crate::is_float_type(ty)
+        || crate::is_bool_type(ty)
+        || crate::is_char_type(ty)
+        || crate::is_string_type(ty)
+    {
+        return None;
+    }
+    let segments = &path.path.segments;
+    let owner = segments.iter().nth_back(1)?;
+    (path.qself.is_none()
+        && segments.iter().all(|segment| segment.arguments.is_none())
+        && crate::type_ident(ty).is_some_and(|name| owner.ident == name))
+    .then_some((field, &path.path))
+}
+
+/// Returns the conditions under which every division in a lowered invariant is
+/// defined in Rust: `divisor != 0` for each divisor that isn't a nonzero
+/// literal, and `(divisor != -1 || x > MIN)` for each signed field `x` divided
+/// by anything but a literal other than `-1`.
+fn division_guards(expr: &Expr, fields: &[(Member, Type)]) -> Vec<Expr> {
+    struct Collector<'f>(Vec<Expr>, &'f [(Member, Type)]);
+
+    impl<'ast> Visit<'ast> for Collector<'_> {
+        fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+            if matches!(node.op, syn::BinOp::Div(_) | syn::BinOp::Rem(_)) {
+                let divisor = &node.right;
+                let literal = int_literal(divisor);
+                let mut guards = Vec::new();
+                if literal.is_none_or(|n| n == 0) {
+                    guards.push(parse_quote! { #divisor != 0 });
+                }
+                if literal.is_none_or(|n| n == -1)
+                    && let Some(above_min) = above_min(&node.left, self.1)
+                {
+                    guards.push(match literal {
+                        Some(_) => above_min,
+                        None => {
+                            // A literal token, so it renders as `-1` rather than `- 1`
+                            let minus_one = int_lit(-1);
+                            parse_quote! { (#divisor != #minus_one || #above_min) }
+                        }
+                    });
+                }
+                for guard in guards {
+                    if !self.0.contains(&guard) {
+                        self.0.push(guard);
+                    }
+                }
+            }
+            visit::visit_expr_binary(self, node);
+        }
+    }
+
+    let mut collector = Collector(Vec::new(), fields);
+    collector.visit_expr(expr);
+    collector.0
+}
+
+/// Returns `self.x > MIN` for a signed integer field `x`, whose `MIN / -1`
+/// overflows. `i64::MIN` is beyond the prover's literals, so an `i64` field is
+/// compared with `MIN + 1` instead.
+fn above_min(value: &Expr, fields: &[(Member, Type)]) -> Option<Expr> {
+    let field = self_field(value)?;
+    let (_, ty) = fields.iter().find(|(name, _)| name == field)?;
+    let ty = crate::type_ident(ty)?;
+    let (min, _) = sized_integer_bounds(&ty, 64)?;
+    if min == 0 {
+        return None;
+    }
+    match type_bounds(&ty)? {
+        (Some(min), _) => Some(parse_quote! { #value > #min }),
+        _ => (min + 1 > i64::MIN.into()).then(|| {
+            let next = int_lit(min + 1);
+            parse_quote! { #value >= #next }
+        }),
+    }
+}
+
+/// Returns the solver symbol for a variant compared with a field, e.g.
+/// `Color__Red` for `Color::Red`.
+pub(crate) fn variant_symbol(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("__")
+}
+
+/// Collects the variants compared with a field by `==` or `!=`, paired with
+/// the field, in order of first mention.
+pub(crate) fn compared_variants<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+    fields: &[(Member, Type)],
+) -> Vec<(Member, syn::Path)> {
+    struct Collector<'f>(Vec<(Member, syn::Path)>, &'f [(Member, Type)]);
+
+    impl<'ast> Visit<'ast> for Collector<'_> {
+        fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+            if let Some((field, path)) = variant_comparison(node, self.1)
+                && !self
+                    .0
+                    .iter()
+                    .any(|(seen, known)| seen == field && known == path)
+            {
+                self.0.push((field.clone(), path.clone()));
+            }
+            visit::visit_expr_binary(self, node);
+        }
+    }
+
+    let mut collector = Collector(Vec::new(), fields);
+    for expr in exprs {
+        collector.visit_expr(expr);
+    }
+    collector.0
+}
+
+/// Collects the single-level nested field accesses used by the invariants,
+/// such as `self.limits.max`, keyed by their dotted path (`limits.max`).
+pub(crate) fn nested_fields<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+) -> BTreeMap<String, TokenStream> {
+    struct Collector(BTreeMap<String, TokenStream>);
+
+    impl<'ast> Visit<'ast> for Collector {
+        fn visit_expr_field(&mut self, node: &'ast syn::ExprField) {
+            match (self_field(&node.base), &node.member) {
+                (Some(outer), Member::Named(inner)) => {
+                    let symbol = format!("{}.{}", crate::member_name(outer), inner);
+                    self.0.insert(symbol, quote! { #outer.#inner });
+                }
+                _ => visit::visit_expr_field(self, node),
+            }
+        }
+    }
+
+    let mut collector = Collector(BTreeMap::new());
+    for expr in exprs {
+        collector.visit_expr(expr);
+    }
+    collector.0
+}
+
+/// Returns the element type and length of a fixed-size array type with a
+/// literal length.
+pub(crate) fn fixed_array(ty: &Type) -> Option<(&Type, usize)> {
+    match ty {
+        Type::Array(array) => match &array.len {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(len),
+                ..
+            }) => Some((&array.elem, len.base10_parse().ok()?)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the field and length of `self.arr` when `arr` is a fixed-size array
+/// of integers, whose elements the solver sees as `arr__<i>`.
+pub(crate) fn integer_array_field<'a>(
+    expr: &'a Expr,
+    fields: &[(Member, Type)],
+) -> Option<(&'a Member, usize)> {
+    let name = self_field(expr)?;
+    let (_, ty) = fields.iter().find(|(field, _)| field == name)?;
+    let (elem, len) = fixed_array(ty)?;
+    crate::is_integer_type(elem).then_some((name, len))
+}
+
+/// Lowers an invariant for the solver.
+///
+/// Returns `None` if the expression needed no rewriting, so callers can keep
+/// the invariant string exactly as written.
+pub(crate) fn lower(
+    expr: &Expr,
+    fields: &[(Member, Type)],
+    const_params: &[Ident],
+) -> syn::Result<Option<Expr>> {
+    let mut lowered = expr.clone();
+    let mut lowerer = Lowerer {
+        fields,
+        const_params,
+        changed: false,
+        error: None,
+    };
+    lowerer.visit_expr_mut(&mut lowered);
+
+    // Rust panics where a division is undefined, so the invariant can't hold
+    // there. Guarding the whole expression, rather than the comparison, keeps
+    // the guard out of negations.
+    let guards = division_guards(&lowered, fields);
+    if !guards.is_empty() {
+        lowered = parse_quote! { (#(#guards &&)* (#lowered)) };
+        lowerer.changed = true;
+    }
+
+    match lowerer.error {
+        Some(e) => Err(e),
+        None if lowerer.changed => Ok(Some(lowered)),
+        None => Ok(None),
+    }
+}
+
+struct Lowerer<'a> {
+    fields: &'a [(Member, Type)],
+    const_params: &'a [Ident],
+    changed: bool,
+    error: Option<syn::Error>,
+}
+
+impl Lowerer<'_> {
+    fn push_error(&mut self, err: syn::Error) {
+        match &mut self.error {
+            Some(existing) => existing.combine(err),
+            None => self.error = Some(err),
+        }
+    }
+
+    fn field_type(&self, name: &Member) -> Option<&Type> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, ty)| ty)
+    }
+
+    /// Expands `self.arr.iter().sum()` into an explicit sum of elements.
+    fn lower_sum(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let Expr::MethodCall(iter) = &*call.receiver else {
+            return None;
+        };
+        if iter.method != "iter" || !iter.args.is_empty() {
+            return None;
+        }
+        let field = self_field(&iter.receiver)?;
+
+        match self.field_type(field).and_then(fixed_array) {
+            Some((_, 0)) => Some(parse_quote! { 0 }),
+            Some((_, len)) => {
+                let terms: Vec<Expr> = (0..len)
+                    .map(|i| symbol_place(&element_symbol(field, i)))
+                    .collect();
+                Some(parse_quote! { (#(#terms)+*) })
+            }
+            None => {
+                self.push_error(syn::Error::new_spanned(
+                    call,
+                    format!(
+                        "`.iter().sum()` can only be verified on fixed-size arrays with a literal length; `{}` is not one",
+                        crate::member_name(field)
+                    ),
+                ));
+                None
+            }
+        }
+    }
+}
+
+/// Codepoint ranges (inclusive) matched by each `is_ascii_*` method.
+fn ascii_class_ranges(method: &str) -> Option<&'static [(u32, u32)]> {
+    const DIGIT: (u32, u32) = (0x30, 0x39);
+    const UPPER: (u32, u32) = (0x41, 0x5A);
+    const LOWER: (u32, u32) = (0x61, 0x7A);
+
+    Some(match method {
+        "is_ascii" => &[(0x00, 0x7F)],
+        "is_ascii_digit" => &[DIGIT],
+        "is_ascii_uppercase" => &[UPPER],
+        "is_ascii_lowercase" => &[LOWER],
+        "is_ascii_alphabetic" => &[UPPER, LOWER],
+        "is_ascii_alphanumeric" => &[DIGIT, UPPER, LOWER],
+        "is_ascii_hexdigit" => &[DIGIT, (0x41, 0x46), (0x61, 0x66)],
+        "is_ascii_graphic" => &[(0x21, 0x7E)],
+        "is_ascii_punctuation" => &[(0x21, 0x2F), (0x3A, 0x40), (0x5B, 0x60), (0x7B, 0x7E)],
+        "is_ascii_whitespace" => &[(0x09, 0x0A), (0x0C, 0x0D), (0x20, 0x20)],
+        "is_ascii_control" => &[(0x00, 0x1F), (0x7F, 0x7F)],
+        _ => return None,
+    })
+}
+
+impl Lowerer<'_> {
+    /// Expands `self.c.is_ascii_*()` on a `char`/`u8` field into codepoint ranges.
+    fn lower_ascii_class(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let ranges = ascii_class_ranges(&call.method.to_string())?;
+        if !call.args.is_empty() {
+            return None;
+        }
+        let field = self_field(&call.receiver)?;
+        let ty = self.field_type(field)?;
+        if !(crate::is_char_type(ty) || crate::type_ident(ty).as_deref() == Some("u8")) {
+            return None;
+        }
+
+        let terms: Vec<Expr> = ranges
+            .iter()
+            .map(|&(lo, hi)| {
+                let lo = proc_macro2::Literal::u32_unsuffixed(lo);
+                let hi = proc_macro2::Literal::u32_unsuffixed(hi);
+                parse_quote! { (self.#field >= #lo && self.#field <= #hi) }
+            })
+            .collect();
+        match terms.as_slice() {
+            [single] => Some(single.clone()),
+            _ => Some(parse_quote! { (#(#terms)||*) }),
+        }
+    }
+}
+
+impl Lowerer<'_> {
+    /// Expands `self.x.rem_euclid(n)` / `self.x.div_euclid(n)` on an integer field.
+    ///
+    /// Unsigned fields need no correction. On a signed field the forms stay
+    /// within the type for `n <= MAX / 2`, so a larger divisor is rejected
+    /// rather than left to wrap under bitvector semantics.
+    fn lower_euclid(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        if call.args.len() != 1 {
+            return None;
+        }
+        let field = self_field(&call.receiver)?;
+        let (min, max) = self
+            .field_type(field)
+            .filter(|ty| crate::is_integer_type(ty))
+            .and_then(crate::type_ident)
+            .and_then(|ty| sized_integer_bounds(&ty, 16))?;
+
+        let divisor = &call.args[0];
+        let Some(n) = int_literal(divisor).filter(|&n| n > 0) else {
+            self.push_error(syn::Error::new_spanned(
+                divisor,
+                format!(
+                    "`.{}()` can only be verified with a positive integer literal divisor",
+                    call.method
+                ),
+            ));
+            return None;
+        };
+
+        if min == 0 {
+            return Some(if call.method == "rem_euclid" {
+                parse_quote! { (self.#field % #divisor) }
+            } else {
+                parse_quote! { (self.#field / #divisor) }
+            });
+        }
+        if n > max / 2 {
+            self.push_error(syn::Error::new_spanned(
+                divisor,
+                format!(
+                    "`.{}()` on a signed field can only be verified with a divisor of at most {}",
+                    call.method,
+                    max / 2
+                ),
+            ));
+            return None;
+        }
+
+        // The remainder's correction `rem - x % n` is exactly 0 or n
+        let rem: Expr = parse_quote! { (((self.#field % #divisor) + #divisor) % #divisor) };
+        if call.method == "rem_euclid" {
+            Some(rem)
+        } else {
+            Some(parse_quote! {
+                ((self.#field / #divisor) - ((#rem - (self.#field % #divisor)) / #divisor))
+            })
+        }
+    }
+}
+
+/// Inclusive bounds of an integer type, with `usize`/`isize` taken to be
+/// `pointer_width` bits wide.
+pub(crate) fn sized_integer_bounds(ty: &str, pointer_width: u32) -> Option<(i128, i128)> {
+    let ty = match (ty, pointer_width) {
+        ("isize", 16) => "i16",
+        ("isize", 32) => "i32",
+        ("isize", _) => "i64",
+        ("usize", 16) => "u16",
+        ("usize", 32) => "u32",
+        ("usize", _) => "u64",
+        (ty, _) => ty,
+    };
+    Some(match ty {
+        "i8" => (i8::MIN.into(), i8::MAX.into()),
+        "i16" => (i16::MIN.into(), i16::MAX.into()),
+        "i32" => (i32::MIN.into(), i32::MAX.into()),
+        "i64" => (i64::MIN.into(), i64::MAX.into()),
+        "i128" => (i128::MIN, i128::MAX),
+        "u8" => (0, u8::MAX.into()),
+        "u16" => (0, u16::MAX.into()),
+        "u32" => (0, u32::MAX.into()),
+        "u64" => (0, u64::MAX.into()),
+        "u128" => (0, i128::MAX),
+        _ => return None,
+    })
+}
+
+/// Returns the type-width constraints that tell the solver the fields'
+/// domains, e.g. `self.x >= 0 && self.x <= 255` for a `u8` field.
+///
+/// Covers integer and `char` fields, the elements of fixed-size integer arrays
+/// and string and `Vec` lengths. `usize`/`isize` are `pointer_width` bits wide,
+/// and so are lengths. Bounds beyond the prover's 64-bit literals are dropped.
+pub(crate) fn domain_constraints(fields: &[(Member, Type)], pointer_width: u32) -> Vec<String> {
+    let integer_bounds = |ty: &str| sized_integer_bounds(ty, pointer_width);
+    let mut places: Vec<(Expr, (i128, i128))> = Vec::new();
+    for (name, ty) in fields {
+        if crate::is_char_type(ty) {
+            places.push((
+                parse_quote! { self.#name },
+                (0, u32::from(char::MAX).into()),
+            ));
+        } else if let Some(bounds) = crate::type_ident(ty).as_deref().and_then(integer_bounds) {
+            places.push((parse_quote! { self.#name }, bounds));
+        } else if (crate::is_string_type(ty) || crate::is_vec_type(ty))
+            && let Some(bounds) = integer_bounds("usize")
+        {
+            places.push((parse_quote! { self.#name.len }, bounds));
+        } else if let Some(bounds) = crate::option_inner(ty)
+            .and_then(crate::type_ident)
+            .as_deref()
+            .and_then(integer_bounds)
+        {
+            places.push((parse_quote! { self.#name }, bounds));
+        } else if let Some((elem, len)) = fixed_array(ty)
+            && let Some(bounds) = crate::type_ident(elem).as_deref().and_then(integer_bounds)
+        {
+            for index in 0..len {
+                places.push((symbol_place(&element_symbol(name, index)), bounds));
+            }
+        }
+    }
+
+    places
+        .into_iter()
+        .filter_map(|(place, (lo, hi))| {
+            range_check(&place, lo, hi).map(|check| crate::render(&check))
+        })
+        .collect()
+}
+
+/// Builds `x >= lo && x <= hi`, dropping sides beyond the prover's 64-bit
+/// literals. Returns `None` if neither side fits.
+fn range_check(value: &Expr, lo: i128, hi: i128) -> Option<Expr> {
+    bounds_check(
+        value,
+        (lo > i64::MIN.into()).then(|| int_lit(lo)),
+        (hi <= i64::MAX.into()).then(|| int_lit(hi)),
+    )
+}
+
+/// Builds `x >= lo && x <= hi` from whichever sides are given.
+fn bounds_check(value: &Expr, lo: Option<Expr>, hi: Option<Expr>) -> Option<Expr> {
+    let lo = lo.map(|lo| binary(value.clone(), syn::BinOp::Ge(Default::default()), lo));
+    let hi = hi.map(|hi| binary(value.clone(), syn::BinOp::Le(Default::default()), hi));
+    lo.into_iter()
+        .chain(hi)
+        .reduce(|left, right| binary(left, syn::BinOp::And(Default::default()), right))
+}
+
+/// The `usize`/`isize` bounds that vary with the target's pointer width.
+const POINTER_BOUNDS: [(&str, &str); 3] = [("usize", "MAX"), ("isize", "MIN"), ("isize", "MAX")];
+
+/// Returns the solver symbol for a pointer-width-dependent bound, e.g.
+/// `usize__MAX`, which the field provider pins to the target's value.
+fn pointer_bound_symbol(ty: &str, bound: &str) -> String {
+    format!("{}__{}", ty, bound)
+}
+
+/// Returns `T::MIN` and `T::MAX` for the solver, as literals where they are the
+/// same on every target and as pinned symbols where they depend on the pointer
+/// width. Literals beyond the prover's 64 bits are dropped.
+fn type_bounds(ty: &str) -> Option<(Option<Expr>, Option<Expr>)> {
+    let (lo, hi) = sized_integer_bounds(ty, 64)?;
+    let (narrow_lo, narrow_hi) = sized_integer_bounds(ty, 16)?;
+    let side = |value: i128, narrow: i128, bound: &str| {
+        if value != narrow {
+            Some(symbol_place(&pointer_bound_symbol(ty, bound)))
+        } else {
+            (i128::from(i64::MIN) < value && value <= i64::MAX.into()).then(|| int_lit(value))
+        }
+    };
+    Some((side(lo, narrow_lo, "MIN"), side(hi, narrow_hi, "MAX")))
+}
+
+/// Returns the pointer-width-dependent bounds the lowered invariants read, as
+/// their solver symbol, type and constant, e.g. `usize__MAX`, `usize`, `MAX`.
+pub(crate) fn pointer_bounds<'a>(
+    smt: impl IntoIterator<Item = &'a str>,
+) -> Vec<(String, Type, Ident)> {
+    let smt: Vec<_> = smt.into_iter().collect();
+    POINTER_BOUNDS
+        .iter()
+        .map(|(ty, bound)| (pointer_bound_symbol(ty, bound), ty, bound))
+        .filter(|(symbol, ..)| {
+            let place = format!("self.{}", symbol);
+            smt.iter().any(|expr| expr.contains(&place))
+        })
+        .map(|(symbol, ty, bound)| {
+            let span = proc_macro2::Span::call_site();
+            let ty = Ident::new(ty, span);
+            (symbol, parse_quote! { #ty }, Ident::new(bound, span))
+        })
+        .collect()
+}
+
+/// Matches `T::try_from(x).is_ok()` with an integer target `T`, returning the
+/// target's name and `x`.
+pub(crate) fn try_from_check(call: &syn::ExprMethodCall) -> Option<(String, &Expr)> {
+    if call.method != "is_ok" || !call.args.is_empty() {
+        return None;
+    }
+    let Expr::Call(conversion) = &*call.receiver else {
+        return None;
+    };
+    let Expr::Path(func) = &*conversion.func else {
+        return None;
+    };
+    let segments: Vec<_> = func.path.segments.iter().collect();
+    match (segments.as_slice(), conversion.args.len()) {
+        ([target, method], 1) if method.ident == "try_from" => {
+            let target = target.ident.to_string();
+            sized_integer_bounds(&target, 64)?;
+            Some((target, &conversion.args[0]))
+        }
+        _ => None,
+    }
+}
+
+impl Lowerer<'_> {
+    /// Expands `T::try_from(x).is_ok()` into the range of `T`.
+    fn lower_try_from(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let (target, value) = try_from_check(call)?;
+        let (lo, hi) = type_bounds(&target)?;
+        if (lo.is_none() || hi.is_none()) && self.reads_128_bit_field(value) {
+            self.push_error(syn::Error::new_spanned(
+                call,
+                format!(
+                    "`{}::try_from(..)` on a 128-bit field can't be verified: the target's range exceeds the prover's 64-bit literals",
+                    target
+                ),
+            ));
+            return None;
+        }
+
+        Some(match bounds_check(value, lo, hi) {
+            Some(check) => Expr::Paren(syn::ExprParen {
+                attrs: Vec::new(),
+                paren_token: Default::default(),
+                expr: Box::new(check),
+            }),
+            None => parse_quote! { true },
+        })
+    }
+
+    /// Returns whether `expr` reads an `i128` or `u128` field, whose values
+    /// the solver sees at full width.
+    fn reads_128_bit_field(&self, expr: &Expr) -> bool {
+        struct Finder<'l, 'a>(&'l Lowerer<'a>, bool);
+
+        impl<'ast> Visit<'ast> for Finder<'_, '_> {
+            fn visit_expr(&mut self, node: &'ast Expr) {
+                let wide = self_field(node)
+                    .and_then(|field| self.0.field_type(field))
+                    .and_then(crate::type_ident)
+                    .is_some_and(|ty| ty == "i128" || ty == "u128");
+                self.1 |= wide;
+                visit::visit_expr(self, node);
+            }
+        }
+
+        let mut finder = Finder(self, false);
+        finder.visit_expr(expr);
+        finder.1
+    }
+}
+
+// Nodes are built directly rather than with `parse_quote!`: re-parsing splits a
+// negative literal into `- 32768`, which the prover rejects.
+
+fn binary(left: Expr, op: syn::BinOp, right: Expr) -> Expr {
+    Expr::Binary(syn::ExprBinary {
+        attrs: Vec::new(),
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    })
+}
+
+fn int_lit(value: i128) -> Expr {
+    Expr::Lit(syn::ExprLit {
+        attrs: Vec::new(),
+        lit: syn::Lit::Int(proc_macro2::Literal::i128_unsuffixed(value).into()),
+    })
+}
+
+impl Lowerer<'_> {
+    /// Unrolls `is_sorted(self.arr)` / `is_sorted_unique(self.arr)` into pairwise
+    /// comparisons of adjacent elements.
+    fn lower_is_sorted(&mut self, call: &syn::ExprCall) -> Option<Expr> {
+        let Expr::Path(func) = &*call.func else {
+            return None;
+        };
+        let strict = match func.path.get_ident()?.to_string().as_str() {
+            "is_sorted" => false,
+            "is_sorted_unique" => true,
+            _ => return None,
+        };
+        if call.args.len() != 1 {
+            return None;
+        }
+        let (field, len) = integer_array_field(&call.args[0], self.fields)?;
+
+        let element = |i| symbol_place(&element_symbol(field, i));
+        let op = if strict {
+            syn::BinOp::Lt(Default::default())
+        } else {
+            syn::BinOp::Le(Default::default())
+        };
+        let pairs = (1..len).map(|i| binary(element(i - 1), op, element(i)));
+        Some(
+            match pairs
+                .reduce(|left, right| binary(left, syn::BinOp::And(Default::default()), right))
+            {
+                Some(chain) => parse_quote! { (#chain) },
+                None => parse_quote! { true },
+            },
+        )
+    }
+}
+
+/// Turns `(start..=end).contains(&x)` into `(x >= start && x <= end)`, or
+/// `x < end` for an exclusive range. Ranges missing a bound are left alone.
+fn contains(call: &syn::ExprMethodCall) -> Option<Expr> {
+    let Expr::Paren(receiver) = call.receiver.as_ref() else {
+        return None;
+    };
+    let Expr::Range(range) = receiver.expr.as_ref() else {
+        return None;
+    };
+    let (Some(start), Some(end)) = (&range.start, &range.end) else {
+        return None;
+    };
+    let [Expr::Reference(value)] = call.args.iter().collect::<Vec<_>>().as_slice() else {
+        return None;
+    };
+    let value = &value.expr;
+    Some(match range.limits {
+        syn::RangeLimits::Closed(_) => parse_quote! { (#value >= #start && #value <= #end) },
+        syn::RangeLimits::HalfOpen(_) => parse_quote! { (#value >= #start && #value < #end) },
+    })
+}
+
+/// Turns `if a { b } else { true }` into `(!(a) || (b))`. Other `if`
+/// expressions are left alone.
+fn implication(branch: &syn::ExprIf) -> Option<Expr> {
+    let [syn::Stmt::Expr(then, None)] = branch.then_branch.stmts.as_slice() else {
+        return None;
+    };
+    let (_, otherwise) = branch.else_branch.as_ref()?;
+    let Expr::Block(otherwise) = otherwise.as_ref() else {
+        return None;
+    };
+    let [
+        syn::Stmt::Expr(
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Bool(fallback),
+                ..
+            }),
+            None,
+        ),
+    ] = otherwise.block.stmts.as_slice()
+    else {
+        return None;
+    };
+    if !fallback.value {
+        return None;
+    }
+    let cond = &branch.cond;
+    Some(parse_quote! { (!(#cond) || (#then)) })
+}
+
+/// Returns the field converted by `value as target` when `value` is an
+/// integer field and `target` an integer type holding every value of it, on
+/// every pointer width.
+fn widened_field<'a>(
+    value: &'a Expr,
+    target: &str,
+    fields: &[(Member, Type)],
+) -> Option<&'a Member> {
+    let field = self_field(value)?;
+    let (_, ty) = fields.iter().find(|(name, _)| name == field)?;
+    let source = crate::type_ident(ty)?;
+    sized_integer_bounds(target, 64)?;
+    let widens = source == target
+        || [16, 32, 64].into_iter().all(|width| {
+            let bounds = |ty: &str| sized_integer_bounds(ty, width);
+            matches!(
+                (bounds(&source), bounds(target)),
+                (Some((lo, hi)), Some((target_lo, target_hi))) if target_lo <= lo && hi <= target_hi
+            )
+        });
+    widens.then_some(field)
+}
+
+/// Matches `T::from(self.x)` when it widens an integer field, returning the
+/// field.
+pub(crate) fn widening_from<'a>(
+    call: &'a syn::ExprCall,
+    fields: &[(Member, Type)],
+) -> Option<&'a Member> {
+    let Expr::Path(func) = &*call.func else {
+        return None;
+    };
+    let [target, method] = func.path.segments.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let [value] = call.args.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    if method.ident != "from" || !target.arguments.is_none() {
+        return None;
+    }
+    widened_field(value, &target.ident.to_string(), fields)
+}
+
+impl Lowerer<'_> {
+    /// Expands `self.x.is_power_of_two()` on an unsigned field into its bit trick.
+    fn lower_power_of_two(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        if !call.args.is_empty() {
+            return None;
+        }
+        let field = self_field(&call.receiver)?;
+        let ty = self.field_type(field)?;
+        if !(crate::is_integer_type(ty) && crate::is_unsigned_type(ty)) {
+            return None;
+        }
+        Some(parse_quote! { (self.#field != 0 && (self.#field & (self.#field - 1)) == 0) })
+    }
+}
+
+impl Lowerer<'_> {
+    /// Turns `self.buf[1]` on a fixed-size integer array into the element
+    /// symbol `self.buf__1`. The index must be a literal within the array.
+    fn lower_index(&mut self, index: &syn::ExprIndex) -> Option<Expr> {
+        let field = self_field(&index.expr)?;
+        let (elem, len) = self.field_type(field).and_then(fixed_array)?;
+        if !crate::is_integer_type(elem) {
+            return None;
+        }
+        let Some(position) = index_value(&index.index) else {
+            self.push_error(syn::Error::new_spanned(
+                &index.index,
+                "array indices in invariants must be integer literals",
+            ));
+            return None;
+        };
+        match usize::try_from(position) {
+            Ok(position) if position < len => Some(symbol_place(&element_symbol(field, position))),
+            _ => {
+                self.push_error(syn::Error::new_spanned(
+                    index,
+                    format!(
+                        "index {} is out of bounds for `{}`, which has {} elements",
+                        position,
+                        crate::member_name(field),
+                        len
+                    ),
+                ));
+                None
+            }
+        }
+    }
+
+    /// Turns `self.name.len()` on a string or `Vec` field into the symbol
+    /// `self.name.len`.
+    fn lower_len(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let field = self_field(&call.receiver)?;
+        if !self
+            .field_type(field)
+            .is_some_and(|ty| crate::is_string_type(ty) || crate::is_vec_type(ty))
+        {
+            return None;
+        }
+        Some(parse_quote! { self.#field.len })
+    }
+
+    /// Turns `self.name.is_empty()` on a `String`, `&str`, `Vec` or `&[T]`
+    /// field into `(self.name.len == 0)`.
+    fn lower_is_empty(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let field = self_field(&call.receiver)?;
+        if !self
+            .field_type(field)
+            .is_some_and(|ty| crate::is_string_type(ty) || crate::is_vec_type(ty))
+        {
+            return None;
+        }
+        Some(parse_quote! { (self.#field.len == 0) })
+    }
+}
+
+impl Lowerer<'_> {
+    /// Turns the `Option` queries on an optional integer field into boolean
+    /// guards over `self.opt.is_some`, substituting the inner value for the
+    /// closure's parameter.
+    fn lower_option(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let field = self_field(&call.receiver)?;
+        let inner = self.field_type(field).and_then(crate::option_inner)?;
+        // The prover's parser only takes bare booleans inside `&&`/`||`
+        let is_some: Expr = parse_quote! { self.#field.is_some == true };
+        let is_none: Expr = parse_quote! { self.#field.is_some == false };
+        // Only integers are visible inside; presence is known for any option
+        match (call.method.to_string().as_str(), call.args.len()) {
+            ("is_some", 0) => return Some(parse_quote! { (#is_some) }),
+            ("is_none", 0) => return Some(parse_quote! { (#is_none) }),
+            _ if !crate::is_integer_type(inner) => return None,
+            _ => {}
+        }
+
+        // `present` selects `is_some && body` (as opposed to `is_none || body`)
+        let (present, closure) = match (call.method.to_string().as_str(), call.args.len()) {
+            ("is_some_and", 1) => (true, &call.args[0]),
+            ("is_none_or", 1) => (false, &call.args[0]),
+            // The default is what an absent value yields
+            ("map_or", 2) => (!bool_literal(&call.args[0])?, &call.args[1]),
+            _ => return None,
+        };
+
+        let Expr::Closure(closure) = closure else {
+            return None;
+        };
+        let [syn::Pat::Ident(param)] = closure.inputs.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        let mut body = (*closure.body).clone();
+        Substitute {
+            param: &param.ident,
+            value: parse_quote! { self.#field },
+        }
+        .visit_expr_mut(&mut body);
+        // The body was visited with the parameter in place of the field
+        self.visit_expr_mut(&mut body);
+
+        Some(if present {
+            parse_quote! { (#is_some && (#body)) }
+        } else {
+            parse_quote! { (#is_none || (#body)) }
+        })
+    }
+}
+
+/// Rewrites an integer literal the prover's tokenizer can't read, such as the
+/// mask `0b1111_0000` or `0x3u8`, as plain decimal. Decimal and hex literals
+/// without suffixes or separators are left as written.
+fn decimal_literal(int: &syn::LitInt) -> Option<Expr> {
+    let text = int.to_string();
+    let digits = text.strip_prefix("0x").unwrap_or(&text);
+    let hex = digits.len() != text.len();
+    let plain = !digits.is_empty()
+        && digits.chars().all(|c| {
+            if hex {
+                c.is_ascii_hexdigit()
+            } else {
+                c.is_ascii_digit()
+            }
+        });
+    if plain {
+        return None;
+    }
+    let value = proc_macro2::Literal::u128_unsuffixed(int.base10_parse().ok()?);
+    Some(parse_quote! { #value })
+}
+
+/// The most iterations a quantified invariant is unrolled into.
+const MAX_UNROLL: i128 = 1024;
+
+impl Lowerer<'_> {
+    /// Unrolls `(lo..hi).all(|i| body)` over a literal range, the desugaring of
+    /// `forall i in lo..hi => body`, into a conjunction of `body` with `i`
+    /// replaced by each value in turn.
+    fn lower_all(&mut self, call: &syn::ExprMethodCall) -> Option<Expr> {
+        let (lo, hi) = self.unroll_range(&call.receiver)?;
+        let [Expr::Closure(closure)] = call.args.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        let [syn::Pat::Ident(param)] = closure.inputs.iter().collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        if hi < lo || hi - lo >= MAX_UNROLL {
+            self.push_error(syn::Error::new_spanned(
+                &call.receiver,
+                format!(
+                    "quantified ranges must be non-empty and span at most {} values",
+                    MAX_UNROLL
+                ),
+            ));
+            return None;
+        }
+
+        let terms: Vec<Expr> = (lo..=hi)
+            .map(|value| {
+                let mut body = (*closure.body).clone();
+                Substitute {
+                    param: &param.ident,
+                    value: int_lit(value),
+                }
+                .visit_expr_mut(&mut body);
+                self.visit_expr_mut(&mut body);
+                parse_quote! { (#body) }
+            })
+            .collect();
+        Some(parse_quote! { (#(#terms)&&*) })
+    }
+}
+
+impl Lowerer<'_> {
+    /// Returns the first and last values of a range whose bounds are known at
+    /// expansion time, see `bound_value`.
+    fn unroll_range(&self, expr: &Expr) -> Option<(i128, i128)> {
+        match expr {
+            Expr::Paren(paren) => self.unroll_range(&paren.expr),
+            Expr::Range(range) => {
+                let lo = self.bound_value(range.start.as_deref()?)?;
+                let end = self.bound_value(range.end.as_deref()?)?;
+                let hi = match range.limits {
+                    syn::RangeLimits::Closed(_) => end,
+                    syn::RangeLimits::HalfOpen(_) => end.checked_sub(1)?,
+                };
+                Some((lo, hi))
+            }
+            _ => None,
+        }
+    }
+
+    /// Evaluates a range bound built from integer literals, the lengths of
+    /// fixed-size array fields (`self.buf.len()`) and `+`, `-`, `*`.
+    fn bound_value(&self, expr: &Expr) -> Option<i128> {
+        match expr {
+            Expr::Paren(paren) => self.bound_value(&paren.expr),
+            Expr::Binary(binary) => {
+                let left = self.bound_value(&binary.left)?;
+                let right = self.bound_value(&binary.right)?;
+                match binary.op {
+                    syn::BinOp::Add(_) => left.checked_add(right),
+                    syn::BinOp::Sub(_) => left.checked_sub(right),
+                    syn::BinOp::Mul(_) => left.checked_mul(right),
+                    _ => None,
+                }
+            }
+            Expr::MethodCall(call) if call.method == "len" && call.args.is_empty() => {
+                let ty = self.field_type(self_field(&call.receiver)?)?;
+                fixed_array(ty).map(|(_, len)| len as i128)
+            }
+            _ => int_literal(expr),
+        }
+    }
+}
+
+/// Evaluates an array index built from integer literals and `+`, `-`, `*`,
+/// as left behind once a `forall` variable has been substituted.
+fn index_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Paren(paren) => index_value(&paren.expr),
+        Expr::Binary(binary) => {
+            let left = index_value(&binary.left)?;
+            let right = index_value(&binary.right)?;
+            match binary.op {
+                syn::BinOp::Add(_) => left.checked_add(right),
+                syn::BinOp::Sub(_) => left.checked_sub(right),
+                syn::BinOp::Mul(_) => left.checked_mul(right),
+                _ => None,
+            }
+        }
+        _ => int_literal(expr),
+    }
+}
+
+/// Returns the value of a `true`/`false` literal.
+fn bool_literal(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(value),
+            ..
+        }) => Some(value.value),
+        _ => None,
+    }
+}
+
+/// Replaces a closure parameter with the value it stands for.
+struct Substitute<'a> {
+    param: &'a Ident,
+    value: Expr,
+}
+
+impl VisitMut for Substitute<'_> {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if let Expr::Path(path) = node
+            && path.path.is_ident(self.param)
+        {
+            *node = self.value.clone();
+            return;
+        }
+        visit_mut::visit_expr_mut(self, node);
+    }
+}
+
+impl VisitMut for Lowerer<'_> {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        // Quantified bodies are lowered once the variable has been substituted
+        if let Expr::MethodCall(call) = node
+            && call.method == "all"
+            && let Some(unrolled) = self.lower_all(call)
+        {
+            *node = unrolled;
+            self.changed = true;
+            return;
+        }
+
+        visit_mut::visit_expr_mut(self, node);
+
+        let replacement = match node {
+            Expr::MethodCall(call) if call.method == "sum" && call.args.is_empty() => {
+                self.lower_sum(call)
+            }
+            Expr::MethodCall(call) if call.method == "is_ok" => self.lower_try_from(call),
+            Expr::MethodCall(call) if call.method == "len" && call.args.is_empty() => {
+                self.lower_len(call)
+            }
+            Expr::MethodCall(call) if call.method == "is_empty" && call.args.is_empty() => {
+                self.lower_is_empty(call)
+            }
+            Expr::MethodCall(call)
+                if matches!(
+                    call.method.to_string().as_str(),
+                    "is_some" | "is_none" | "is_some_and" | "is_none_or" | "map_or"
+                ) =>
+            {
+                self.lower_option(call)
+            }
+            Expr::MethodCall(call) if call.method == "contains" => contains(call),
+            Expr::MethodCall(call) if call.method == "is_power_of_two" => {
+                self.lower_power_of_two(call)
+            }
+            Expr::MethodCall(call)
+                if call.method == "rem_euclid" || call.method == "div_euclid" =>
+            {
+                self.lower_euclid(call)
+            }
+            Expr::MethodCall(call) => self.lower_ascii_class(call),
+            Expr::Call(call) => self.lower_is_sorted(call).or_else(|| {
+                widening_from(call, self.fields).map(|field| parse_quote! { self.#field })
+            }),
+            Expr::Cast(cast) => crate::type_ident(&cast.ty)
+                .and_then(|target| widened_field(&cast.expr, &target, self.fields))
+                .map(|field| parse_quote! { self.#field }),
+            Expr::If(branch) => implication(branch),
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(int),
+                ..
+            }) => decimal_literal(int),
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Char(c),
+                ..
+            }) => {
+                let codepoint = proc_macro2::Literal::u32_unsuffixed(u32::from(c.value()));
+                Some(parse_quote! { #codepoint })
+            }
+            Expr::Index(index) => self.lower_index(index),
+            Expr::Binary(binary) => variant_comparison(binary, self.fields).map(|(_, path)| {
+                let mut binary = binary.clone();
+                let symbol = symbol_place(&variant_symbol(path));
+                match &*binary.right {
+                    Expr::Path(_) => *binary.right = symbol,
+                    _ => *binary.left = symbol,
+                }
+                Expr::Binary(binary)
+            }),
+            Expr::Field(_) => projection(node).map(|(symbol, _)| symbol_place(&symbol)),
+            Expr::Path(_) => associated_const(node)
+                .map(|name| symbol_place(&const_symbol(name)))
+                .or_else(|| {
+                    const_param(node, self.const_params)
+                        .map(|param| symbol_place(&param.to_string()))
+                }),
+            _ => None,
+        };
+
+        if let Some(replacement) = replacement {
+            *node = replacement;
+            self.changed = true;
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Pseudo-functions recognized inside invariant expressions.
+//!
+//! These look like ordinary function calls in `#[invariant(...)]` but are
+//! rewritten by the macro before code generation:
+//!
+//! - `matches_regex(field, "pattern")` - runtime-only string format check
+//!   (requires the `regex` feature)
+//! - `is_sorted(field)` / `is_sorted_unique(field)` - non-decreasing / strictly
+//!   increasing elements. Runtime-only unless the field is a fixed-size integer
+//!   array, which the `lowering` module unrolls for the solver
+//! - `matches!(field, Pattern | ..)` - runtime-only, since the solver has no
+//!   notion of patterns; the macro call is kept as written
+//!
+//! Calls to any other function (other than the conversions the `lowering`
+//! module understands) are uninterpreted: the solver can't see their
+//! bodies, so such invariants are checked at runtime only. The same goes for
+//! method calls the prover's parser can't read once lowered.
+
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Expr, ExprCall, Lit, Member, Type, parse_quote};
+
+/// Names of the recognized pseudo-functions.
+const PSEUDO_FUNCTIONS: &[&str] = &["matches_regex", "is_sorted", "is_sorted_unique"];
+
+/// Rewrites pseudo-function calls in an invariant expression in place.
+///
+/// Returns `true` if the expression contains a call that cannot be expressed
+/// to the SMT solver, making the whole invariant runtime-only.
+pub(crate) fn rewrite(expr: &mut Expr, fields: &[(Member, Type)]) -> syn::Result<bool> {
+    let mut rewriter = PseudoCallRewriter {
+        fields,
+        runtime_only: false,
+        error: None,
+    };
+    rewriter.visit_expr_mut(expr);
+    match rewriter.error {
+        Some(e) => Err(e),
+        None => Ok(rewriter.runtime_only),
+    }
+}
+
+struct PseudoCallRewriter<'a> {
+    fields: &'a [(Member, Type)],
+    runtime_only: bool,
+    error: Option<syn::Error>,
+}
+
+impl PseudoCallRewriter<'_> {
+    fn push_error(&mut self, err: syn::Error) {
+        match &mut self.error {
+            Some(existing) => existing.combine(err),
+            None => self.error = Some(err),
+        }
+    }
+}
+
+impl VisitMut for PseudoCallRewriter<'_> {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        // Rewrite children first so nested pseudo-calls are handled too
+        visit_mut::visit_expr_mut(self, node);
+
+        if let Expr::Macro(mac) = node
+            && mac.mac.path.is_ident("matches")
+        {
+            self.runtime_only = true;
+            return;
+        }
+        let Expr::Call(call) = node else {
+            return;
+        };
+        let (rewritten, runtime_only) = match call_name(call).as_deref() {
+            Some("matches_regex") => (rewrite_matches_regex(call), true),
+            Some(name @ ("is_sorted" | "is_sorted_unique")) => {
+                let provable = call.args.len() == 1
+                    && crate::lowering::integer_array_field(&call.args[0], self.fields).is_some();
+                (
+                    rewrite_is_sorted(call, name == "is_sorted_unique"),
+                    !provable,
+                )
+            }
+            _ => return,
+        };
+        match rewritten {
+            Ok(replacement) => {
+                *node = replacement;
+                self.runtime_only |= runtime_only;
+            }
+            Err(e) => self.push_error(e),
+        }
+    }
+}
+
+/// Returns the first call to a function that isn't a pseudo-function or a
+/// widening conversion of one of `fields`.
+pub(crate) fn uninterpreted_call<'ast>(
+    expr: &'ast Expr,
+    fields: &[(Member, Type)],
+) -> Option<&'ast ExprCall> {
+    struct Finder<'ast, 'f>(Option<&'ast ExprCall>, &'f [(Member, Type)]);
+
+    impl<'ast> Visit<'ast> for Finder<'ast, '_> {
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            // `T::try_from(x).is_ok()` is lowered to a range check
+            match crate::lowering::try_from_check(call) {
+                Some((_, value)) => self.visit_expr(value),
+                None => visit::visit_expr_method_call(self, call),
+            }
+        }
+
+        fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+            if self.0.is_some() {
+                return;
+            }
+            if call_name(call).is_some_and(|name| PSEUDO_FUNCTIONS.contains(&name.as_str())) {
+                visit::visit_expr_call(self, call);
+            } else if crate::lowering::widening_from(call, self.1).is_some() {
+                // `u64::from(self.x)` is lowered to `self.x`
+            } else {
+                self.0 = Some(call);
+            }
+        }
+    }
+
+    let mut finder = Finder(None, fields);
+    finder.visit_expr(expr);
+    finder.0
+}
+
+/// Returns the first method call in an expression.
+pub(crate) fn method_call(expr: &Expr) -> Option<&syn::ExprMethodCall> {
+    struct Finder<'ast>(Option<&'ast syn::ExprMethodCall>);
+
+    impl<'ast> Visit<'ast> for Finder<'ast> {
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            if self.0.is_none() {
+                self.0 = Some(call);
+            }
+        }
+    }
+
+    let mut finder = Finder(None);
+    finder.visit_expr(expr);
+    finder.0
+}
+
+/// Returns the name of a call to a bare function identifier, if any.
+fn call_name(call: &ExprCall) -> Option<String> {
+    if let Expr::Path(path) = &*call.func {
+        return path.path.get_ident().map(|ident| ident.to_string());
+    }
+    None
+}
+
+/// Lowers `matches_regex(field, "pattern")` to a cached `regex::Regex` match.
+fn rewrite_matches_regex(call: &ExprCall) -> syn::Result<Expr> {
+    if call.args.len() != 2 {
+        return Err(syn::Error::new_spanned(
+            call,
+            "matches_regex expects two arguments: matches_regex(field, \"pattern\")",
+        ));
+    }
+
+    let haystack = &call.args[0];
+    let pattern = match &call.args[1] {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => lit_str.clone(),
+        other => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "matches_regex pattern must be a string literal",
+            ));
+        }
+    };
+
+    validate_pattern(&pattern)?;
+
+    Ok(parse_quote! {
+        {
+            static PATTERN: ::std::sync::OnceLock<::regex::Regex> = ::std::sync::OnceLock::new();
+            PATTERN
+                .get_or_init(|| {
+                    ::regex::Regex::new(#pattern).expect("pattern validated at compile time")
+                })
+                .is_match(::core::convert::AsRef::<str>::as_ref(&#haystack))
+        }
+    })
+}
+
+/// Lowers `is_sorted(x)` / `is_sorted_unique(x)` to a pairwise slice check.
+fn rewrite_is_sorted(call: &ExprCall, strict: bool) -> syn::Result<Expr> {
+    if call.args.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            call,
+            "is_sorted expects one argument: is_sorted(self.field)",
+        ));
+    }
+
+    let values = &call.args[0];
+    Ok(if strict {
+        parse_quote! { (#values)[..].windows(2).all(|pair| pair[0] < pair[1]) }
+    } else {
+        parse_quote! { (#values)[..].windows(2).all(|pair| pair[0] <= pair[1]) }
+    })
+}
+
+#[cfg(feature = "regex")]
+fn validate_pattern(pattern: &syn::LitStr) -> syn::Result<()> {
+    regex::Regex::new(&pattern.value())
+        .map(|_| ())
+        .map_err(|e| syn::Error::new_spanned(pattern, format!("Invalid regex pattern: {}", e)))
+}
+
+#[cfg(not(feature = "regex"))]
+fn validate_pattern(pattern: &syn::LitStr) -> syn::Result<()> {
+    Err(syn::Error::new_spanned(
+        pattern,
+        "matches_regex requires the `regex` feature of praborrow-defense",
+    ))
+}
@@ -0,0 +1,104 @@
+//! Expansion of `verify_relation!`, which checks a condition relating two
+//! values.
+//!
+//! The condition reads the first value's fields as `a.<field>` and the
+//! second's as `b.<field>`. It is evaluated at runtime with `a` and `b` bound
+//! to the values, then handed to the solver as `self.a.<field>` and
+//! `self.b.<field>`, read from the two values' field providers joined under
+//! those prefixes.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::visit_mut::VisitMut;
+use syn::{Expr, Token};
+
+use crate::conditions::SelfOperands;
+use crate::{block_on, render};
+
+/// Input for `verify_relation!`: the two values and the condition.
+pub(crate) struct RelationArgs {
+    a: Expr,
+    b: Expr,
+    condition: Expr,
+}
+
+impl Parse for RelationArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let a = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let b = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let condition = input.parse()?;
+        let _ = input.parse::<Option<Token![,]>>()?;
+        Ok(Self { a, b, condition })
+    }
+}
+
+/// Generates the block checking the relation, which evaluates to
+/// `Result<VerificationToken, ProofError>`.
+pub(crate) fn expand(args: RelationArgs) -> syn::Result<TokenStream> {
+    let RelationArgs { a, b, condition } = args;
+    let (expr_str, runtime_expr) = match &condition {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => match syn::parse_str::<Expr>(&lit_str.value()) {
+            Ok(expr) => (lit_str.value(), expr),
+            Err(err) => {
+                return Err(syn::Error::new_spanned(
+                    lit_str,
+                    format!("Syntax error in relation string: {}", err),
+                ));
+            }
+        },
+        _ => (render(&condition), condition.clone()),
+    };
+
+    // `a.output` reaches the solver as `self.a.output`
+    let mut operands = runtime_expr.clone();
+    SelfOperands.visit_expr_mut(&mut operands);
+    let smt = render(&operands);
+    if let Err(e) = praborrow_prover::parser::ExpressionParser::parse(&smt) {
+        return Err(syn::Error::new_spanned(
+            &condition,
+            format!("Invalid relation syntax: {}", e),
+        ));
+    }
+
+    let verification = block_on(quote! { ctx.verify_invariants(&provider, &[#smt]) });
+    Ok(quote! {
+        {
+            use core::future::Future;
+            use praborrow_prover::ProofError;
+            use praborrow_prover::backend::{FieldValue, FieldValueProvider};
+
+            // Serves `a.<field>` and `b.<field>` from the two values' providers
+            struct RelationProvider<'p>(&'p dyn FieldValueProvider, &'p dyn FieldValueProvider);
+
+            impl FieldValueProvider for RelationProvider<'_> {
+                fn get_field_value(&self, name: &str) -> Result<FieldValue, ProofError> {
+                    match name.split_once('.') {
+                        Some(("a", field)) => self.0.get_field_value(field),
+                        Some(("b", field)) => self.1.get_field_value(field),
+                        _ => Err(ProofError::ParseError(format!("Unknown field: {}", name))),
+                    }
+                }
+            }
+
+            let a = &#a;
+            let b = &#b;
+            if #runtime_expr {
+                let a_provider = praborrow_prover::ProveInvariant::get_field_provider(a);
+                let b_provider = praborrow_prover::ProveInvariant::get_field_provider(b);
+                let provider = RelationProvider(&*a_provider, &*b_provider);
+                match praborrow_prover::SmtContext::new() {
+                    Ok(ctx) => #verification,
+                    Err(e) => Err(e),
+                }
+            } else {
+                Err(ProofError::InvariantViolated(#expr_str.to_string()))
+            }
+        }
+    })
+}
@@ -0,0 +1,119 @@
+//! Narrowing of the prover's parse errors to the tokens they were found at.
+//!
+//! `ExpressionParser::parse_spanned` reports a byte range in the string it was
+//! handed. For a bare invariant that string is the rendered token stream, so
+//! the range is matched back to the tokens that rendered to it. For a string
+//! literal the range is a subspan of the literal, which only nightly compilers
+//! can point at. Anything else, such as a lowered invariant whose string no
+//! longer matches the source, keeps the whole invariant as the span.
+
+use std::ops::Range;
+
+use proc_macro2::{Delimiter, Punct, Spacing, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Expr, Lit};
+
+/// Builds the error for an invariant the prover's parser rejected.
+///
+/// `written` is the expression the solver string `smt` was rendered from, if
+/// it was rendered rather than taken from a string literal.
+pub(crate) fn parse_error(
+    expr: &Expr,
+    written: Option<&Expr>,
+    smt: &str,
+    message: String,
+) -> syn::Error {
+    let range = praborrow_prover::parser::ExpressionParser::parse_spanned(smt)
+        .err()
+        .and_then(|e| e.span);
+    let narrowed = range.and_then(|range| match (expr, written) {
+        (
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit), ..
+            }),
+            _,
+        ) if lit.value() == smt => {
+            literal_subspan(lit, range).map(|span| syn::Error::new(span, &message))
+        }
+        (_, Some(written)) if crate::render(written) == smt => {
+            token_range(written.to_token_stream(), smt, range)
+                .map(|tokens| syn::Error::new_spanned(tokens, &message))
+        }
+        _ => None,
+    });
+    narrowed.unwrap_or_else(|| syn::Error::new_spanned(expr, message))
+}
+
+/// Returns the span of `range` within a string literal's value, if the value
+/// is spelt out verbatim between the quotes and the compiler supports it.
+fn literal_subspan(lit: &syn::LitStr, range: Range<usize>) -> Option<Span> {
+    let token = lit.token();
+    let source = token.to_string();
+    let value = lit.value();
+    if source.get(1..source.len().checked_sub(1)?) != Some(value.as_str()) {
+        return None;
+    }
+    token.subspan(range.start + 1..range.end + 1)
+}
+
+/// Finds the tokens of `tokens` that rendered to `range` of `rendered`,
+/// returned as a stream whose first and last spans bound them. An empty range
+/// at the end selects the last token.
+fn token_range(tokens: TokenStream, rendered: &str, range: Range<usize>) -> Option<TokenStream> {
+    let mut leaves = Vec::new();
+    flatten(tokens, &mut leaves);
+
+    let mut cursor = 0;
+    let mut selected: Vec<Span> = Vec::new();
+    let mut last = None;
+    for (text, span) in leaves {
+        let start = cursor + rendered[cursor..].find(&text)?;
+        let end = start + text.len();
+        cursor = end;
+        if start < range.end && range.start < end {
+            selected.push(span);
+        }
+        last = Some(span);
+    }
+    if selected.is_empty() && range.start >= rendered.len() {
+        selected.extend(last);
+    }
+
+    let first = *selected.first()?;
+    let last = *selected.last()?;
+    Some(
+        [first, last]
+            .into_iter()
+            .map(|span| {
+                let mut marker = Punct::new('.', Spacing::Alone);
+                marker.set_span(span);
+                TokenTree::Punct(marker)
+            })
+            .collect(),
+    )
+}
+
+/// Collects each token's rendered text and span, with a group's delimiters as
+/// tokens of their own.
+fn flatten(tokens: TokenStream, leaves: &mut Vec<(String, Span)>) {
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                if !open.is_empty() {
+                    leaves.push((open.to_string(), group.span_open()));
+                }
+                flatten(group.stream(), leaves);
+                if !close.is_empty() {
+                    leaves.push((close.to_string(), group.span_close()));
+                }
+            }
+            token => leaves.push((token.to_string(), token.span())),
+        }
+    }
+}
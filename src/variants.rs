@@ -0,0 +1,528 @@
+//! Expansion of `#[derive(Constitution)]` on enums.
+//!
+//! Invariants are attached to variant fields and written against `self`, as on
+//! a struct: `Circle { #[invariant(self.r > 0.0)] r: f64 }`. Each generated
+//! method matches on the active variant, binds its fields by reference and
+//! rewrites `self.r` in the variant's checks to the binding, so only the
+//! active variant's invariants are evaluated.
+//!
+//! The solver sees the fields of the active variant under their own names.
+//! `invariant_expressions()` lists every variant's invariants in declaration
+//! order, and verification only hands over the active variant's slice.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::visit_mut::{self, VisitMut};
+use syn::{DataEnum, DeriveInput, Expr, Ident, Member, Meta, Type, parse_quote};
+
+use crate::attrs::{FieldOptions, InvariantArgs, StructOptions};
+use crate::{Invariant, collecting_check, parse_invariant, runtime_check};
+
+/// A variant together with its fields and invariants.
+struct Variant<'a> {
+    ident: &'a Ident,
+    fields: Vec<(Member, Type)>,
+    invariants: Vec<Invariant>,
+    /// Fields marked `#[constitution(skip_hash)]`.
+    unhashed: Vec<Member>,
+}
+
+/// Generates the enum's `CheckProtocol` and `ProveInvariant` impls along with
+/// its inherent methods.
+pub(crate) fn expand(
+    input: &DeriveInput,
+    data: &DataEnum,
+    options: &StructOptions,
+) -> syn::Result<TokenStream> {
+    if let Some(option) = options.struct_only_option() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!(
+                "`{}` is not supported in `#[constitution(...)]` on an enum",
+                option
+            ),
+        ));
+    }
+    if let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("invariant"))
+    {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "invariants on an enum must be attached to a variant's fields",
+        ));
+    }
+
+    let const_params = crate::const_params(&input.generics);
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        let fields: Vec<(Member, Type)> = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let member = match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(index.into()),
+                };
+                (member, field.ty.clone())
+            })
+            .collect();
+
+        let mut invariants = Vec::new();
+        let mut unhashed = Vec::new();
+        for (field, (member, _)) in variant.fields.iter().zip(&fields) {
+            let field_options = FieldOptions::from_attrs(&field.attrs)?;
+            if let Some(group) = field_options.group {
+                return Err(syn::Error::new_spanned(
+                    group,
+                    "check groups are not supported on enum variants",
+                ));
+            }
+            if field_options.nested {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "nested fields are not supported on enum variants",
+                ));
+            }
+            if let Some(repr) = field_options.repr {
+                return Err(syn::Error::new_spanned(
+                    repr,
+                    "enum discriminant fields are not supported on enum variants",
+                ));
+            }
+            if let Some(bits) = field_options.bits {
+                return Err(syn::Error::new_spanned(
+                    bits,
+                    "bit widths are not supported on enum variants",
+                ));
+            }
+            if field_options.skip_hash {
+                unhashed.push(member.clone());
+            }
+            for attr in &field.attrs {
+                let Meta::List(meta_list) = &attr.meta else {
+                    continue;
+                };
+                if !meta_list.path.is_ident("invariant") {
+                    continue;
+                }
+                let args = meta_list.parse_args::<InvariantArgs>()?;
+                if let Some(group) = args.group {
+                    return Err(syn::Error::new_spanned(
+                        group,
+                        "check groups are not supported on enum variants",
+                    ));
+                }
+                let mut invariant =
+                    parse_invariant(&args.expr, &fields, &const_params, options.strict_parse)?;
+                invariant.name = args.name.map(|name| name.value());
+                invariants.push(invariant);
+            }
+        }
+
+        variants.push(Variant {
+            ident: &variant.ident,
+            fields,
+            invariants,
+            unhashed,
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut runtime_arms = Vec::new();
+    let mut collecting_arms = Vec::new();
+    let mut condition_arms = Vec::new();
+    for variant in &variants {
+        let mut used = Vec::new();
+        let conditions = variant
+            .invariants
+            .iter()
+            .map(|inv| {
+                let condition = &inv.expr;
+                rebind(quote! { #condition }, &mut used).map(|condition| quote! { #condition })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let condition = crate::conjunction(&conditions);
+        let runtime_checks = variant
+            .invariants
+            .iter()
+            .map(|inv| rebind(runtime_check(inv), &mut used))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let collecting_checks = variant
+            .invariants
+            .iter()
+            .map(|inv| rebind(collecting_check(inv), &mut used))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let pattern = variant_pattern(name, variant, |field| used.contains(field));
+        runtime_arms.push(quote! {
+            #pattern => {
+                #(#runtime_checks)*
+                Ok(())
+            }
+        });
+        condition_arms.push(quote! { #pattern => #condition, });
+        collecting_arms.push(quote! {
+            #pattern => {
+                let mut violations = Vec::new();
+                #(#collecting_checks)*
+                if violations.is_empty() {
+                    Ok(())
+                } else {
+                    Err(violations)
+                }
+            }
+        });
+    }
+
+    // Every variant's solver invariants, with the slice belonging to each variant
+    let mut invariant_literals = Vec::new();
+    let mut slice_arms = Vec::new();
+    let mut runtime_only_arms = Vec::new();
+    for variant in &variants {
+        let start = invariant_literals.len();
+        invariant_literals.extend(variant.invariants.iter().filter_map(|inv| inv.smt.clone()));
+        let end = invariant_literals.len();
+        let pattern = variant_pattern(name, variant, |_| false);
+        slice_arms.push(quote! { #pattern => #start..#end, });
+        let runtime_only = variant
+            .invariants
+            .iter()
+            .filter(|inv| inv.smt.is_none())
+            .map(|inv| &inv.expr_str);
+        runtime_only_arms.push(quote! { #pattern => &[#(#runtime_only),*], });
+    }
+
+    // The variant index is hashed first, so equal fields in different variants differ
+    let hash_arms = variants.iter().enumerate().map(|(index, variant)| {
+        let index = index as u32;
+        let updates: Vec<_> = variant
+            .fields
+            .iter()
+            .filter(|(field, _)| !variant.unhashed.contains(field))
+            .map(|(field, ty)| (field, crate::hash_update(ty, place(field), false)))
+            .collect();
+        let pattern = variant_pattern(name, variant, |field| {
+            updates.iter().any(|(hashed, _)| *hashed == field)
+        });
+        let updates = updates.iter().map(|(_, update)| update);
+        quote! {
+            #pattern => {
+                hasher.update(&#index.to_le_bytes());
+                #(#updates)*
+                hasher.finalize().to_vec()
+            }
+        }
+    });
+
+    // Associated constants are visible whichever variant is active
+    let const_arms = crate::const_arms(
+        input,
+        variants
+            .iter()
+            .flat_map(|variant| &variant.invariants)
+            .map(|inv| &inv.expr),
+    );
+
+    // Variants compared with a field are exposed by their index
+    let compared: Vec<_> = variants
+        .iter()
+        .map(|variant| {
+            crate::lowering::compared_variants(
+                variant.invariants.iter().map(|inv| &inv.expr),
+                &variant.fields,
+            )
+        })
+        .collect();
+    let symbols = crate::variant_symbols(&compared.concat());
+    let const_arms: Vec<_> = const_arms
+        .into_iter()
+        .chain(crate::const_param_arms(
+            &const_params,
+            variants
+                .iter()
+                .flat_map(|variant| &variant.invariants)
+                .map(|inv| &inv.expr),
+        ))
+        .chain(crate::pointer_bound_arms(
+            variants
+                .iter()
+                .flat_map(|variant| &variant.invariants)
+                .filter_map(|inv| inv.smt.as_deref()),
+            options.bitvector(),
+        ))
+        .chain(crate::variant_symbol_arms(&symbols))
+        .collect();
+
+    let provider_arms = variants.iter().zip(&compared).map(|(variant, compared)| {
+        let visible: Vec<_> = variant
+            .fields
+            .iter()
+            .map(|field| {
+                let mut arms = crate::provider_arms(
+                    core::slice::from_ref(field),
+                    place,
+                    options.bitvector(),
+                    &[],
+                );
+                let (member, _) = field;
+                arms.extend(crate::variant_field_arm(
+                    member,
+                    compared,
+                    &symbols,
+                    place(member),
+                ));
+                arms
+            })
+            .collect();
+        let pattern = variant_pattern(name, variant, |field| {
+            variant
+                .fields
+                .iter()
+                .zip(&visible)
+                .any(|((member, _), arms)| member == field && !arms.is_empty())
+        });
+        let arms = visible.iter().flatten().chain(&const_arms);
+        quote! {
+            #pattern => match name {
+                #(#arms)*
+                _ => Err(ProofError::ParseError(format!("Unknown field: {}", name))),
+            },
+        }
+    });
+
+    let warnings = variants
+        .iter()
+        .flat_map(|variant| &variant.invariants)
+        .filter_map(|inv| inv.warning.as_ref());
+
+    let blocking_verify = options
+        .blocking
+        .then(|| crate::blocking_verify_fn(options.backend.as_ref()));
+    let batch_verify = options.batch.then(crate::batch_verify_fn);
+    let cached_verify = crate::cached_verify_fn(options.backend.as_ref());
+    let verify_report = options.report.then(|| {
+        crate::verify_report_fn(
+            quote! {
+                &Self::INVARIANTS[match *self {
+                    #(#slice_arms)*
+                }]
+            },
+            quote! {
+                match *self {
+                    #(#runtime_only_arms)*
+                }
+            },
+            None,
+            false,
+        )
+    });
+    let parallel_verify = options.parallel.then(|| {
+        crate::parallel_verify_fn(
+            quote! {
+                &Self::INVARIANTS[match *self {
+                    #(#slice_arms)*
+                }]
+            },
+            &[],
+            None,
+            false,
+            options.backend.as_ref(),
+        )
+    });
+    let guard = options.guard.then(crate::guard_fns);
+    let is_valid = crate::is_valid_fn();
+    let equivalence = {
+        let fields: Vec<_> = variants.iter().flat_map(|v| v.fields.clone()).collect();
+        crate::equivalence_consts(&fields, variants.iter().flat_map(|v| &v.invariants))
+    };
+    let invariant_fn = options.invariant_fn.then(|| {
+        crate::invariant_fn(quote! {
+            match *self {
+                #(#condition_arms)*
+            }
+        })
+    });
+    let release_skip = crate::release_skip();
+    let prove_generics = crate::prove_generics(input);
+    let prove_where_clause = &prove_generics.where_clause;
+    let provider_generics = crate::provider_generics(input);
+    let (provider_impl_generics, provider_ty_generics, provider_where_clause) =
+        provider_generics.split_for_impl();
+
+    let smt_script = options.smt_script.then(|| {
+        quote! {
+            /// Renders the SMT-LIB2 script the solver is given for this value: the
+            /// active variant's fields pinned to their current values and one
+            /// assertion per invariant of that variant.
+            pub fn smt_script(&self) -> Result<String, praborrow_prover::ProofError> {
+                let provider = praborrow_prover::ProveInvariant::get_field_provider(self);
+                let active = match *self {
+                    #(#slice_arms)*
+                };
+                praborrow_prover::smtlib::script(
+                    &*provider,
+                    &Self::INVARIANTS[active],
+                )
+            }
+        }
+    });
+    let enforce_all = options.enforce_all.then(|| {
+        quote! {
+            /// Checks the active variant's invariants like `enforce_law()`, but
+            /// reports all violations in declaration order instead of stopping at
+            /// the first.
+            pub fn enforce_law_all(&self) -> Result<(), Vec<praborrow_core::ConstitutionError>> {
+                match *self {
+                    #(#collecting_arms)*
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        // Runtime check implementation - checks the active variant's invariants
+        #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+        impl #impl_generics CheckProtocol for #name #ty_generics #where_clause {
+            fn enforce_law(&self) -> Result<(), praborrow_core::ConstitutionError> {
+                #release_skip
+                match *self {
+                    #(#runtime_arms)*
+                }
+            }
+        }
+
+        #[allow(clippy::float_cmp, clippy::len_zero, clippy::unnecessary_map_or)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Every variant's solver invariants, in declaration order.
+            pub const INVARIANTS: &'static [&'static str] = &[#(#invariant_literals),*];
+
+            #enforce_all
+
+            #is_valid
+
+            #invariant_fn
+
+            #guard
+
+            #equivalence
+        }
+
+        impl #impl_generics #name #ty_generics #prove_where_clause {
+            #blocking_verify
+
+            #batch_verify
+
+            #cached_verify
+
+            #verify_report
+
+            #parallel_verify
+
+            #smt_script
+        }
+
+        #(#warnings)*
+
+        // Formal verification implementation
+        impl #impl_generics praborrow_prover::ProveInvariant for #name #ty_generics #prove_where_clause {
+            fn invariant_expressions() -> &'static [&'static str] {
+                Self::INVARIANTS
+            }
+
+            fn compute_data_hash(&self) -> Vec<u8> {
+                use praborrow_prover::sha2::{Sha256, Digest};
+                let mut hasher = Sha256::new();
+                match *self {
+                    #(#hash_arms)*
+                }
+            }
+
+            fn get_field_provider(&self) -> alloc::boxed::Box<dyn praborrow_prover::backend::FieldValueProvider + '_> {
+                 use praborrow_prover::backend::{FieldValueProvider, FieldValue};
+                 use praborrow_prover::ProofError;
+
+                 struct FieldProvider #provider_impl_generics (&'__a #name #ty_generics) #provider_where_clause;
+
+                 impl #provider_impl_generics FieldValueProvider for FieldProvider #provider_ty_generics #provider_where_clause {
+                    fn get_field_value(&self, name: &str) -> Result<FieldValue, ProofError> {
+                        match *self.0 {
+                            #(#provider_arms)*
+                        }
+                    }
+                 }
+
+                 alloc::boxed::Box::new(FieldProvider(self))
+            }
+
+            fn verify_with_context(
+                &self,
+                ctx: &praborrow_prover::SmtContext
+            ) -> impl core::future::Future<Output = Result<praborrow_prover::VerificationToken, praborrow_prover::ProofError>> + Send {
+                async move {
+                    let provider = self.get_field_provider();
+                    let active = match *self {
+                        #(#slice_arms)*
+                    };
+                    ctx.verify_invariants(&*provider, &Self::invariant_expressions()[active]).await
+                }
+            }
+        }
+    })
+}
+
+/// The local a variant field is bound to. Prefixed so it can't shadow the
+/// generated code's own locals, e.g. a field called `name`.
+fn binding(field: &Member) -> Ident {
+    format_ident!("field_{}", crate::member_name(field))
+}
+
+/// Reads a bound field by value, as `self.field` would.
+fn place(field: &Member) -> TokenStream {
+    let binding = binding(field);
+    quote! { (*#binding) }
+}
+
+/// Generates a pattern matching the variant and binding the selected fields by
+/// reference, e.g. `Shape::Circle { r: ref field_r, .. }`.
+fn variant_pattern(name: &Ident, variant: &Variant, bind: impl Fn(&Member) -> bool) -> TokenStream {
+    let ident = variant.ident;
+    let bound = variant
+        .fields
+        .iter()
+        .map(|(field, _)| field)
+        .filter(|field| bind(field));
+    let bindings = bound.clone().map(binding);
+    quote! { #name::#ident { #(#bound: ref #bindings,)* .. } }
+}
+
+/// Rewrites `self.field` in generated check code to the variant's binding,
+/// recording the fields it reads in `used`. Invariants only reference the
+/// variant's own fields, which `parse_invariant` has already checked.
+fn rebind(check: TokenStream, used: &mut Vec<Member>) -> syn::Result<Expr> {
+    let mut check: Expr = syn::parse2(check)?;
+    Rebinder { used }.visit_expr_mut(&mut check);
+    Ok(check)
+}
+
+struct Rebinder<'a> {
+    used: &'a mut Vec<Member>,
+}
+
+impl VisitMut for Rebinder<'_> {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if let Some(field) = crate::bounds::self_field(node) {
+            if !self.used.contains(field) {
+                self.used.push(field.clone());
+            }
+            let binding = binding(field);
+            *node = parse_quote! { (*#binding) };
+            return;
+        }
+        visit_mut::visit_expr_mut(self, node);
+    }
+}
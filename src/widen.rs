@@ -0,0 +1,120 @@
+//! Widening of integer arithmetic in runtime checks (`widen_arithmetic`).
+//!
+//! `self.a + self.b > 0` on `i32` fields overflows, and panics in debug
+//! builds, before the comparison is ever made. With
+//! `#[constitution(widen_arithmetic)]`, each comparison whose operands do
+//! arithmetic is evaluated in `i128` instead: every operand is cast up, and
+//! the comparison sees the mathematical result.
+//!
+//! A comparison is only widened when each of its operands is an integer
+//! literal, an integer field or a `len()` call, combined with `+`, `-`, `*`,
+//! `/`, `%`, unary `-` and parentheses, and no intermediate result can exceed
+//! `i128`. Anything else, such as a float, a bitwise operator or the product
+//! of two `u64` fields, leaves the comparison as written. The solver's integer
+//! model is chosen separately by `int_semantics`, so only the runtime check
+//! changes.
+
+use syn::visit_mut::{self, VisitMut};
+use syn::{BinOp, Expr, Lit, Member, Type, UnOp, parse_quote};
+
+use crate::bounds::self_field;
+
+/// Widens the arithmetic comparisons in an invariant's runtime check.
+pub(crate) fn widen_arithmetic(expr: &mut Expr, fields: &[(Member, Type)]) {
+    Widener { fields }.visit_expr_mut(expr);
+}
+
+struct Widener<'a> {
+    fields: &'a [(Member, Type)],
+}
+
+impl Widener<'_> {
+    /// Returns the largest magnitude an operand tree made only of widenable
+    /// leaves can take, noting whether it does any arithmetic at all. `None`
+    /// if a leaf isn't widenable or the magnitude could exceed `i128`.
+    fn magnitude(&self, expr: &Expr, arithmetic: &mut bool) -> Option<u128> {
+        let magnitude = match expr {
+            Expr::Paren(paren) => self.magnitude(&paren.expr, arithmetic)?,
+            Expr::Group(group) => self.magnitude(&group.expr, arithmetic)?,
+            Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+                *arithmetic = true;
+                self.magnitude(&unary.expr, arithmetic)?
+            }
+            Expr::Binary(binary) if is_arithmetic(&binary.op) => {
+                *arithmetic = true;
+                let left = self.magnitude(&binary.left, arithmetic)?;
+                let right = self.magnitude(&binary.right, arithmetic)?;
+                match binary.op {
+                    BinOp::Add(_) | BinOp::Sub(_) => left.checked_add(right)?,
+                    BinOp::Mul(_) => left.checked_mul(right)?,
+                    BinOp::Div(_) => left,
+                    _ => left.min(right),
+                }
+            }
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse().ok()?,
+            Expr::MethodCall(call) if call.method == "len" && call.args.is_empty() => {
+                u64::MAX.into()
+            }
+            _ => {
+                let name = self_field(expr)?;
+                let (_, ty) = self.fields.iter().find(|(field, _)| field == name)?;
+                // `u128` is the one integer type `i128` doesn't hold
+                let ty = crate::type_ident(ty).filter(|ty| ty != "u128")?;
+                let (lo, hi) = crate::lowering::sized_integer_bounds(&ty, 64)?;
+                lo.unsigned_abs().max(hi.unsigned_abs())
+            }
+        };
+        (magnitude <= i128::MAX.unsigned_abs()).then_some(magnitude)
+    }
+}
+
+impl VisitMut for Widener<'_> {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if let Expr::Binary(binary) = node
+            && is_comparison(&binary.op)
+        {
+            let mut arithmetic = false;
+            if self.magnitude(&binary.left, &mut arithmetic).is_some()
+                && self.magnitude(&binary.right, &mut arithmetic).is_some()
+                && arithmetic
+            {
+                Cast.visit_expr_mut(&mut binary.left);
+                Cast.visit_expr_mut(&mut binary.right);
+                return;
+            }
+        }
+        visit_mut::visit_expr_mut(self, node);
+    }
+}
+
+/// Casts every non-literal leaf of a widenable operand tree to `i128`.
+/// Literals are left to inference.
+struct Cast;
+
+impl VisitMut for Cast {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        match node {
+            Expr::Paren(_) | Expr::Group(_) | Expr::Unary(_) | Expr::Binary(_) => {
+                visit_mut::visit_expr_mut(self, node);
+            }
+            Expr::Lit(_) => {}
+            leaf => *leaf = parse_quote! { ((#leaf) as i128) },
+        }
+    }
+}
+
+fn is_arithmetic(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Add(_) | BinOp::Sub(_) | BinOp::Mul(_) | BinOp::Div(_) | BinOp::Rem(_)
+    )
+}
+
+fn is_comparison(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Eq(_) | BinOp::Ne(_) | BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_)
+    )
+}
@@ -0,0 +1,74 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use proptest::arbitrary::any;
+use proptest::strategy::{Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+
+#[derive(Constitution, Debug)]
+#[constitution(arbitrary)]
+struct Ticket {
+    // Far too narrow for rejection sampling over the whole `u64` domain
+    #[invariant(self.id >= 1_000 && self.id <= 1_003)]
+    id: u64,
+    #[invariant(self.percent <= 100)]
+    percent: u8,
+    #[invariant(self.offset < 50)]
+    offset: i32,
+    note: u16,
+}
+
+#[derive(Constitution, Debug)]
+#[constitution(arbitrary)]
+struct Window {
+    #[invariant(self.start <= self.end)]
+    start: u8,
+    #[invariant(self.end < 200)]
+    end: u8,
+}
+
+fn samples<T: proptest::arbitrary::Arbitrary>(count: usize) -> Vec<T> {
+    let mut runner = TestRunner::default();
+    (0..count)
+        .map(|_| any::<T>().new_tree(&mut runner).unwrap().current())
+        .collect()
+}
+
+#[test]
+fn test_bounded_fields_sampled_from_range() {
+    for ticket in samples::<Ticket>(256) {
+        assert!(ticket.enforce_law().is_ok(), "{ticket:?}");
+        assert!((1_000..=1_003).contains(&ticket.id));
+    }
+}
+
+#[test]
+fn test_cross_field_invariants_filtered() {
+    for window in samples::<Window>(256) {
+        assert!(window.enforce_law().is_ok(), "{window:?}");
+    }
+}
+
+fn draws<S: Strategy>(strategy: S, count: usize) -> Vec<S::Value> {
+    let mut runner = TestRunner::default();
+    (0..count)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .collect()
+}
+
+#[test]
+fn test_arbitrary_valid_upholds_invariants() {
+    for ticket in draws(Ticket::arbitrary_valid(), 64) {
+        assert!(ticket.enforce_law().is_ok(), "{ticket:?}");
+    }
+}
+
+#[test]
+fn test_arbitrary_invalid_breaks_an_invariant() {
+    for ticket in draws(Ticket::arbitrary_invalid(), 64) {
+        assert!(ticket.enforce_law().is_err(), "{ticket:?}");
+    }
+    for window in draws(Window::arbitrary_invalid(), 64) {
+        assert!(window.enforce_law().is_err(), "{window:?}");
+    }
+}
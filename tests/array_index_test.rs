@@ -0,0 +1,57 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Window {
+    #[invariant(self.buf[0] < self.buf[1])]
+    #[invariant(self.buf[3] <= 100)]
+    buf: [i32; 4],
+    scale: u8,
+}
+
+fn window(buf: [i32; 4]) -> Window {
+    Window { buf, scale: 1 }
+}
+
+#[test]
+fn test_indexed_invariants_runtime() {
+    assert!(window([1, 2, 0, 100]).enforce_law().is_ok());
+    assert!(window([2, 2, 0, 100]).enforce_law().is_err());
+    assert!(window([1, 2, 0, 101]).enforce_law().is_err());
+}
+
+#[test]
+fn test_indexed_invariants_use_element_symbols() {
+    assert_eq!(
+        Window::invariant_expressions(),
+        &["self.buf__0 < self.buf__1", "self.buf__3 <= 100"]
+    );
+    let w = window([1, 2, 3, 4]);
+    let provider = w.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("buf__2"),
+        Ok(FieldValue::BitVec {
+            bits: 3,
+            width: 32,
+            signed: true
+        })
+    );
+    assert!(w.verify_integrity_blocking().is_ok());
+    assert_eq!(w.scale, 1);
+}
+
+#[test]
+fn test_array_elements_hashed() {
+    assert_ne!(
+        window([1, 2, 3, 4]).compute_data_hash(),
+        window([1, 2, 3, 5]).compute_data_hash()
+    );
+    assert_eq!(
+        window([1, 2, 3, 4]).compute_data_hash(),
+        window([1, 2, 3, 4]).compute_data_hash()
+    );
+}
@@ -0,0 +1,51 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+struct Portfolio {
+    #[invariant(self.weights.iter().sum::<i32>() == 100)]
+    weights: [i32; 3],
+}
+
+#[test]
+fn test_sum_runtime() {
+    assert!(
+        Portfolio {
+            weights: [50, 30, 20]
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Portfolio {
+            weights: [50, 30, 30]
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_sum_expanded_for_solver() {
+    assert_eq!(
+        Portfolio::invariant_expressions(),
+        &["(self.weights__0 + self.weights__1 + self.weights__2) == 100"]
+    );
+
+    let p = Portfolio {
+        weights: [50, 30, 20],
+    };
+    let provider = p.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("weights__1"),
+        Ok(FieldValue::BitVec {
+            bits: 30,
+            width: 32,
+            signed: true
+        })
+    );
+    assert!(provider.get_field_value("weights__3").is_err());
+}
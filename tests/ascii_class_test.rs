@@ -0,0 +1,59 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+struct Ticket {
+    #[invariant(self.row.is_ascii_uppercase())]
+    row: char,
+    #[invariant(self.seat.is_ascii_digit())]
+    seat: char,
+}
+
+#[test]
+fn test_ascii_class_runtime() {
+    assert!(
+        Ticket {
+            row: 'C',
+            seat: '7'
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Ticket {
+            row: 'c',
+            seat: '7'
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(
+        Ticket {
+            row: 'C',
+            seat: 'x'
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_ascii_class_lowered_to_ranges() {
+    assert_eq!(
+        Ticket::invariant_expressions(),
+        &[
+            "(self.row >= 65 && self.row <= 90)",
+            "(self.seat >= 48 && self.seat <= 57)"
+        ]
+    );
+
+    let t = Ticket {
+        row: 'C',
+        seat: '7',
+    };
+    let provider = t.get_field_provider();
+    assert_eq!(provider.get_field_value("row"), Ok(FieldValue::UInt(67)));
+}
@@ -0,0 +1,88 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Volume {
+    #[invariant(self.value <= Self::MAX_VALUE)]
+    value: i32,
+    #[invariant(self.step >= Self::MIN_STEP && self.step <= self.value)]
+    step: i32,
+}
+
+impl Volume {
+    const MAX_VALUE: i32 = 1000;
+    const MIN_STEP: i32 = 1;
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+enum Setting {
+    Level(#[invariant(self.0 <= Self::TOP)] u32),
+    Off,
+}
+
+impl Setting {
+    const TOP: u32 = 11;
+}
+
+#[test]
+fn test_associated_consts_enforced() {
+    assert!(
+        Volume {
+            value: 1000,
+            step: 5
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Volume {
+            value: 1001,
+            step: 5
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(Volume { value: 10, step: 0 }.enforce_law().is_err());
+    assert!(Setting::Level(11).enforce_law().is_ok());
+    assert!(Setting::Level(12).enforce_law().is_err());
+    assert!(Setting::Off.enforce_law().is_ok());
+}
+
+#[test]
+fn test_associated_consts_exposed_to_solver() {
+    assert_eq!(
+        Volume::invariant_expressions(),
+        &[
+            "self.value <= self.Self__MAX_VALUE",
+            "self.step >= self.Self__MIN_STEP && self.step <= self.value",
+        ]
+    );
+    let volume = Volume { value: 10, step: 2 };
+    let provider = volume.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("Self__MAX_VALUE"),
+        Ok(FieldValue::Int(1000))
+    ));
+    assert!(matches!(
+        provider.get_field_value("Self__MIN_STEP"),
+        Ok(FieldValue::Int(1))
+    ));
+}
+
+#[test]
+fn test_associated_consts_verify() {
+    assert!(
+        Volume {
+            value: 1000,
+            step: 5
+        }
+        .verify_integrity_blocking()
+        .is_ok()
+    );
+    assert!(Setting::Level(3).verify_integrity_blocking().is_ok());
+}
@@ -0,0 +1,38 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(backend = "z3", blocking, parallel)]
+struct Quota {
+    #[invariant(self.used <= self.limit)]
+    used: u32,
+    limit: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(backend = "z3", blocking)]
+enum Meter {
+    Active {
+        #[invariant(self.reading < 1000)]
+        reading: u16,
+    },
+    Idle,
+}
+
+#[test]
+fn test_backend_selected_for_verification() {
+    let quota = Quota { used: 3, limit: 5 };
+    assert!(quota.enforce_law().is_ok());
+    assert!(quota.verify_integrity_blocking().is_ok());
+    assert!(quota.verify_all_parallel().is_ok());
+
+    let over = Quota { used: 6, limit: 5 };
+    assert!(over.verify_integrity_blocking().is_err());
+
+    assert!(
+        Meter::Active { reading: 10 }
+            .verify_integrity_blocking()
+            .is_ok()
+    );
+}
@@ -0,0 +1,63 @@
+// `Window`'s closure invariant is runtime-only by design
+#![allow(deprecated)]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[invariant(start <= end)]
+#[invariant(end - start <= Self::MAX_SPAN)]
+struct Span {
+    #[invariant(start >= 1)]
+    start: u32,
+    #[invariant(self.end > 0)]
+    end: u32,
+}
+
+impl Span {
+    const MAX_SPAN: u32 = 100;
+}
+
+#[derive(Constitution, Debug)]
+struct Window {
+    #[invariant(samples.iter().all(|start| *start > 0))]
+    samples: Vec<u8>,
+    #[invariant("start < 10")]
+    start: u8,
+}
+
+#[test]
+fn test_bare_fields_checked_at_runtime() {
+    assert!(Span { start: 1, end: 5 }.enforce_law().is_ok());
+    assert!(Span { start: 6, end: 5 }.enforce_law().is_err());
+    assert!(Span { start: 1, end: 200 }.enforce_law().is_err());
+}
+
+#[test]
+fn test_solver_sees_prefixed_fields() {
+    assert_eq!(
+        Span::INVARIANTS,
+        [
+            "self.start >= 1",
+            "self.end > 0",
+            "self.start <= self.end",
+            "self.end - self.start <= self.Self__MAX_SPAN",
+        ]
+    );
+    assert_eq!(Window::INVARIANTS, ["self.start < 10"]);
+}
+
+#[test]
+fn test_closure_parameters_shadow_fields() {
+    let window = Window {
+        samples: vec![1, 2],
+        start: 3,
+    };
+    assert!(window.enforce_law().is_ok());
+    let window = Window {
+        samples: vec![0],
+        start: 3,
+    };
+    assert!(window.enforce_law().is_err());
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "bench")]
+
+extern crate alloc;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+/// Counts allocations so the success path can be shown to be allocation-free.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[derive(Constitution)]
+struct RingBuffer {
+    #[invariant(self.head < self.capacity)]
+    head: u32,
+    #[invariant(self.capacity > 0 && self.capacity <= 4096)]
+    capacity: u32,
+}
+
+#[test]
+fn test_bench_enforce_success_path_does_not_allocate() {
+    let sample = RingBuffer {
+        head: 3,
+        capacity: 64,
+    };
+    assert!(sample.enforce_law().is_ok());
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let elapsed = RingBuffer::bench_enforce(10_000, &sample);
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+
+    assert_eq!(after, before);
+    assert!(elapsed.as_nanos() > 0);
+}
@@ -0,0 +1,103 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Register {
+    #[constitution(bits = 12)]
+    #[invariant(self.offset % 4 == 0)]
+    offset: u32,
+    #[constitution(bits = 4)]
+    delta: i8,
+    mode: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(int_semantics = "math")]
+struct Header(#[constitution(bits = 3)] u16);
+
+#[test]
+fn test_bits_add_a_range_invariant() {
+    assert_eq!(
+        Register::INVARIANTS,
+        &[
+            "self.offset <= 4095",
+            "self.offset % 4 == 0",
+            "self.delta >= -8 && self.delta <= 7",
+        ]
+    );
+    assert_eq!(Header::INVARIANTS, &["self.0 <= 7"]);
+}
+
+#[test]
+fn test_bits_checked_at_runtime() {
+    let valid = Register {
+        offset: 4092,
+        delta: -8,
+        mode: 255,
+    };
+    assert!(valid.enforce_law().is_ok());
+    let wide = Register {
+        offset: 4096,
+        delta: 0,
+        mode: 0,
+    };
+    assert!(wide.enforce_law().is_err());
+    let signed = Register {
+        offset: 0,
+        delta: 8,
+        mode: 0,
+    };
+    assert!(signed.enforce_law().is_err());
+    assert!(Header(7).enforce_law().is_ok());
+    assert!(Header(8).enforce_law().is_err());
+}
+
+#[test]
+fn test_solver_sees_the_logical_width() {
+    let register = Register {
+        offset: 4092,
+        delta: -3,
+        mode: 1,
+    };
+    let provider = register.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("offset").unwrap(),
+        FieldValue::BitVec {
+            bits: 4092,
+            width: 12,
+            signed: false,
+        }
+    );
+    assert_eq!(
+        provider.get_field_value("delta").unwrap(),
+        FieldValue::BitVec {
+            bits: -3i8 as u128,
+            width: 4,
+            signed: true,
+        }
+    );
+    assert_eq!(
+        provider.get_field_value("mode").unwrap(),
+        FieldValue::BitVec {
+            bits: 1,
+            width: u8::BITS,
+            signed: false,
+        }
+    );
+    assert!(register.verify_integrity_blocking().is_ok());
+}
+
+#[test]
+fn test_values_beyond_the_width_fail_the_proof() {
+    let wide = Register {
+        offset: 5000,
+        delta: 0,
+        mode: 0,
+    };
+    assert!(wide.get_field_provider().get_field_value("offset").is_err());
+    assert!(wide.verify_integrity_blocking().is_err());
+}
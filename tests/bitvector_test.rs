@@ -0,0 +1,92 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(bitvector)]
+struct Grid {
+    #[invariant(self.rows * self.cols <= self.max)]
+    rows: u32,
+    cols: u32,
+    max: u32,
+    #[invariant(self.offset + 1 > self.offset)]
+    offset: i8,
+    cells: [u16; 2],
+}
+
+#[derive(Constitution)]
+#[constitution(int_semantics = "math")]
+struct Plain {
+    #[invariant(self.rows * self.cols <= 100)]
+    rows: u32,
+    cols: u32,
+}
+
+#[test]
+fn test_bitvector_fields_carry_width_and_signedness() {
+    let grid = Grid {
+        rows: 4,
+        cols: 5,
+        max: 20,
+        offset: -3,
+        cells: [7, 9],
+    };
+    assert!(grid.enforce_law().is_ok());
+    let provider = grid.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("rows").unwrap(),
+        FieldValue::BitVec {
+            bits: 4,
+            width: 32,
+            signed: false
+        }
+    );
+    match provider.get_field_value("offset").unwrap() {
+        FieldValue::BitVec {
+            bits,
+            width: 8,
+            signed: true,
+        } => assert_eq!(bits as u8 as i8, -3),
+        other => panic!("expected an 8-bit signed bitvector, got {:?}", other),
+    }
+    assert_eq!(
+        provider.get_field_value("cells__1").unwrap(),
+        FieldValue::BitVec {
+            bits: 9,
+            width: 16,
+            signed: false
+        }
+    );
+}
+
+#[derive(Constitution)]
+struct Defaulted {
+    #[invariant(self.a + self.b > self.a)]
+    a: i16,
+    b: i16,
+}
+
+#[test]
+fn test_integers_are_bitvectors_by_default() {
+    let value = Defaulted { a: 1, b: 2 };
+    assert!(value.enforce_law().is_ok());
+    assert_eq!(
+        value.get_field_provider().get_field_value("b"),
+        Ok(FieldValue::BitVec {
+            bits: 2,
+            width: 16,
+            signed: true
+        })
+    );
+}
+
+#[test]
+fn test_integers_unbounded_under_math() {
+    let plain = Plain { rows: 4, cols: 5 };
+    assert!(matches!(
+        plain.get_field_provider().get_field_value("rows"),
+        Ok(FieldValue::UInt(4))
+    ));
+}
@@ -0,0 +1,51 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking, smt_script)]
+struct ControlRegister {
+    #[invariant(self.flags & 0x3 == 0)]
+    #[invariant(self.flags | 0x80 == 0x80)]
+    flags: u8,
+    #[invariant(self.mask ^ 0xFF != 0)]
+    #[invariant(self.mask & 0b1111_0000 == 0)]
+    #[invariant(self.mask & 0x1u8 == 1)]
+    mask: u8,
+    #[invariant(self.shift << 2 <= 64 && self.shift >> 1 < 8)]
+    shift: u32,
+}
+
+fn register(flags: u8, mask: u8, shift: u32) -> ControlRegister {
+    ControlRegister { flags, mask, shift }
+}
+
+#[test]
+fn test_bitwise_invariants_at_runtime() {
+    assert!(register(0x80, 0x0F, 4).enforce_law().is_ok());
+    assert!(register(0x81, 0x0F, 4).enforce_law().is_err());
+    assert!(register(0x84, 0x0F, 4).enforce_law().is_err());
+    assert!(register(0x80, 0xFF, 4).enforce_law().is_err());
+    assert!(register(0x80, 0x1F, 4).enforce_law().is_err());
+    assert!(register(0x80, 0x0F, 17).enforce_law().is_err());
+}
+
+#[test]
+fn test_bitwise_invariants_accepted_by_solver() {
+    assert_eq!(
+        ControlRegister::invariant_expressions(),
+        &[
+            "self.flags & 0x3 == 0",
+            "self.flags | 0x80 == 0x80",
+            "self.mask ^ 0xFF != 0",
+            "self.mask & 240 == 0",
+            "self.mask & 1 == 1",
+            "self.shift << 2 <= 64 && self.shift >> 1 < 8",
+        ]
+    );
+    assert!(register(0x80, 0x0F, 4).verify_integrity_blocking().is_ok());
+    let script = register(0x80, 0x0F, 4).smt_script().unwrap();
+    assert!(script.contains("bvand"));
+    assert!(script.contains("bvshl"));
+}
@@ -0,0 +1,43 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Budget {
+    #[invariant(self.spent <= 1000)]
+    spent: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Ledger {
+    #[constitution(nested)]
+    budget: Budget,
+    #[invariant(self.entries >= 1)]
+    entries: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+enum Limit {
+    Soft(#[invariant(self.0 > 0)] u32),
+    None,
+}
+
+#[test]
+fn test_blocking_verification_without_runtime() {
+    assert!(Budget { spent: 10 }.verify_integrity_blocking().is_ok());
+    let ledger = Ledger {
+        budget: Budget { spent: 10 },
+        entries: 3,
+    };
+    assert!(ledger.enforce_law().is_ok());
+    assert!(ledger.verify_integrity_blocking().is_ok());
+}
+
+#[test]
+fn test_blocking_verification_on_enum() {
+    assert!(Limit::Soft(5).verify_integrity_blocking().is_ok());
+    assert!(Limit::None.verify_integrity_blocking().is_ok());
+}
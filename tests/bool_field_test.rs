@@ -0,0 +1,69 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct Queue {
+    #[invariant(self.enabled || self.count == 0)]
+    enabled: bool,
+    #[invariant(self.count <= 64)]
+    count: u32,
+}
+
+#[test]
+fn test_bool_field_runtime() {
+    assert!(
+        Queue {
+            enabled: true,
+            count: 3
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Queue {
+            enabled: false,
+            count: 0
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Queue {
+            enabled: false,
+            count: 3
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_bool_field_exposed_to_solver() {
+    let queue = Queue {
+        enabled: true,
+        count: 3,
+    };
+    let provider = queue.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("enabled"),
+        Ok(FieldValue::Bool(true))
+    );
+    assert!(queue.verify_integrity_partial().is_verified());
+}
+
+#[test]
+fn test_bool_field_hashed() {
+    let on = Queue {
+        enabled: true,
+        count: 0,
+    };
+    let off = Queue {
+        enabled: false,
+        count: 0,
+    };
+    assert_ne!(on.compute_data_hash(), off.compute_data_hash());
+}
@@ -0,0 +1,72 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(builder)]
+struct ServerConfig<'a> {
+    #[invariant(self.port >= 1024)]
+    port: u16,
+    #[invariant(self.workers > 0 && self.workers <= 64)]
+    workers: u32,
+    host: &'a str,
+    #[invariant(self.backlog.is_none_or(|backlog| backlog > 0))]
+    backlog: Option<u32>,
+}
+
+#[derive(Constitution, Debug)]
+#[constitution(builder)]
+struct Span(#[invariant(self.0 <= self.1)] u32, u32);
+
+#[test]
+fn test_builder_checks_invariants() {
+    let config = ServerConfig::builder()
+        .port(8080)
+        .workers(4)
+        .host("localhost")
+        .build()
+        .unwrap();
+    assert_eq!(
+        (config.port, config.workers, config.host, config.backlog),
+        (8080, 4, "localhost", None)
+    );
+    let config = ServerConfig::builder()
+        .port(8080)
+        .workers(4)
+        .host("localhost")
+        .backlog(Some(128))
+        .build()
+        .unwrap();
+    assert_eq!(config.backlog, Some(128));
+
+    match ServerConfig::builder()
+        .port(80)
+        .workers(4)
+        .host("localhost")
+        .build()
+    {
+        Err(ConstitutionError::InvariantViolation { expression, .. }) => {
+            assert_eq!(expression, "self.port >= 1024");
+        }
+        other => panic!("expected a violation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_builder_reports_missing_field() {
+    match ServerConfig::builder().port(8080).host("localhost").build() {
+        Err(ConstitutionError::MissingField { field }) => assert_eq!(field, "workers"),
+        other => panic!("expected a missing field, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_builder_on_tuple_struct() {
+    let span = Span::builder().field_0(1).field_1(5).build().unwrap();
+    assert_eq!((span.0, span.1), (1, 5));
+    assert!(Span::builder().field_0(6).field_1(5).build().is_err());
+    assert!(matches!(
+        Span::builder().field_1(5).build(),
+        Err(ConstitutionError::MissingField { field }) if field == "0"
+    ));
+}
@@ -0,0 +1,37 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(c_export)]
+#[repr(C)]
+pub struct SensorReading {
+    #[invariant(self.kelvin >= 200 && self.kelvin <= 400)]
+    kelvin: u32,
+    #[invariant(self.channel < 8)]
+    channel: u8,
+}
+
+#[test]
+fn test_c_export_returns_violation_codes() {
+    let valid = SensorReading {
+        kelvin: 293,
+        channel: 3,
+    };
+    let too_hot = SensorReading {
+        kelvin: 500,
+        channel: 3,
+    };
+    let bad_channel = SensorReading {
+        kelvin: 293,
+        channel: 9,
+    };
+
+    unsafe {
+        assert_eq!(validate_sensor_reading(&valid), 0);
+        assert_eq!(validate_sensor_reading(&too_hot), 1);
+        assert_eq!(validate_sensor_reading(&bad_channel), 2);
+        assert_eq!(validate_sensor_reading(core::ptr::null()), -1);
+    }
+    assert!(valid.enforce_law().is_ok());
+}
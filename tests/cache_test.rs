@@ -0,0 +1,70 @@
+#![cfg(feature = "cache")]
+
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{CacheResult, ProofError, ProveInvariant, VerificationCache};
+
+#[derive(Constitution)]
+struct Sample {
+    #[invariant(self.reading <= 100)]
+    reading: u32,
+}
+
+#[derive(Constitution)]
+enum Gauge {
+    Level(#[invariant(self.0 <= 10)] u8),
+    Off,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+fn key_of(sample: &Sample) -> [u8; 32] {
+    VerificationCache::compute_key(
+        core::any::type_name::<Sample>(),
+        &sample.compute_data_hash(),
+        Sample::invariant_expressions(),
+    )
+}
+
+#[test]
+fn test_cache_stores_verdict_on_miss() {
+    let cache = VerificationCache::new();
+    let sample = Sample { reading: 42 };
+    assert_eq!(cache.lookup(&key_of(&sample)), CacheResult::Miss);
+    assert!(block_on(sample.verify_integrity_cached(&cache)).is_ok());
+    assert_eq!(cache.lookup(&key_of(&sample)), CacheResult::Hit);
+    assert!(block_on(Sample { reading: 42 }.verify_integrity_cached(&cache)).is_ok());
+    assert_eq!(
+        cache.lookup(&key_of(&Sample { reading: 43 })),
+        CacheResult::Miss
+    );
+}
+
+#[test]
+fn test_cache_hit_skips_solver() {
+    let cache = VerificationCache::new();
+    let sample = Sample { reading: 7 };
+    cache.store(key_of(&sample), false);
+    assert!(matches!(
+        block_on(sample.verify_integrity_cached(&cache)),
+        Err(ProofError::InvariantViolated(_))
+    ));
+}
+
+#[test]
+fn test_cache_on_enum() {
+    let cache = VerificationCache::new();
+    assert!(block_on(Gauge::Level(3).verify_integrity_cached(&cache)).is_ok());
+    assert!(block_on(Gauge::Off.verify_integrity_cached(&cache)).is_ok());
+    assert!(Gauge::Level(3).enforce_law().is_ok());
+}
@@ -0,0 +1,28 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution, Debug)]
+#[constitution(blocking)]
+struct Celsius(#[invariant(self.0 >= -273.15)] f64);
+
+#[test]
+fn test_celsius_runtime() {
+    assert!(Celsius(20.0).enforce_law().is_ok());
+    assert!(Celsius(-273.15).enforce_law().is_ok());
+    assert!(Celsius(-273.16).enforce_law().is_err());
+}
+
+#[test]
+fn test_celsius_reaches_the_solver() {
+    assert_eq!(Celsius::invariant_expressions(), &["self.0 >= -273.15"]);
+    let freezing = Celsius(0.0);
+    assert_eq!(
+        freezing.get_field_provider().get_field_value("0"),
+        Ok(FieldValue::Real(0.0))
+    );
+    assert!(freezing.verify_integrity_blocking().is_ok());
+    assert!(Celsius(-300.0).verify_integrity_blocking().is_err());
+}
@@ -0,0 +1,62 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+struct Report {
+    #[invariant(self.grade >= 'A' && self.grade <= 'F')]
+    grade: char,
+    score: u8,
+}
+
+#[test]
+fn test_char_field_runtime() {
+    assert!(
+        Report {
+            grade: 'B',
+            score: 80
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Report {
+            grade: 'Z',
+            score: 80
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_char_literals_lowered_to_codepoints() {
+    assert_eq!(
+        Report::invariant_expressions(),
+        &["self.grade >= 65 && self.grade <= 70"]
+    );
+    let report = Report {
+        grade: 'C',
+        score: 70,
+    };
+    assert!(matches!(
+        report.get_field_provider().get_field_value("grade"),
+        Ok(FieldValue::UInt(67))
+    ));
+    assert_eq!(report.score, 70);
+}
+
+#[test]
+fn test_char_field_hashed() {
+    let a = Report {
+        grade: 'A',
+        score: 1,
+    };
+    let b = Report {
+        grade: 'B',
+        score: 1,
+    };
+    assert_ne!(a.compute_data_hash(), b.compute_data_hash());
+}
@@ -0,0 +1,79 @@
+// Function-call invariants warn that they are runtime-only, which is expected here
+#![allow(deprecated)]
+// `verify_integrity_partial()` mustn't set up a solver it never uses
+#![deny(unused_imports, unused_mut, unused_variables)]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{ProveInvariant, VerificationMode};
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct Packet {
+    #[invariant(self.len <= 4)]
+    len: u8,
+    data: [u8; 4],
+    #[invariant(self.crc == crc32(&self.data))]
+    crc: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct Frame {
+    data: [u8; 4],
+    #[invariant(self.crc == crc32(&self.data))]
+    crc: u32,
+}
+
+fn packet(data: [u8; 4]) -> Packet {
+    Packet {
+        len: 4,
+        data,
+        crc: crc32(&data),
+    }
+}
+
+#[test]
+fn test_checksum_runtime() {
+    let mut p = packet(*b"abcd");
+    assert!(p.enforce_law().is_ok());
+
+    p.data[0] = b'x';
+    assert!(p.enforce_law().is_err());
+}
+
+#[test]
+fn test_checksum_is_runtime_only() {
+    assert_eq!(Packet::invariant_expressions(), &["self.len <= 4"]);
+
+    let proof = packet(*b"abcd").verify_integrity_partial();
+    let modes: Vec<_> = proof.outcomes.iter().map(|o| o.mode).collect();
+    assert_eq!(modes, [VerificationMode::Formal, VerificationMode::Runtime]);
+    assert!(proof.is_verified());
+}
+
+#[test]
+fn test_partial_without_formal_invariants() {
+    let data = *b"abcd";
+    let frame = Frame {
+        data,
+        crc: crc32(&data),
+    };
+    let proof = frame.verify_integrity_partial();
+    let modes: Vec<_> = proof.outcomes.iter().map(|o| o.mode).collect();
+    assert_eq!(modes, [VerificationMode::Runtime]);
+    assert!(proof.is_verified());
+}
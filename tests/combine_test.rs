@@ -0,0 +1,59 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(combine, partial)]
+#[invariant(self.low <= self.high)]
+struct Band {
+    #[invariant(self.low > 0)]
+    #[invariant(self.low < 100)]
+    low: u32,
+    #[invariant(self.high < 1000, name = "ceiling")]
+    high: u32,
+}
+
+#[derive(Constitution)]
+struct Separate {
+    #[invariant(self.x > 0)]
+    #[invariant(self.x < 100)]
+    x: u32,
+}
+
+#[test]
+fn test_combine_conjoins_field_invariants_for_solver() {
+    assert_eq!(
+        Band::invariant_expressions(),
+        &[
+            "(self.low > 0) && (self.low < 100)",
+            "self.high < 1000",
+            "self.low <= self.high"
+        ]
+    );
+    assert_eq!(Band::invariant_labels(), &[None, Some("ceiling"), None]);
+    assert_eq!(
+        Separate::invariant_expressions(),
+        &["self.x > 0", "self.x < 100"]
+    );
+}
+
+#[test]
+fn test_combine_keeps_separate_runtime_checks() {
+    assert!(Band { low: 5, high: 50 }.enforce_law().is_ok());
+    let error = Band {
+        low: 200,
+        high: 500,
+    }
+    .enforce_law()
+    .unwrap_err();
+    assert!(error.to_string().contains("self.low < 100"), "{error}");
+    assert!(!error.to_string().contains("self.low > 0"), "{error}");
+    assert_eq!(
+        Band { low: 0, high: 500 }
+            .verify_integrity_partial()
+            .outcomes
+            .len(),
+        4
+    );
+}
@@ -0,0 +1,56 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Packet {
+    #[invariant(
+        when = "self.kind == 2",
+        then = "self.extra > 0",
+        name = "extended payload"
+    )]
+    kind: u8,
+    #[invariant(self.extra > 100 => self.kind == 2)]
+    extra: u32,
+}
+
+#[test]
+fn test_conditional_checked_at_runtime() {
+    assert!(Packet { kind: 1, extra: 0 }.enforce_law().is_ok());
+    assert!(Packet { kind: 2, extra: 5 }.enforce_law().is_ok());
+    match (Packet { kind: 2, extra: 0 }).enforce_law() {
+        Err(ConstitutionError::InvariantViolation {
+            name, expression, ..
+        }) => {
+            assert_eq!(name.as_deref(), Some("extended payload"));
+            assert_eq!(expression, "self.kind == 2 => self.extra > 0");
+        }
+        other => panic!("expected a violation, got {:?}", other),
+    }
+    assert!(
+        Packet {
+            kind: 1,
+            extra: 200
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_conditional_lowered_to_implication() {
+    assert_eq!(
+        Packet::invariant_expressions(),
+        &[
+            "(! (self.kind == 2) || (self.extra > 0))",
+            "(! (self.extra > 100) || (self.kind == 2))",
+        ]
+    );
+    assert!(
+        Packet { kind: 2, extra: 1 }
+            .verify_integrity_blocking()
+            .is_ok()
+    );
+}
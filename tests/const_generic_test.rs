@@ -0,0 +1,67 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Buf<const N: usize> {
+    #[invariant(self.len <= N)]
+    len: usize,
+    data: [u8; N],
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+enum Slot<const CAP: u32> {
+    Used {
+        #[invariant(self.count > 0 && self.count <= CAP)]
+        count: u32,
+    },
+    Free,
+}
+
+#[test]
+fn test_const_generic_runtime() {
+    assert!(
+        Buf::<4> {
+            len: 4,
+            data: [0; 4]
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Buf::<4> {
+            len: 5,
+            data: [0; 4]
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(Slot::<8>::Used { count: 8 }.enforce_law().is_ok());
+    assert!(Slot::<8>::Used { count: 9 }.enforce_law().is_err());
+    assert!(Slot::<8>::Free.enforce_law().is_ok());
+}
+
+#[test]
+fn test_const_generic_pinned_for_solver() {
+    assert_eq!(Buf::<4>::invariant_expressions(), &["self.len <= self.N"]);
+
+    let full = Buf::<4> {
+        len: 4,
+        data: [0; 4],
+    };
+    assert!(full.verify_integrity_blocking().is_ok());
+    let over = Buf::<4> {
+        len: 5,
+        data: [0; 4],
+    };
+    assert!(over.verify_integrity_blocking().is_err());
+
+    assert!(
+        Slot::<8>::Used { count: 3 }
+            .verify_integrity_blocking()
+            .is_ok()
+    );
+}
@@ -0,0 +1,34 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(validate_const)]
+struct Ring<const N: usize> {
+    #[invariant(self.head < 64 && self.head <= self.tail)]
+    head: usize,
+    #[invariant(self.tail < 64)]
+    tail: usize,
+    #[invariant(self.enabled || self.tail == 0)]
+    enabled: bool,
+}
+
+impl<const N: usize> Ring<N> {
+    const DEFAULT: Self = Ring {
+        head: 0,
+        tail: N,
+        enabled: true,
+    };
+}
+
+const _: () = assert!(Ring::<4>::DEFAULT.validate_const());
+
+#[test]
+fn test_validate_const_matches_runtime() {
+    assert!(Ring::<8>::DEFAULT.validate_const());
+    assert!(Ring::<8>::DEFAULT.enforce_law().is_ok());
+
+    let invalid = Ring::<128>::DEFAULT;
+    assert!(!invalid.validate_const());
+    assert!(invalid.enforce_law().is_err());
+}
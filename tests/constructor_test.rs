@@ -0,0 +1,32 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(constructor)]
+struct Range {
+    #[invariant(self.start <= self.end)]
+    start: u32,
+    end: u32,
+    label: String,
+}
+
+#[derive(Constitution, Debug)]
+#[constitution(constructor)]
+struct Meters(#[invariant(self.0 >= 0)] i64);
+
+#[test]
+fn test_try_new_checks_invariants() {
+    let range = Range::try_new(1, 5, "window".to_string()).unwrap();
+    assert_eq!(
+        (range.start, range.end, range.label.as_str()),
+        (1, 5, "window")
+    );
+    assert!(Range::try_new(6, 5, String::new()).is_err());
+}
+
+#[test]
+fn test_try_new_on_tuple_struct() {
+    assert_eq!(Meters::try_new(3).unwrap().0, 3);
+    assert!(Meters::try_new(-1).is_err());
+}
@@ -0,0 +1,79 @@
+// Function-call invariants warn that they are runtime-only, which is expected here
+#![allow(deprecated)]
+
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ContractField};
+use praborrow_defense::Constitution;
+
+fn is_even(value: u32) -> bool {
+    value.is_multiple_of(2)
+}
+
+#[derive(Constitution)]
+#[constitution(contract)]
+struct Shipment {
+    #[invariant(self.weight > 0, group = "physical")]
+    weight: u32,
+    #[invariant(is_even(self.pallets))]
+    pallets: u32,
+    label: [u8; 2],
+}
+
+#[derive(Constitution)]
+#[constitution(contract)]
+struct ShipmentV2 {
+    #[invariant(self.weight > 0, group = "physical")]
+    #[invariant(self.weight < 40000)]
+    weight: u32,
+    #[invariant(is_even(self.pallets))]
+    pallets: u32,
+}
+
+#[test]
+fn test_contract_describes_type() {
+    let contract = Shipment::contract();
+    assert_eq!(contract.type_name, "Shipment");
+    assert_eq!(contract.version, env!("CARGO_PKG_VERSION"));
+
+    assert_eq!(contract.fields.len(), 3);
+    assert_eq!(
+        contract.fields[0],
+        ContractField {
+            name: "weight",
+            ty: "u32"
+        }
+    );
+    assert_eq!(contract.fields[2].name, "label");
+
+    assert_eq!(contract.invariants.len(), 2);
+    let weight = &contract.invariants[0];
+    assert_eq!(weight.expression, "self.weight > 0");
+    assert_eq!(weight.field, Some("weight"));
+    assert_eq!(weight.group, Some("physical"));
+    assert!(!weight.runtime_only);
+
+    let pallets = &contract.invariants[1];
+    assert_eq!(pallets.field, Some("pallets"));
+    assert_eq!(pallets.group, None);
+    assert!(pallets.runtime_only);
+}
+
+#[test]
+fn test_contract_hash_tracks_invariant_set() {
+    let v1 = Shipment::contract();
+    let v2 = ShipmentV2::contract();
+    assert_eq!(v1.invariant_set_hash.len(), 32);
+    assert_eq!(
+        v1.invariant_set_hash,
+        Shipment::contract().invariant_set_hash
+    );
+    assert_ne!(v1.invariant_set_hash, v2.invariant_set_hash);
+
+    let s = Shipment {
+        weight: 1,
+        pallets: 2,
+        label: *b"ok",
+    };
+    assert!(s.enforce_law().is_ok());
+    assert_eq!(&s.label, b"ok");
+}
@@ -0,0 +1,98 @@
+extern crate alloc;
+use std::collections::BTreeMap;
+
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::backend::{FieldValue, FieldValueProvider};
+use praborrow_prover::{
+    InvariantStatus, ProofError, ProveInvariant, SmtContext, VerificationToken,
+};
+
+/// Stands in for a solver that refutes `self.spent <= 1000` with a model.
+struct Refuted {
+    spent: i64,
+}
+
+struct SpentProvider(i64);
+
+impl FieldValueProvider for SpentProvider {
+    fn get_field_value(&self, name: &str) -> Result<FieldValue, ProofError> {
+        match name {
+            "spent" => Ok(FieldValue::Int(self.0)),
+            _ => Err(ProofError::ParseError(format!("Unknown field: {}", name))),
+        }
+    }
+}
+
+impl CheckProtocol for Refuted {
+    fn enforce_law(&self) -> Result<(), ConstitutionError> {
+        Ok(())
+    }
+}
+
+impl ProveInvariant for Refuted {
+    fn invariant_expressions() -> &'static [&'static str] {
+        &["self.spent <= 1000"]
+    }
+
+    fn compute_data_hash(&self) -> Vec<u8> {
+        self.spent.to_le_bytes().to_vec()
+    }
+
+    fn get_field_provider(&self) -> Box<dyn FieldValueProvider + '_> {
+        Box::new(SpentProvider(self.spent))
+    }
+
+    async fn verify_with_context(
+        &self,
+        _ctx: &SmtContext,
+    ) -> Result<VerificationToken, ProofError> {
+        Err(ProofError::CounterExample {
+            expression: "self.spent <= 1000".to_string(),
+            assignment: BTreeMap::from([("spent".to_string(), self.spent)]),
+        })
+    }
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Ledger {
+    #[constitution(nested)]
+    budget: Refuted,
+    #[invariant(self.entries >= 1)]
+    entries: u32,
+}
+
+#[test]
+fn test_nested_counterexample_keyed_by_path() {
+    let ledger = Ledger {
+        budget: Refuted { spent: 1001 },
+        entries: 1,
+    };
+    match ledger.verify_integrity_blocking() {
+        Err(ProofError::CounterExample {
+            expression,
+            assignment,
+        }) => {
+            assert_eq!(expression, "in field 'budget': self.spent <= 1000");
+            assert_eq!(
+                assignment,
+                BTreeMap::from([("budget.spent".to_string(), 1001)])
+            );
+        }
+        other => panic!("expected a counterexample, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_counterexample_reported_as_disproved() {
+    let error = ProofError::CounterExample {
+        expression: "self.spent <= 1000".to_string(),
+        assignment: BTreeMap::from([("spent".to_string(), 1001)]),
+    };
+    assert!(error.to_string().contains("spent"));
+    assert!(matches!(
+        InvariantStatus::from(Err(error)),
+        InvariantStatus::Disproved { .. }
+    ));
+}
@@ -0,0 +1,45 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Invalid(String),
+    #[allow(dead_code)]
+    Io,
+}
+
+impl From<ConstitutionError> for AppError {
+    fn from(error: ConstitutionError) -> Self {
+        AppError::Invalid(error.to_string())
+    }
+}
+
+#[derive(Constitution)]
+#[constitution(error = "crate::AppError")]
+struct Payment {
+    #[invariant(self.cents > 0)]
+    cents: u64,
+}
+
+fn charge(payment: &Payment) -> Result<u64, AppError> {
+    payment.validate()?;
+    Ok(payment.cents)
+}
+
+#[test]
+fn test_validate_converts_violation() {
+    assert_eq!(charge(&Payment { cents: 250 }), Ok(250));
+    match charge(&Payment { cents: 0 }) {
+        Err(AppError::Invalid(message)) => assert!(message.contains("self.cents > 0")),
+        other => panic!("expected a converted violation, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_enforce_law_keeps_constitution_error() {
+    assert!(matches!(
+        Payment { cents: 0 }.enforce_law(),
+        Err(ConstitutionError::InvariantViolation { .. })
+    ));
+}
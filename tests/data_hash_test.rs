@@ -0,0 +1,88 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Hash, Clone, Copy, PartialEq, Eq)]
+enum Tier {
+    Free,
+    Paid,
+}
+
+#[derive(Hash)]
+struct Region(&'static str);
+
+#[derive(Constitution)]
+struct Profile {
+    #[invariant(self.age < 150)]
+    age: u8,
+    name: String,
+    active: bool,
+    initial: char,
+    tier: Tier,
+    region: Region,
+    tags: (u8, u8),
+}
+
+#[derive(Constitution)]
+struct Scaled<T: Into<i64> + Copy> {
+    #[invariant(self.value > 0)]
+    value: T,
+}
+
+fn profile() -> Profile {
+    Profile {
+        age: 30,
+        name: "ana".to_string(),
+        active: true,
+        initial: 'a',
+        tier: Tier::Free,
+        region: Region("eu"),
+        tags: (1, 2),
+    }
+}
+
+#[test]
+fn test_every_field_changes_the_hash() {
+    let base = profile().compute_data_hash();
+    assert!(profile().enforce_law().is_ok());
+    assert_eq!(base, profile().compute_data_hash());
+
+    let variants = [
+        Profile {
+            name: "bob".to_string(),
+            ..profile()
+        },
+        Profile {
+            active: false,
+            ..profile()
+        },
+        Profile {
+            initial: 'b',
+            ..profile()
+        },
+        Profile {
+            tier: Tier::Paid,
+            ..profile()
+        },
+        Profile {
+            region: Region("us"),
+            ..profile()
+        },
+        Profile {
+            tags: (2, 1),
+            ..profile()
+        },
+    ];
+    for changed in &variants {
+        assert_ne!(base, changed.compute_data_hash());
+    }
+}
+
+#[test]
+fn test_generic_numeric_field_is_hashed() {
+    assert_ne!(
+        Scaled { value: 1i32 }.compute_data_hash(),
+        Scaled { value: 2i32 }.compute_data_hash()
+    );
+}
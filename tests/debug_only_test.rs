@@ -0,0 +1,20 @@
+#![cfg(feature = "debug-only")]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(enforce_all)]
+struct Gauge {
+    #[invariant(self.level <= 100)]
+    level: u32,
+}
+
+#[test]
+fn test_debug_only_checks_follow_debug_assertions() {
+    let gauge = Gauge { level: 150 };
+    assert_eq!(gauge.enforce_law().is_err(), cfg!(debug_assertions));
+    // Explicitly invoked checks still run
+    assert!(gauge.enforce_law_all().is_err());
+}
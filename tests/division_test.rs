@@ -0,0 +1,137 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Slot {
+    #[invariant(self.offset % self.align == 0)]
+    offset: u32,
+    align: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Signed {
+    #[invariant(self.value % 4 == self.rem)]
+    #[invariant(self.value / 4 == self.quot)]
+    value: i32,
+    rem: i32,
+    quot: i32,
+}
+
+#[derive(Constitution)]
+struct Ratio {
+    #[invariant(self.total / self.parts <= self.total / (self.parts + 1) + self.total / self.parts)]
+    total: u64,
+    parts: u64,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Stride {
+    #[invariant(!(self.offset % self.step == 0))]
+    offset: u32,
+    step: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Scale {
+    #[invariant(self.value / self.factor <= self.limit)]
+    value: i32,
+    factor: i32,
+    limit: i32,
+}
+
+#[test]
+fn test_non_literal_divisor_guarded() {
+    assert_eq!(
+        Slot::invariant_expressions(),
+        &["(self.align != 0 && (self.offset % self.align == 0))"]
+    );
+    assert_eq!(
+        Ratio::invariant_expressions(),
+        &["(self.parts != 0 && (self.parts + 1) != 0 && \
+           (self.total / self.parts <= self.total / (self.parts + 1) + self.total / self.parts))"]
+    );
+}
+
+#[test]
+fn test_literal_divisor_unchanged() {
+    assert_eq!(
+        Signed::invariant_expressions(),
+        &["self.value % 4 == self.rem", "self.value / 4 == self.quot"]
+    );
+}
+
+#[test]
+fn test_division_truncates_like_rust() {
+    // Rust truncates toward zero: -5 % 4 == -1 and -5 / 4 == -1, where the
+    // Euclidean forms would give 3 and -2
+    let signed = Signed {
+        value: -5,
+        rem: -1,
+        quot: -1,
+    };
+    assert!(signed.enforce_law().is_ok());
+    assert!(signed.verify_integrity_blocking().is_ok());
+
+    let euclidean = Signed {
+        value: -5,
+        rem: 3,
+        quot: -2,
+    };
+    assert!(euclidean.enforce_law().is_err());
+    assert!(euclidean.verify_integrity_blocking().is_err());
+}
+
+#[test]
+fn test_zero_divisor_fails_verification() {
+    let aligned = Slot {
+        offset: 12,
+        align: 4,
+    };
+    assert!(aligned.enforce_law().is_ok());
+    assert!(aligned.verify_integrity_blocking().is_ok());
+
+    let unaligned = Slot {
+        offset: 12,
+        align: 0,
+    };
+    assert!(unaligned.verify_integrity_blocking().is_err());
+}
+
+#[test]
+fn test_negated_division_guarded_at_root() {
+    assert_eq!(
+        Stride::invariant_expressions(),
+        &["(self.step != 0 && (!(self.offset % self.step == 0)))"]
+    );
+    let misaligned = Stride { offset: 5, step: 4 };
+    assert!(misaligned.verify_integrity_blocking().is_ok());
+
+    // Negating the comparison mustn't turn the zero-divisor guard into an escape
+    let zero = Stride { offset: 5, step: 0 };
+    assert!(zero.verify_integrity_blocking().is_err());
+}
+
+#[test]
+fn test_signed_min_by_minus_one_fails_verification() {
+    assert_eq!(
+        Scale::invariant_expressions(),
+        &[
+            "(self.factor != 0 && (self.factor != -1 || self.value > -2147483648) && \
+             (self.value / self.factor <= self.limit))"
+        ]
+    );
+    let scale = |value, factor| Scale {
+        value,
+        factor,
+        limit: i32::MAX,
+    };
+    assert!(scale(i32::MIN + 1, -1).verify_integrity_blocking().is_ok());
+    assert!(scale(i32::MIN, 2).verify_integrity_blocking().is_ok());
+    assert!(scale(i32::MIN, -1).verify_integrity_blocking().is_err());
+}
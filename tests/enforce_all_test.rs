@@ -0,0 +1,44 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(enforce_all)]
+#[invariant(self.low <= self.high)]
+struct Band {
+    #[invariant(self.low >= 10)]
+    low: u32,
+    #[invariant(self.high < 100)]
+    high: u32,
+}
+
+fn expressions(violations: &[ConstitutionError]) -> Vec<&str> {
+    violations
+        .iter()
+        .map(|violation| match violation {
+            ConstitutionError::InvariantViolation { expression, .. } => expression.as_str(),
+            other => panic!("unexpected error {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_enforce_law_all_reports_every_violation() {
+    let band = Band { low: 5, high: 150 };
+    let violations = band.enforce_law_all().unwrap_err();
+    assert_eq!(violations.len(), 2);
+    assert_eq!(
+        expressions(&violations),
+        ["self.low >= 10", "self.high < 100"]
+    );
+
+    // `enforce_law` still stops at the first violation
+    assert_eq!(band.enforce_law().unwrap_err(), violations[0]);
+}
+
+#[test]
+fn test_enforce_law_all_passes_valid_value() {
+    assert_eq!(Band { low: 10, high: 99 }.enforce_law_all(), Ok(()));
+    let violations = Band { low: 50, high: 20 }.enforce_law_all().unwrap_err();
+    assert_eq!(expressions(&violations), ["self.low <= self.high"]);
+}
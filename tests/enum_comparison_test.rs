@@ -0,0 +1,89 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+// Deliberately not `Copy`: comparisons only need `PartialEq`
+#[derive(Debug, PartialEq, Hash)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Light {
+    #[invariant(self.color != Color::Red)]
+    #[invariant(Color::Blue != self.color || self.level <= 10)]
+    color: Color,
+    level: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+enum Lamp {
+    Lit {
+        #[invariant(self.color == Color::Green)]
+        color: Color,
+    },
+    Off,
+}
+
+#[test]
+fn test_enum_comparison_runtime() {
+    let light = |color, level| Light { color, level };
+    assert!(light(Color::Green, 200).enforce_law().is_ok());
+    assert!(light(Color::Blue, 10).enforce_law().is_ok());
+    assert!(light(Color::Red, 0).enforce_law().is_err());
+    assert!(light(Color::Blue, 11).enforce_law().is_err());
+
+    assert!(
+        Lamp::Lit {
+            color: Color::Green
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(Lamp::Lit { color: Color::Blue }.enforce_law().is_err());
+    assert!(Lamp::Off.enforce_law().is_ok());
+}
+
+#[test]
+fn test_solver_sees_variant_indices() {
+    assert_eq!(
+        Light::invariant_expressions(),
+        &[
+            "self.color != self.Color__Red",
+            "self.Color__Blue != self.color || self.level <= 10",
+        ]
+    );
+    let index = |light: &Light, name: &str| match light.get_field_provider().get_field_value(name) {
+        Ok(FieldValue::UInt(index)) => index,
+        other => panic!("expected an index for {name}, got {other:?}"),
+    };
+    let light = Light {
+        color: Color::Blue,
+        level: 3,
+    };
+    assert_eq!(index(&light, "Color__Red"), 0);
+    assert_eq!(index(&light, "Color__Blue"), 1);
+    assert_eq!(index(&light, "color"), 1);
+    // A variant no invariant names reads as one past the named ones
+    let green = Light {
+        color: Color::Green,
+        level: 3,
+    };
+    assert_eq!(index(&green, "color"), 2);
+    assert!(light.verify_integrity_blocking().is_ok());
+
+    let lamp = Lamp::Lit {
+        color: Color::Green,
+    };
+    assert!(matches!(
+        lamp.get_field_provider().get_field_value("color"),
+        Ok(FieldValue::UInt(0))
+    ));
+    assert!(lamp.verify_integrity_blocking().is_ok());
+}
@@ -0,0 +1,63 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+enum Status {
+    Active = 1,
+    Closed = 2,
+    Archived = 3,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Account {
+    #[constitution(repr(u8))]
+    #[invariant((self.status as u8) < 3)]
+    status: Status,
+    #[invariant(self.balance >= 0)]
+    balance: i64,
+}
+
+fn account(status: Status) -> Account {
+    Account { status, balance: 0 }
+}
+
+#[test]
+fn test_discriminant_runtime() {
+    assert!(account(Status::Active).enforce_law().is_ok());
+    assert!(account(Status::Closed).enforce_law().is_ok());
+    match account(Status::Archived).enforce_law() {
+        Err(ConstitutionError::InvariantViolation { values, .. }) => assert!(values.is_empty()),
+        other => panic!("expected a violation, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_solver_sees_discriminant() {
+    assert_eq!(
+        Account::invariant_expressions(),
+        &["(self.status) < 3", "self.balance >= 0"]
+    );
+    let account = account(Status::Closed);
+    assert!(matches!(
+        account.get_field_provider().get_field_value("status"),
+        Ok(FieldValue::BitVec {
+            bits: 2,
+            width: 8,
+            ..
+        })
+    ));
+    assert!(account.verify_integrity_blocking().is_ok());
+}
+
+#[test]
+fn test_discriminant_is_hashed() {
+    assert_ne!(
+        account(Status::Active).compute_data_hash(),
+        account(Status::Closed).compute_data_hash()
+    );
+}
@@ -0,0 +1,99 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(enforce_all)]
+enum Shape {
+    Circle {
+        #[invariant(self.r > 0.0)]
+        r: f64,
+    },
+    Rect {
+        #[invariant(self.w > 0)]
+        w: u32,
+        #[invariant(self.h > 0 && self.h <= self.w)]
+        h: u32,
+    },
+    Point,
+}
+
+#[derive(Constitution)]
+enum Reading {
+    Celsius(#[invariant(self.0 >= 0)] i32),
+    Kelvin(#[invariant(self.0 >= 0)] i32),
+    Label {
+        #[invariant(self.name.len() > 0)]
+        name: String,
+    },
+}
+
+#[test]
+fn test_enum_checks_active_variant_only() {
+    assert!(Shape::Circle { r: 1.5 }.enforce_law().is_ok());
+    assert!(Shape::Circle { r: -1.0 }.enforce_law().is_err());
+    assert!(Shape::Rect { w: 4, h: 3 }.enforce_law().is_ok());
+    assert!(Shape::Rect { w: 3, h: 4 }.enforce_law().is_err());
+    assert!(Shape::Point.enforce_law().is_ok());
+}
+
+#[test]
+fn test_enum_violation_reports_variant_field_values() {
+    let empty = Shape::Rect { w: 0, h: 0 };
+    match empty.enforce_law() {
+        Err(ConstitutionError::InvariantViolation {
+            expression, values, ..
+        }) => {
+            assert_eq!(expression, "self.w > 0");
+            assert_eq!(values.get("w").map(String::as_str), Some("0"));
+        }
+        other => panic!("expected a violation, got {:?}", other),
+    }
+    assert_eq!(empty.enforce_law_all().unwrap_err().len(), 2);
+}
+
+#[test]
+fn test_enum_tuple_and_string_variants() {
+    assert!(Reading::Celsius(20).enforce_law().is_ok());
+    assert!(Reading::Kelvin(-1).enforce_law().is_err());
+    assert!(
+        Reading::Label {
+            name: "ambient".to_string()
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Reading::Label {
+            name: String::new()
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_enum_expressions_and_provider() {
+    assert_eq!(Shape::invariant_expressions().len(), 3);
+    let shape = Shape::Rect { w: 4, h: 3 };
+    let provider = shape.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("h"),
+        Ok(FieldValue::BitVec { bits: 3, .. })
+    ));
+    assert!(provider.get_field_value("r").is_err());
+}
+
+#[test]
+fn test_enum_hash_includes_discriminant() {
+    assert_ne!(
+        Reading::Celsius(5).compute_data_hash(),
+        Reading::Kelvin(5).compute_data_hash()
+    );
+    assert_eq!(
+        Reading::Celsius(5).compute_data_hash(),
+        Reading::Celsius(5).compute_data_hash()
+    );
+}
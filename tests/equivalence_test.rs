@@ -0,0 +1,57 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::{Constitution, assert_invariants_equivalent};
+
+#[derive(Constitution)]
+struct OldRange {
+    #[invariant(self.low >= 0)]
+    low: i32,
+    #[invariant(self.high <= 100)]
+    high: i32,
+}
+
+#[derive(Constitution)]
+struct NewRange {
+    #[invariant(self.high <= 100)]
+    high: i32,
+    #[invariant(self.low >= 0)]
+    low: i32,
+}
+
+#[test]
+fn test_equivalent_invariants() {
+    assert_invariants_equivalent!(OldRange, NewRange);
+    assert!(OldRange { low: 0, high: 1 }.enforce_law().is_ok());
+    assert!(NewRange { low: 0, high: 1 }.enforce_law().is_ok());
+}
+
+#[derive(Constitution)]
+struct EvenRange {
+    #[invariant(self.low >= 0)]
+    low: i32,
+    #[invariant(self.high <= 100)]
+    #[invariant(matches!(self.high % 2, 0))]
+    high: i32,
+}
+
+#[derive(Constitution)]
+struct SteppedRange {
+    #[invariant(self.low >= 0)]
+    low: i32,
+    #[invariant(self.high <= 100)]
+    high: i32,
+    #[invariant(self.step > 0)]
+    step: i32,
+}
+
+#[test]
+#[should_panic(expected = "runtime-only")]
+fn test_runtime_only_invariants_are_not_compared() {
+    assert_invariants_equivalent!(OldRange, EvenRange);
+}
+
+#[test]
+#[should_panic(expected = "constrain `step`, which `OldRange` has no field for")]
+fn test_unshared_fields_are_not_compared() {
+    assert_invariants_equivalent!(OldRange, SteppedRange);
+}
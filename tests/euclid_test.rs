@@ -0,0 +1,81 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+struct Heading {
+    #[invariant(self.angle.rem_euclid(360) == self.normalized_angle)]
+    angle: i32,
+    normalized_angle: i32,
+    #[invariant(self.minutes.div_euclid(60) == self.hours)]
+    minutes: i64,
+    hours: i64,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Bucket {
+    #[invariant(self.wide.rem_euclid(200) == self.slot)]
+    wide: u8,
+    slot: u8,
+    #[invariant(self.value.div_euclid(3) == self.third)]
+    value: i32,
+    third: i32,
+}
+
+fn heading(angle: i32, normalized_angle: i32) -> Heading {
+    Heading {
+        angle,
+        normalized_angle,
+        minutes: -90,
+        hours: -2,
+    }
+}
+
+#[test]
+fn test_euclid_runtime_on_signed_values() {
+    assert!(heading(370, 10).enforce_law().is_ok());
+    assert!(heading(-90, 270).enforce_law().is_ok());
+    // `%` would give -90 here; `rem_euclid` does not
+    assert!(heading(-90, -90).enforce_law().is_err());
+}
+
+#[test]
+fn test_euclid_lowered_for_solver() {
+    assert_eq!(
+        Heading::invariant_expressions(),
+        &[
+            "(((self.angle % 360) + 360) % 360) == self.normalized_angle",
+            "((self.minutes / 60) - (((((self.minutes % 60) + 60) % 60) - (self.minutes % 60)) / 60)) == self.hours",
+        ]
+    );
+}
+
+#[test]
+fn test_euclid_unsigned_and_signed_boundaries() {
+    assert_eq!(
+        Bucket::invariant_expressions(),
+        &[
+            "(self.wide % 200) == self.slot",
+            "((self.value / 3) - (((((self.value % 3) + 3) % 3) - (self.value % 3)) / 3)) == self.third",
+        ]
+    );
+
+    // `(x % n) + n` would wrap for a `u8` here, and `x - rem` for `i32::MIN`
+    let edge = Bucket {
+        wide: 199,
+        slot: 199,
+        value: i32::MIN,
+        third: i32::MIN.div_euclid(3),
+    };
+    assert!(edge.enforce_law().is_ok());
+    assert!(edge.verify_integrity_blocking().is_ok());
+
+    let wrong = Bucket {
+        third: i32::MIN / 3,
+        ..edge
+    };
+    assert!(wrong.enforce_law().is_err());
+    assert!(wrong.verify_integrity_blocking().is_err());
+}
@@ -0,0 +1,62 @@
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{ProofMode, ProveInvariant, SmtContext};
+
+#[derive(Constitution)]
+#[constitution(fallback_linear, blocking)]
+struct Grid {
+    #[invariant(self.rows * self.cols <= 4096)]
+    rows: u32,
+    cols: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(fallback_linear, timeout_ms = 200, partial, blocking, report, parallel)]
+struct Board {
+    #[constitution(nested)]
+    grid: Grid,
+    #[invariant(self.layers * self.layers <= 64)]
+    layers: u16,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+fn board() -> Board {
+    Board {
+        grid: Grid { rows: 64, cols: 64 },
+        layers: 8,
+    }
+}
+
+#[test]
+fn test_fast_proof_stays_exact() {
+    let grid = Grid { rows: 64, cols: 64 };
+    assert!(grid.enforce_law().is_ok());
+    let token = grid.verify_integrity_blocking().unwrap();
+    assert_eq!(token.mode(), ProofMode::Exact);
+    assert!(token.is_exact());
+}
+
+#[test]
+fn test_fallback_through_every_entry_point() {
+    let board = board();
+    assert!(board.enforce_law().is_ok());
+    assert!(board.verify_integrity_blocking().unwrap().is_exact());
+    assert!(board.verify_all_parallel().unwrap().is_exact());
+    assert!(board.verify_integrity_partial().is_verified());
+
+    let ctx = SmtContext::new().unwrap();
+    let report = block_on(board.verify_report(&ctx));
+    assert!(report.is_verified());
+    assert_eq!(Board::invariant_expressions().len(), 1);
+}
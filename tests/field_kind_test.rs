@@ -0,0 +1,101 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(int_semantics = "math", blocking)]
+struct Sensor {
+    #[invariant(self.gain > 0.0)]
+    gain: f64,
+    #[invariant(self.enabled || self.offset == 0)]
+    enabled: bool,
+    offset: i32,
+    #[invariant(self.channel < 16)]
+    channel: u8,
+    #[invariant(self.unit == 'C' || self.unit == 'F')]
+    unit: char,
+}
+
+fn sensor() -> Sensor {
+    Sensor {
+        gain: 1.5,
+        enabled: true,
+        offset: -3,
+        channel: 4,
+        unit: 'C',
+    }
+}
+
+#[test]
+fn test_every_kind_reaches_the_solver() {
+    let sensor = sensor();
+    let provider = sensor.get_field_provider();
+    assert_eq!(provider.get_field_value("gain"), Ok(FieldValue::Real(1.5)));
+    assert_eq!(
+        provider.get_field_value("enabled"),
+        Ok(FieldValue::Bool(true))
+    );
+    assert_eq!(provider.get_field_value("offset"), Ok(FieldValue::Int(-3)));
+    assert_eq!(provider.get_field_value("channel"), Ok(FieldValue::UInt(4)));
+    assert_eq!(provider.get_field_value("unit"), Ok(FieldValue::UInt(67)));
+}
+
+#[test]
+fn test_mixed_kinds_verify() {
+    assert!(sensor().enforce_law().is_ok());
+    assert!(sensor().verify_integrity_blocking().is_ok());
+
+    let disabled = Sensor {
+        enabled: false,
+        ..sensor()
+    };
+    assert!(disabled.enforce_law().is_err());
+    assert!(disabled.verify_integrity_blocking().is_err());
+}
+
+#[test]
+fn test_every_kind_is_hashed() {
+    let base = sensor().compute_data_hash();
+    assert_ne!(
+        Sensor {
+            gain: 2.0,
+            ..sensor()
+        }
+        .compute_data_hash(),
+        base
+    );
+    assert_ne!(
+        Sensor {
+            enabled: false,
+            ..sensor()
+        }
+        .compute_data_hash(),
+        base
+    );
+    assert_ne!(
+        Sensor {
+            offset: 3,
+            ..sensor()
+        }
+        .compute_data_hash(),
+        base
+    );
+    assert_ne!(
+        Sensor {
+            channel: 5,
+            ..sensor()
+        }
+        .compute_data_hash(),
+        base
+    );
+    assert_ne!(
+        Sensor {
+            unit: 'F',
+            ..sensor()
+        }
+        .compute_data_hash(),
+        base
+    );
+}
@@ -0,0 +1,41 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Hash)]
+struct Tag(u8);
+
+#[derive(Constitution)]
+#[constitution(field_report)]
+struct Order {
+    #[invariant(self.quantity > 0)]
+    #[invariant(self.quantity <= 100)]
+    quantity: u32,
+    #[invariant(self.tag.0 != 0)]
+    tag: Tag,
+    note: String,
+}
+
+#[test]
+fn test_field_report_groups_by_field() {
+    let order = Order {
+        quantity: 250,
+        tag: Tag(1),
+        note: String::new(),
+    };
+    assert!(order.enforce_law().is_err());
+    assert!(order.note.is_empty());
+
+    let report = order.field_report();
+    assert_eq!(report.len(), 2);
+
+    assert_eq!(report[0].name, "quantity");
+    assert_eq!(report[0].value.as_deref(), Some("250"));
+    assert_eq!(report[0].satisfied, ["self.quantity > 0"]);
+    assert_eq!(report[0].violated, ["self.quantity <= 100"]);
+
+    assert_eq!(report[1].name, "tag");
+    assert_eq!(report[1].value, None);
+    assert_eq!(report[1].satisfied, ["self.tag.0 != 0"]);
+    assert!(report[1].violated.is_empty());
+}
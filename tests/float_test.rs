@@ -0,0 +1,71 @@
+// Generated checks compare floats exactly and must not trip this lint
+#![warn(clippy::float_cmp)]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+struct Mix {
+    #[invariant(self.ratio > 0.0)]
+    ratio: f64,
+    #[invariant(self.scale == 1.0 || self.scale == 2.0)]
+    scale: f32,
+}
+
+#[test]
+fn test_float_fields_runtime() {
+    assert!(
+        Mix {
+            ratio: 0.5,
+            scale: 2.0
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Mix {
+            ratio: 0.0,
+            scale: 1.0
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(
+        Mix {
+            ratio: 0.5,
+            scale: 1.5
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_float_fields_exposed_as_reals() {
+    let mix = Mix {
+        ratio: 0.25,
+        scale: 1.0,
+    };
+    let provider = mix.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("ratio"),
+        Ok(FieldValue::Real(0.25))
+    );
+    assert_eq!(provider.get_field_value("scale"), Ok(FieldValue::Real(1.0)));
+}
+
+#[test]
+fn test_float_fields_hashed() {
+    let a = Mix {
+        ratio: 0.25,
+        scale: 1.0,
+    };
+    let b = Mix {
+        ratio: 0.5,
+        scale: 1.0,
+    };
+    assert_ne!(a.compute_data_hash(), b.compute_data_hash());
+}
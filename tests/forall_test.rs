@@ -0,0 +1,71 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Samples {
+    #[invariant(forall i in 0..4 => self.buf[i] >= 0)]
+    buf: [i32; 4],
+    #[invariant(
+        expr = "forall i in 0..=1 => self.pair[i] <= self.limit",
+        name = "pair cap"
+    )]
+    pair: [u8; 2],
+    limit: u8,
+}
+
+#[derive(Constitution)]
+struct Ladder {
+    #[invariant((0..2).all(|i| self.rungs[i] < self.rungs[i + 1]))]
+    rungs: [u32; 3],
+}
+
+fn samples(buf: [i32; 4], pair: [u8; 2]) -> Samples {
+    Samples {
+        buf,
+        pair,
+        limit: 10,
+    }
+}
+
+#[test]
+fn test_forall_checked_at_runtime() {
+    assert!(samples([0, 1, 2, 3], [10, 0]).enforce_law().is_ok());
+    assert!(samples([0, 1, -2, 3], [10, 0]).enforce_law().is_err());
+    match samples([0, 1, 2, 3], [1, 11]).enforce_law() {
+        Err(ConstitutionError::InvariantViolation {
+            name, expression, ..
+        }) => {
+            assert_eq!(name.as_deref(), Some("pair cap"));
+            assert_eq!(
+                expression,
+                "forall i in 0..=1 => self.pair[i] <= self.limit"
+            );
+        }
+        other => panic!("expected a violation, got {:?}", other),
+    }
+    assert!(Ladder { rungs: [1, 2, 3] }.enforce_law().is_ok());
+    assert!(Ladder { rungs: [1, 3, 3] }.enforce_law().is_err());
+}
+
+#[test]
+fn test_forall_unrolled_for_solver() {
+    assert_eq!(
+        Samples::invariant_expressions(),
+        &[
+            "((self.buf__0 >= 0) && (self.buf__1 >= 0) && (self.buf__2 >= 0) && (self.buf__3 >= 0))",
+            "((self.pair__0 <= self.limit) && (self.pair__1 <= self.limit))",
+        ]
+    );
+    assert_eq!(
+        Ladder::invariant_expressions(),
+        &["((self.rungs__0 < self.rungs__1) && (self.rungs__1 < self.rungs__2))"]
+    );
+    assert!(
+        samples([0, 1, 2, 3], [1, 2])
+            .verify_integrity_blocking()
+            .is_ok()
+    );
+}
@@ -0,0 +1,65 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Stat<T: Into<i64> + Copy> {
+    #[invariant(self.value > 0)]
+    value: T,
+    #[invariant(self.count > 0)]
+    count: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(smt_script)]
+struct Reading<T>
+where
+    T: core::convert::Into<i64> + Copy + Send + Sync,
+{
+    #[invariant(self.low <= self.high)]
+    low: T,
+    high: T,
+}
+
+#[test]
+fn test_generic_field_checked_at_runtime() {
+    assert!(
+        Stat {
+            value: 3u8,
+            count: 1
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Stat {
+            value: 0i32,
+            count: 1
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(Reading { low: 1i16, high: 2 }.enforce_law().is_ok());
+    assert!(Reading { low: 3i16, high: 2 }.enforce_law().is_err());
+}
+
+#[test]
+fn test_generic_field_read_as_i64() {
+    let script = Reading {
+        low: -4i32,
+        high: 9,
+    }
+    .smt_script()
+    .unwrap();
+    assert!(script.contains("(assert (= low (- 4)))"));
+    assert!(script.contains("(assert (= high 9))"));
+    assert!(
+        Stat {
+            value: 7u16,
+            count: 1
+        }
+        .verify_integrity_blocking()
+        .is_ok()
+    );
+}
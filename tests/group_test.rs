@@ -0,0 +1,50 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Session {
+    #[invariant(self.token_age < 3600, group = "security")]
+    #[invariant(self.token_age >= 0)]
+    token_age: i64,
+    #[constitution(group = "consistency")]
+    #[invariant(self.seq > 0)]
+    #[invariant(self.seq <= self.acked, group = "security")]
+    seq: u64,
+    acked: u64,
+}
+
+#[test]
+fn test_groups_listed_in_declaration_order() {
+    assert_eq!(Session::groups(), &["security", "consistency"]);
+}
+
+#[test]
+fn test_enforce_group_runs_only_that_group() {
+    let stale = Session {
+        token_age: 7200,
+        seq: 1,
+        acked: 1,
+    };
+    assert!(stale.enforce_group("security").is_err());
+    assert!(stale.enforce_group("consistency").is_ok());
+    assert!(stale.enforce_law().is_err());
+
+    let unsynced = Session {
+        token_age: 10,
+        seq: 0,
+        acked: 0,
+    };
+    assert!(unsynced.enforce_group("security").is_ok());
+    assert!(unsynced.enforce_group("consistency").is_err());
+
+    // Ungrouped invariants (and unknown groups) aren't run by enforce_group
+    let negative = Session {
+        token_age: -1,
+        seq: 1,
+        acked: 1,
+    };
+    assert!(negative.enforce_group("security").is_ok());
+    assert!(negative.enforce_group("performance").is_ok());
+    assert!(negative.enforce_law().is_err());
+}
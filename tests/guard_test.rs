@@ -0,0 +1,83 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(guard)]
+struct Account {
+    #[invariant(self.balance >= 0)]
+    balance: i64,
+}
+
+#[derive(Constitution, Debug)]
+#[constitution(guard)]
+enum Gauge {
+    Level {
+        #[invariant(self.level <= 10)]
+        level: u8,
+    },
+    Off,
+}
+
+#[test]
+fn test_guard_allows_valid_edit() {
+    let mut account = Account { balance: 5 };
+    {
+        let mut guard = account.guard();
+        guard.balance -= 5;
+        assert_eq!(guard.balance, 0);
+    }
+    assert_eq!(account.balance, 0);
+}
+
+#[test]
+#[should_panic(expected = "invariant broken during mutation")]
+fn test_guard_panics_on_broken_invariant() {
+    let mut account = Account { balance: 5 };
+    let mut guard = account.guard();
+    guard.balance -= 6;
+}
+
+#[test]
+fn test_guard_commit_returns_violation() {
+    let mut account = Account { balance: 5 };
+    let mut guard = account.guard();
+    guard.balance = -1;
+    assert!(matches!(
+        guard.commit(),
+        Err(ConstitutionError::InvariantViolation { .. })
+    ));
+    assert_eq!(account.balance, -1);
+}
+
+#[test]
+fn test_try_guard() {
+    let mut account = Account { balance: 5 };
+    assert_eq!(account.try_guard(|a| a.balance + 1).unwrap(), 6);
+    assert!(account.try_guard(|a| a.balance = -3).is_err());
+    assert!(account.enforce_law().is_err());
+}
+
+#[test]
+fn test_guard_does_not_double_panic() {
+    let outcome = std::panic::catch_unwind(|| {
+        let mut account = Account { balance: 5 };
+        let mut guard = account.guard();
+        guard.balance = -1;
+        panic!("edit failed");
+    });
+    let payload = outcome.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"edit failed"));
+}
+
+#[test]
+fn test_enum_guard() {
+    let mut gauge = Gauge::Level { level: 3 };
+    let result = gauge.try_guard(|g| {
+        if let Gauge::Level { level } = g {
+            *level = 11;
+        }
+    });
+    assert!(result.is_err());
+    assert!(Gauge::Off.try_guard(|_| ()).is_ok());
+}
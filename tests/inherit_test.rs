@@ -0,0 +1,79 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::{Constitution, constitution_fragment};
+use praborrow_prover::ProveInvariant;
+
+#[constitution_fragment(invariant(self.created <= self.updated))]
+trait Timestamped {}
+
+mod audit {
+    use praborrow_defense::constitution_fragment;
+
+    #[constitution_fragment(invariant(self.revision > 0), invariant("self.revision < 1000"))]
+    pub trait Revisioned {}
+}
+
+#[derive(Constitution)]
+#[constitution(inherit = Timestamped)]
+struct Post {
+    #[invariant(self.likes <= 1000000)]
+    likes: u32,
+    created: u64,
+    updated: u64,
+}
+
+#[derive(Constitution)]
+#[constitution(inherit = Timestamped, inherit = audit::Revisioned, contract)]
+struct Document {
+    created: u64,
+    updated: u64,
+    revision: u32,
+}
+
+#[test]
+fn test_inherited_invariants_are_enforced() {
+    let post = Post {
+        likes: 3,
+        created: 10,
+        updated: 20,
+    };
+    assert!(post.enforce_law().is_ok());
+
+    let backdated = Post {
+        likes: 3,
+        created: 20,
+        updated: 10,
+    };
+    assert!(backdated.enforce_law().is_err());
+}
+
+#[test]
+fn test_multiple_fragments_fold_in_order() {
+    assert_eq!(
+        Document::invariant_expressions(),
+        &[
+            "self.created <= self.updated",
+            "self.revision > 0",
+            "self.revision < 1000",
+        ]
+    );
+
+    let doc = Document {
+        created: 1,
+        updated: 1,
+        revision: 0,
+    };
+    assert!(doc.enforce_law().is_err());
+
+    let contract = Document::contract();
+    assert_eq!(contract.invariants.len(), 3);
+    assert_eq!(contract.invariants[0].field, None);
+}
+
+fn assert_timestamped<T: Timestamped>() {}
+
+#[test]
+fn test_marker_fragments_are_implemented() {
+    assert_timestamped::<Post>();
+    assert_timestamped::<Document>();
+}
@@ -0,0 +1,36 @@
+#![allow(deprecated)]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Motion {
+    #[invariant(self.delta.abs() < 10)]
+    delta: i32,
+    #[invariant(self.step.pow(2) <= 100)]
+    step: u32,
+    #[invariant(self.low.max(self.high) == self.high)]
+    low: i64,
+    high: i64,
+    #[invariant(self.trend.signum() >= 0)]
+    trend: i8,
+}
+
+fn motion(delta: i32, step: u32, low: i64) -> Motion {
+    Motion {
+        delta,
+        step,
+        low,
+        high: 5,
+        trend: 1,
+    }
+}
+
+#[test]
+fn test_int_methods_checked_at_runtime() {
+    assert!(motion(-9, 10, 5).enforce_law().is_ok());
+    assert!(motion(-10, 0, 0).enforce_law().is_err());
+    assert!(motion(0, 11, 0).enforce_law().is_err());
+    assert!(motion(0, 0, 6).enforce_law().is_err());
+}
@@ -0,0 +1,32 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(introspect)]
+#[invariant(self.low <= self.high)]
+struct Range {
+    #[invariant(self.low >= 0)]
+    low: i32,
+    #[invariant(self.high < 100)]
+    high: i32,
+}
+
+#[test]
+fn test_describe_reports_every_invariant() {
+    let range = Range { low: -1, high: 200 };
+    assert_eq!(
+        range.describe_invariants(),
+        [
+            ("self.low >= 0".to_string(), false),
+            ("self.high < 100".to_string(), false),
+            ("self.low <= self.high".to_string(), true),
+        ]
+    );
+}
+
+#[test]
+fn test_describe_lawful_value() {
+    let range = Range { low: 1, high: 5 };
+    assert!(range.describe_invariants().iter().all(|(_, holds)| *holds));
+}
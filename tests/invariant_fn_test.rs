@@ -0,0 +1,71 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug, PartialEq)]
+#[constitution(invariant_fn)]
+struct Reading {
+    #[invariant(self.value >= self.min)]
+    #[invariant(self.value <= 100)]
+    value: i32,
+    min: i32,
+}
+
+#[derive(Constitution)]
+#[constitution(invariant_fn)]
+struct Plain {
+    value: i32,
+}
+
+#[derive(Constitution)]
+#[constitution(invariant_fn)]
+struct Station {
+    #[constitution(nested)]
+    latest: Reading,
+    #[invariant(self.id > 0)]
+    id: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(invariant_fn)]
+enum Signal {
+    Level {
+        #[invariant(self.db <= 0)]
+        db: i8,
+    },
+    Muted,
+}
+
+fn reading(value: i32) -> Reading {
+    Reading { value, min: 0 }
+}
+
+#[test]
+fn test_invariant_fn_filters() {
+    let readings = [reading(5), reading(-1), reading(101), reading(100)];
+    let valid: Vec<_> = readings.iter().filter(|r| r.invariant_fn()).collect();
+    assert_eq!(valid, [&reading(5), &reading(100)]);
+    for r in &readings {
+        assert_eq!(r.invariant_fn(), r.enforce_law().is_ok());
+    }
+}
+
+#[test]
+fn test_invariant_fn_without_invariants_is_true() {
+    assert!(Plain { value: -7 }.invariant_fn());
+    assert!(Signal::Muted.invariant_fn());
+}
+
+#[test]
+fn test_invariant_fn_includes_nested_and_variants() {
+    let station = |value, id| Station {
+        latest: reading(value),
+        id,
+    };
+    assert!(station(5, 1).invariant_fn());
+    assert!(!station(-5, 1).invariant_fn());
+    assert!(!station(5, 0).invariant_fn());
+
+    assert!(Signal::Level { db: -3 }.invariant_fn());
+    assert!(!Signal::Level { db: 3 }.invariant_fn());
+}
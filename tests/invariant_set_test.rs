@@ -0,0 +1,72 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::{Constitution, define_invariants};
+use praborrow_prover::ProveInvariant;
+
+define_invariants!(
+    PositiveId = ["self.id > 0"],
+    Versioned = [self.version >= 1, "self.version < 100"],
+);
+
+mod catalog {
+    use praborrow_defense::define_invariants;
+
+    define_invariants!(Priced = ["self.price <= 10000"]);
+}
+
+#[derive(Constitution)]
+#[constitution(use = "PositiveId")]
+struct User {
+    id: u64,
+    #[invariant(self.age <= 150)]
+    age: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(use = "PositiveId", use = "Versioned", use = "catalog::Priced")]
+struct Product {
+    id: u64,
+    version: u32,
+    price: u32,
+}
+
+#[test]
+fn test_set_is_enforced() {
+    assert!(User { id: 1, age: 30 }.enforce_law().is_ok());
+    assert!(User { id: 0, age: 30 }.enforce_law().is_err());
+    assert!(User { id: 1, age: 200 }.enforce_law().is_err());
+}
+
+#[test]
+fn test_sets_follow_own_invariants_in_order() {
+    assert_eq!(
+        User::invariant_expressions(),
+        &["self.age <= 150", "self.id > 0"]
+    );
+    assert_eq!(
+        Product::invariant_expressions(),
+        &[
+            "self.id > 0",
+            "self.version >= 1",
+            "self.version < 100",
+            "self.price <= 10000",
+        ]
+    );
+}
+
+#[test]
+fn test_sets_shared_across_types() {
+    let product = Product {
+        id: 7,
+        version: 100,
+        price: 5,
+    };
+    assert!(product.enforce_law().is_err());
+
+    let product = Product {
+        id: 7,
+        version: 3,
+        price: 5,
+    };
+    assert!(product.enforce_law().is_ok());
+}
@@ -0,0 +1,41 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+struct Pair {
+    #[invariant(self.low >= 1)]
+    low: u32,
+    #[invariant(self.high > self.low)]
+    high: u32,
+}
+
+#[derive(Constitution)]
+struct Tagged<T> {
+    #[invariant(self.id > 0)]
+    id: u64,
+    #[constitution(skip_hash)]
+    tag: T,
+}
+
+// Usable wherever a constant is
+const PAIR_INVARIANTS: usize = Pair::INVARIANTS.len();
+
+#[test]
+fn test_invariants_const_lists_expressions() {
+    assert_eq!(PAIR_INVARIANTS, 2);
+    assert_eq!(Pair::INVARIANTS, &["self.low >= 1", "self.high > self.low"]);
+    assert_eq!(Pair::INVARIANTS, Pair::invariant_expressions());
+}
+
+#[test]
+fn test_invariants_const_on_generic_type() {
+    assert_eq!(Tagged::<String>::INVARIANTS, &["self.id > 0"]);
+    let tagged = Tagged {
+        id: 1,
+        tag: "a".to_string(),
+    };
+    assert!(tagged.enforce_law().is_ok());
+    assert_eq!(tagged.tag, "a");
+}
@@ -0,0 +1,35 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Range {
+    #[invariant(self.start <= self.end)]
+    start: u32,
+    end: u32,
+}
+
+#[derive(Constitution)]
+enum Reading {
+    Celsius {
+        #[invariant(self.degrees >= -273)]
+        degrees: i32,
+    },
+    Missing,
+}
+
+#[test]
+fn test_is_valid_matches_enforce_law() {
+    let valid = Range { start: 1, end: 2 };
+    let invalid = Range { start: 3, end: 2 };
+    assert!(valid.is_valid());
+    assert!(!invalid.is_valid());
+    assert_eq!(invalid.is_valid(), invalid.enforce_law().is_ok());
+}
+
+#[test]
+fn test_is_valid_on_enum() {
+    assert!(Reading::Celsius { degrees: 20 }.is_valid());
+    assert!(!Reading::Celsius { degrees: -300 }.is_valid());
+    assert!(Reading::Missing.is_valid());
+}
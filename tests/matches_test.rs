@@ -0,0 +1,65 @@
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{InvariantStatus, ProofError, ProveInvariant, SmtContext};
+
+#[derive(Debug, Clone, Copy, PartialEq, Hash)]
+enum State {
+    Idle,
+    Running,
+    Paused,
+}
+
+#[derive(Constitution)]
+#[constitution(strict_parse, blocking, report)]
+struct Job {
+    #[invariant(matches!(self.state, State::Running | State::Paused))]
+    state: State,
+    #[invariant(self.retries <= 3)]
+    retries: u8,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+fn job(state: State) -> Job {
+    Job { state, retries: 0 }
+}
+
+#[test]
+fn test_matches_enforced_at_runtime() {
+    assert!(job(State::Running).enforce_law().is_ok());
+    assert!(job(State::Paused).enforce_law().is_ok());
+    let err = job(State::Idle).enforce_law().unwrap_err();
+    assert!(err.to_string().contains("matches!"));
+}
+
+#[test]
+fn test_matches_not_handed_to_solver() {
+    assert_eq!(Job::invariant_expressions(), &["self.retries <= 3"]);
+    assert!(job(State::Running).verify_integrity_blocking().is_ok());
+}
+
+#[test]
+fn test_matches_reported_unsupported() {
+    let ctx = SmtContext::new().unwrap();
+    let job = job(State::Running);
+    let report = block_on(job.verify_report(&ctx));
+    let status = &report
+        .iter()
+        .find(|result| result.expression.contains("matches!"))
+        .unwrap()
+        .status;
+    assert!(matches!(
+        status,
+        InvariantStatus::Error(ProofError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,92 @@
+// `Order`'s counting invariant and `Batch`'s element check are runtime-only by design
+#![allow(deprecated)]
+
+extern crate alloc;
+use core::cell::Cell;
+use praborrow_core::{CheckProtocol, ConstitutionError, LawCache};
+use praborrow_defense::Constitution;
+
+thread_local! {
+    // Per thread, so tests running in parallel don't see each other's checks
+    static CHECKS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn checks() -> usize {
+    CHECKS.with(Cell::get)
+}
+
+#[derive(Constitution)]
+#[constitution(memoize, constructor, builder)]
+struct Order {
+    #[invariant(self.qty > 0)]
+    #[invariant(self.counted())]
+    qty: u32,
+    cache: LawCache,
+}
+
+impl Order {
+    fn counted(&self) -> bool {
+        CHECKS.with(|checks| checks.set(checks.get() + 1));
+        true
+    }
+}
+
+#[derive(Constitution)]
+#[constitution(memoize)]
+struct Batch {
+    #[invariant(self.items.iter().all(|item| *item > 0))]
+    items: Vec<u32>,
+    cache: LawCache,
+}
+
+#[test]
+fn test_memoized_until_hash_changes() {
+    let mut order = Order::try_new(3).unwrap();
+    let before = checks();
+    assert!(order.enforce_law().is_ok());
+    assert!(order.enforce_law().is_ok());
+    assert_eq!(checks(), before);
+
+    order.qty = 4;
+    assert!(order.enforce_law().is_ok());
+    assert_eq!(checks(), before + 1);
+
+    order.cache.invalidate();
+    assert!(order.enforce_law().is_ok());
+    assert_eq!(checks(), before + 2);
+}
+
+#[test]
+fn test_violation_is_cached_too() {
+    let order = Order {
+        qty: 0,
+        cache: LawCache::new(),
+    };
+    let first = order.enforce_law();
+    assert!(matches!(
+        first,
+        Err(ConstitutionError::InvariantViolation { .. })
+    ));
+    assert_eq!(order.enforce_law(), first);
+}
+
+#[test]
+fn test_builder_starts_with_empty_cache() {
+    assert!(Order::builder().qty(2).build().is_ok());
+    assert!(Order::builder().qty(0).build().is_err());
+}
+
+#[test]
+fn test_element_change_invalidates_result() {
+    let mut batch = Batch {
+        items: vec![1, 2],
+        cache: LawCache::new(),
+    };
+    assert!(batch.enforce_law().is_ok());
+
+    batch.items[0] = 0;
+    assert!(matches!(
+        batch.enforce_law(),
+        Err(ConstitutionError::InvariantViolation { .. })
+    ));
+}
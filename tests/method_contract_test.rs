@@ -0,0 +1,89 @@
+extern crate alloc;
+use praborrow_core::ConstitutionError;
+use praborrow_defense::contract;
+
+#[contract]
+#[requires("x > 0")]
+#[ensures("result >= x")]
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+#[contract]
+#[requires(divisor != 0)]
+#[ensures(result <= dividend)]
+fn divide(dividend: i64, divisor: i64) -> i64 {
+    if divisor == 1 {
+        return dividend;
+    }
+    dividend / divisor
+}
+
+#[contract]
+#[ensures("result < 10")]
+fn off_by_one(x: u8) -> u8 {
+    x + 1
+}
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    #[contract]
+    #[requires(self.count < 3)]
+    #[ensures(self.count <= 3)]
+    fn bump(&mut self) {
+        self.count += 1;
+    }
+}
+
+#[test]
+fn test_contract_passes_result_through() {
+    assert_eq!(double(4), Ok(8));
+    assert_eq!(divide(9, 3), Ok(3));
+    assert_eq!(divide(9, 1), Ok(9));
+}
+
+#[test]
+fn test_precondition_violation() {
+    match double(0) {
+        Err(ConstitutionError::PreconditionViolation {
+            function,
+            expression,
+        }) => {
+            assert_eq!(function, "double");
+            assert_eq!(expression, "x > 0");
+        }
+        other => panic!("expected a precondition violation, got {:?}", other),
+    }
+    assert!(divide(1, 0).is_err());
+}
+
+#[test]
+fn test_postcondition_checks_early_returns_too() {
+    assert_eq!(off_by_one(8), Ok(9));
+    assert_eq!(
+        off_by_one(9),
+        Err(ConstitutionError::PostconditionViolation {
+            function: "off_by_one".to_string(),
+            expression: "result < 10".to_string(),
+        })
+    );
+    // Negative dividends end up above the quotient's bound
+    assert!(divide(-9, 1).is_ok());
+    assert!(matches!(
+        divide(-9, 3),
+        Err(ConstitutionError::PostconditionViolation { .. })
+    ));
+}
+
+#[test]
+fn test_contract_on_method() {
+    let mut counter = Counter { count: 0 };
+    for _ in 0..3 {
+        assert!(counter.bump().is_ok());
+    }
+    assert!(counter.bump().is_err());
+    assert_eq!(counter.count, 3);
+}
@@ -0,0 +1,77 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Quota {
+    #[invariant((self.used as u32) < self.limit)]
+    used: u8,
+    #[invariant(u64::from(self.limit) <= self.total)]
+    limit: u32,
+    #[invariant(i32::from(self.delta) < 100)]
+    delta: i8,
+    total: u64,
+}
+
+#[derive(Constitution)]
+struct Cursor {
+    #[invariant((self.head as usize) <= self.len)]
+    head: u16,
+    #[invariant((self.tail as usize) <= self.len)]
+    tail: u32,
+    len: usize,
+}
+
+fn quota(used: u8, limit: u32) -> Quota {
+    Quota {
+        used,
+        limit,
+        delta: 1,
+        total: 1000,
+    }
+}
+
+#[test]
+fn test_mixed_width_runtime() {
+    assert!(quota(3, 4).enforce_law().is_ok());
+    assert!(quota(4, 4).enforce_law().is_err());
+    assert!(quota(3, 1001).enforce_law().is_err());
+}
+
+#[test]
+fn test_widening_conversions_compare_by_value() {
+    assert_eq!(
+        Quota::invariant_expressions(),
+        &[
+            "(self.used) < self.limit",
+            "self.limit <= self.total",
+            "self.delta < 100",
+        ]
+    );
+    let q = quota(3, 4);
+    let provider = q.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("used"),
+        Ok(FieldValue::BitVec { width: 8, .. })
+    ));
+    assert!(matches!(
+        provider.get_field_value("limit"),
+        Ok(FieldValue::BitVec { width: 32, .. })
+    ));
+    assert!(q.verify_integrity_blocking().is_ok());
+}
+
+#[test]
+fn test_pointer_sized_widening_holds_on_every_target() {
+    // `u32` only fits `usize` on targets of at least 32 bits
+    assert_eq!(
+        Cursor::invariant_expressions(),
+        &[
+            "(self.head) <= self.len",
+            "(self.tail as usize) <= self.len"
+        ]
+    );
+}
@@ -0,0 +1,77 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(contract)]
+#[invariant(self.balance <= self.limit, name = "within limit")]
+struct Account {
+    #[invariant(name = "positive balance", expr = "self.balance >= 0")]
+    balance: i64,
+    #[invariant(self.limit > 0)]
+    limit: i64,
+}
+
+#[test]
+fn test_named_invariant_in_error() {
+    let error = Account {
+        balance: -1,
+        limit: 10,
+    }
+    .enforce_law()
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("invariant 'positive balance' breached"),
+        "{error}"
+    );
+
+    let error = Account {
+        balance: 11,
+        limit: 10,
+    }
+    .enforce_law()
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("invariant 'within limit' breached"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_unnamed_invariant_message_unchanged() {
+    let error = Account {
+        balance: 0,
+        limit: 0,
+    }
+    .enforce_law()
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Invariant violated: self.limit > 0"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_invariant_labels_parallel_expressions() {
+    assert_eq!(
+        Account::invariant_expressions(),
+        &[
+            "self.balance >= 0",
+            "self.limit > 0",
+            "self.balance <= self.limit"
+        ]
+    );
+    assert_eq!(
+        Account::invariant_labels(),
+        &[Some("positive balance"), None, Some("within limit")]
+    );
+    let contract = Account::contract();
+    assert_eq!(contract.invariants[0].name, Some("positive balance"));
+}
@@ -0,0 +1,57 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::parser::ExpressionParser;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Reading {
+    #[invariant(self.temp >= -273)]
+    temp: i32,
+    #[invariant(-self.offset <= 100 && self.offset < 100)]
+    offset: i16,
+    #[invariant(self.lat >= -90.0 && self.lat <= 90.0)]
+    lat: f64,
+    #[invariant(self.step * -2 < 10)]
+    step: i64,
+}
+
+#[derive(Constitution)]
+struct Window {
+    #[invariant("self.low >= -10 && self.low -1 < self.high")]
+    low: i8,
+    high: i8,
+}
+
+fn reading(temp: i32, offset: i16) -> Reading {
+    Reading {
+        temp,
+        offset,
+        lat: -6.2,
+        step: 0,
+    }
+}
+
+#[test]
+fn test_negative_bounds_enforced() {
+    assert!(reading(-273, 0).enforce_law().is_ok());
+    assert!(reading(-274, 0).enforce_law().is_err());
+    assert!(reading(20, -100).enforce_law().is_ok());
+    assert!(reading(20, -101).enforce_law().is_err());
+    assert!(Window { low: -10, high: 0 }.enforce_law().is_ok());
+    assert!(Window { low: -11, high: 0 }.enforce_law().is_err());
+}
+
+#[test]
+fn test_negative_bounds_reach_the_solver() {
+    let expressions = Reading::invariant_expressions();
+    assert_eq!(expressions.len(), 4);
+    for expression in expressions.iter().chain(Window::invariant_expressions()) {
+        assert!(
+            ExpressionParser::parse(expression).is_ok(),
+            "{expression} doesn't parse"
+        );
+    }
+    assert!(reading(0, 0).verify_integrity_blocking().is_ok());
+}
@@ -0,0 +1,86 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+struct Inner {
+    #[invariant(self.x > 0)]
+    x: i32,
+}
+
+#[derive(Constitution)]
+struct Middle {
+    #[constitution(nested)]
+    inner: Inner,
+    #[invariant(self.scale >= 1)]
+    scale: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(enforce_all)]
+#[invariant(self.count <= 10)]
+struct Outer {
+    #[constitution(nested)]
+    middle: Middle,
+    count: u32,
+}
+
+fn outer(x: i32, scale: u8, count: u32) -> Outer {
+    Outer {
+        middle: Middle {
+            inner: Inner { x },
+            scale,
+        },
+        count,
+    }
+}
+
+#[test]
+fn test_nested_fields_checked_at_runtime() {
+    assert!(outer(1, 1, 10).enforce_law().is_ok());
+    assert!(outer(0, 1, 10).enforce_law().is_err());
+    assert!(outer(1, 0, 10).enforce_law().is_err());
+    assert!(outer(1, 1, 11).enforce_law().is_err());
+}
+
+#[test]
+fn test_nested_error_names_the_field_path() {
+    let error = outer(0, 1, 10).enforce_law().unwrap_err();
+    match &error {
+        ConstitutionError::NestedViolation { field, source } => {
+            assert_eq!(field, "middle");
+            assert!(matches!(
+                &**source,
+                ConstitutionError::NestedViolation { field, .. } if field == "inner"
+            ));
+        }
+        other => panic!("expected a nested violation, got {:?}", other),
+    }
+    assert_eq!(
+        error.to_string(),
+        "in field 'middle': in field 'inner': Invariant violated: self.x > 0. Values: {\"x\": \"0\"}"
+    );
+}
+
+#[test]
+fn test_nested_fields_run_before_own_invariants() {
+    let errors = outer(0, 1, 11).enforce_law_all().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        ConstitutionError::NestedViolation { .. }
+    ));
+    assert!(matches!(
+        errors[1],
+        ConstitutionError::InvariantViolation { .. }
+    ));
+}
+
+#[test]
+fn test_nested_fields_hashed() {
+    assert_ne!(
+        outer(1, 1, 5).compute_data_hash(),
+        outer(2, 1, 5).compute_data_hash()
+    );
+}
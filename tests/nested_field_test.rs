@@ -0,0 +1,93 @@
+#![allow(deprecated)]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Hash)]
+pub struct Limits {
+    pub max: u32,
+    pub min: i64,
+}
+
+#[derive(Constitution)]
+struct Config {
+    #[invariant(self.limits.max > 0 && self.limits.min <= 10)]
+    limits: Limits,
+    #[invariant(self.retries <= 5)]
+    retries: u8,
+}
+
+#[derive(Hash)]
+pub struct Naming {
+    pub name: String,
+}
+
+#[derive(Hash)]
+pub struct Tag(pub String);
+
+// Leaves that aren't `Copy` are only read at runtime, so they get no provider arm
+#[derive(Constitution)]
+struct Service {
+    #[invariant(self.naming.name.len() > 0)]
+    naming: Naming,
+    #[invariant(!self.tag.0.is_empty())]
+    tag: Tag,
+}
+
+fn config(max: u32, min: i64) -> Config {
+    Config {
+        limits: Limits { max, min },
+        retries: 3,
+    }
+}
+
+#[test]
+fn test_nested_field_runtime() {
+    assert!(config(1, 10).enforce_law().is_ok());
+    assert!(config(0, 10).enforce_law().is_err());
+    assert!(config(1, 11).enforce_law().is_err());
+}
+
+#[test]
+fn test_nested_field_expression_for_solver() {
+    assert_eq!(
+        Config::invariant_expressions(),
+        &[
+            "self.limits.max > 0 && self.limits.min <= 10",
+            "self.retries <= 5"
+        ]
+    );
+}
+
+#[test]
+fn test_nested_field_provider_uses_dotted_path() {
+    let value = config(7, -2);
+    let provider = value.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("limits.max"),
+        Ok(FieldValue::UInt(7))
+    ));
+    assert!(matches!(
+        provider.get_field_value("limits.min"),
+        Ok(FieldValue::Int(-2))
+    ));
+    assert!(provider.get_field_value("limits").is_err());
+}
+
+#[test]
+fn test_runtime_only_nested_leaf_not_exposed() {
+    let service = Service {
+        naming: Naming {
+            name: "api".to_string(),
+        },
+        tag: Tag("v1".to_string()),
+    };
+    assert!(service.enforce_law().is_ok());
+    assert!(Service::invariant_expressions().is_empty());
+    let provider = service.get_field_provider();
+    assert!(provider.get_field_value("naming.name").is_err());
+    assert!(provider.get_field_value("tag__0").is_err());
+}
@@ -0,0 +1,63 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probability(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibrated(Probability);
+
+#[derive(Constitution)]
+struct Forecast {
+    #[invariant(self.p.0 >= 0.0 && self.p.0 <= 1.0)]
+    #[constitution(skip_hash)]
+    p: Probability,
+    #[invariant(self.confidence.0.0 > 0.5)]
+    #[constitution(skip_hash)]
+    confidence: Calibrated,
+    #[invariant(self.weight <= 2.5)]
+    weight: f32,
+}
+
+fn forecast(p: f64) -> Forecast {
+    Forecast {
+        p: Probability(p),
+        confidence: Calibrated(Probability(0.9)),
+        weight: 1.0,
+    }
+}
+
+#[test]
+fn test_newtype_float_runtime() {
+    assert!(forecast(0.0).enforce_law().is_ok());
+    assert!(forecast(1.0).enforce_law().is_ok());
+    assert!(forecast(-0.1).enforce_law().is_err());
+    assert!(forecast(1.5).enforce_law().is_err());
+}
+
+#[test]
+fn test_newtype_float_exposed_as_reals() {
+    assert_eq!(
+        Forecast::invariant_expressions(),
+        &[
+            "self.p__0 >= 0.0 && self.p__0 <= 1.0",
+            "self.confidence__0__0 > 0.5",
+            "self.weight <= 2.5",
+        ]
+    );
+
+    let out_of_range = forecast(1.5);
+    let provider = out_of_range.get_field_provider();
+    assert_eq!(provider.get_field_value("p__0"), Ok(FieldValue::Real(1.5)));
+    assert_eq!(
+        provider.get_field_value("confidence__0__0"),
+        Ok(FieldValue::Real(0.9))
+    );
+    assert_eq!(
+        provider.get_field_value("weight"),
+        Ok(FieldValue::Real(1.0))
+    );
+}
@@ -0,0 +1,117 @@
+#![allow(deprecated)]
+
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{InvariantStatus, ProofError, SmtContext};
+
+fn is_valid_checksum(data: u32, crc: u8) -> bool {
+    data.count_ones() as u8 == crc
+}
+
+#[derive(Constitution)]
+#[constitution(report)]
+struct Packet {
+    #[invariant(is_valid_checksum(self.data, self.crc))]
+    data: u32,
+    #[invariant(self.crc <= 32)]
+    crc: u8,
+}
+
+#[derive(Constitution)]
+struct Roster {
+    #[invariant(self.names.contains(&self.captain))]
+    names: Vec<String>,
+    captain: String,
+}
+
+#[derive(Constitution)]
+#[constitution(report)]
+enum Frame {
+    Data {
+        #[invariant(self.payload.is_ascii())]
+        payload: String,
+    },
+    Ack(#[invariant(self.0 > 0)] u16),
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+#[test]
+fn test_free_function_enforced_at_runtime() {
+    assert!(
+        Packet {
+            data: 0b1011,
+            crc: 3
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Packet {
+            data: 0b1011,
+            crc: 2
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert_eq!(Packet::INVARIANTS, ["self.crc <= 32"]);
+}
+
+#[test]
+fn test_unmodelled_method_enforced_at_runtime() {
+    let roster = Roster {
+        names: vec!["ana".to_string(), "bo".to_string()],
+        captain: "bo".to_string(),
+    };
+    assert!(roster.enforce_law().is_ok());
+    let roster = Roster {
+        captain: "cy".to_string(),
+        ..roster
+    };
+    assert!(roster.enforce_law().is_err());
+    assert!(Roster::INVARIANTS.is_empty());
+}
+
+#[test]
+fn test_report_marks_runtime_only_unsupported() {
+    let ctx = SmtContext::new().unwrap();
+    let report = block_on(Packet { data: 1, crc: 1 }.verify_report(&ctx));
+    let expressions: Vec<_> = report.iter().map(|result| result.expression).collect();
+    assert_eq!(
+        expressions,
+        ["self.crc <= 32", "is_valid_checksum(self.data, self.crc)"]
+    );
+    assert_eq!(
+        report.results[1].status,
+        InvariantStatus::Error(ProofError::Unsupported(
+            "is_valid_checksum(self.data, self.crc)".to_string()
+        ))
+    );
+    assert!(!report.is_verified());
+}
+
+#[test]
+fn test_enum_report_marks_active_variant_runtime_only() {
+    let ctx = SmtContext::new().unwrap();
+    let frame = Frame::Data {
+        payload: "hi".to_string(),
+    };
+    assert!(frame.enforce_law().is_ok());
+    let report = block_on(frame.verify_report(&ctx));
+    assert_eq!(report.results.len(), 1);
+    assert!(matches!(
+        report.results[0].status,
+        InvariantStatus::Error(ProofError::Unsupported(_))
+    ));
+    assert!(block_on(Frame::Ack(1).verify_report(&ctx)).is_verified());
+}
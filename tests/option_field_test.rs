@@ -0,0 +1,96 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct RateLimit {
+    #[invariant(self.limit.map_or(true, |l| l > 0))]
+    limit: Option<u32>,
+    #[invariant(self.burst.is_none_or(|b| b <= 100))]
+    burst: Option<i64>,
+    #[invariant(self.window.is_some_and(|w| w > 0))]
+    window: Option<u8>,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Retry(#[invariant(self.0.is_some())] Option<u16>);
+
+fn limit(limit: Option<u32>, burst: Option<i64>) -> RateLimit {
+    RateLimit {
+        limit,
+        burst,
+        window: Some(10),
+    }
+}
+
+#[test]
+fn test_option_invariants_checked_at_runtime() {
+    assert!(limit(Some(5), Some(100)).enforce_law().is_ok());
+    assert!(limit(None, None).enforce_law().is_ok());
+    assert!(limit(Some(0), None).enforce_law().is_err());
+    assert!(limit(None, Some(101)).enforce_law().is_err());
+    assert!(Retry(Some(3)).enforce_law().is_ok());
+    assert!(Retry(None).enforce_law().is_err());
+}
+
+#[test]
+fn test_option_invariants_lowered_to_guards() {
+    assert_eq!(
+        RateLimit::invariant_expressions(),
+        &[
+            "(self.limit.is_some == false || (self.limit > 0))",
+            "(self.burst.is_some == false || (self.burst <= 100))",
+            "(self.window.is_some == true && (self.window > 0))",
+        ]
+    );
+    assert_eq!(
+        Retry::invariant_expressions(),
+        &["(self.0.is_some == true)"]
+    );
+}
+
+#[test]
+fn test_option_fields_exposed_to_solver() {
+    let present = limit(Some(7), None);
+    let provider = present.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("limit.is_some"),
+        Ok(FieldValue::Bool(true))
+    ));
+    assert!(matches!(
+        provider.get_field_value("limit"),
+        Ok(FieldValue::BitVec { bits: 7, .. })
+    ));
+    // An absent value reads as 0 behind a false `is_some`
+    assert!(matches!(
+        provider.get_field_value("burst.is_some"),
+        Ok(FieldValue::Bool(false))
+    ));
+    assert!(matches!(
+        provider.get_field_value("burst"),
+        Ok(FieldValue::BitVec { bits: 0, .. })
+    ));
+}
+
+#[test]
+fn test_option_hash_distinguishes_none_from_zero() {
+    assert_ne!(
+        Retry(None).compute_data_hash(),
+        Retry(Some(0)).compute_data_hash()
+    );
+    assert_eq!(
+        Retry(Some(4)).compute_data_hash(),
+        Retry(Some(4)).compute_data_hash()
+    );
+}
+
+#[test]
+fn test_option_invariants_verify() {
+    assert!(limit(Some(5), None).verify_integrity_blocking().is_ok());
+    assert!(limit(None, Some(50)).verify_integrity_blocking().is_ok());
+    assert!(Retry(Some(1)).verify_integrity_blocking().is_ok());
+}
@@ -0,0 +1,22 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(panic)]
+struct Budget {
+    #[invariant(self.amount > 0)]
+    amount: i32,
+}
+
+#[test]
+fn test_lawful_value_does_not_panic() {
+    Budget { amount: 1000 }.enforce_law_or_panic();
+    assert!(Budget { amount: 1000 }.enforce_law().is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Invariant violated: self.amount > 0")]
+fn test_violation_panics_with_message() {
+    Budget { amount: -50 }.enforce_law_or_panic();
+}
@@ -0,0 +1,85 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(timeout_ms = 1000, blocking, parallel)]
+struct Sensor {
+    #[invariant(self.a > 0)]
+    a: i32,
+    #[invariant(self.b > 0)]
+    b: i32,
+    #[invariant(self.c > 0)]
+    c: i32,
+    #[invariant(self.d > 0)]
+    d: i32,
+    #[invariant(self.e > 0)]
+    e: i32,
+    #[invariant(self.f > 0)]
+    f: i32,
+    #[invariant(self.g > 0)]
+    g: i32,
+    #[invariant(self.h > 0)]
+    h: i32,
+}
+
+#[derive(Constitution)]
+#[constitution(parallel)]
+struct Rack {
+    #[constitution(nested)]
+    sensor: Sensor,
+    #[invariant(self.slots <= 16)]
+    slots: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(parallel)]
+enum Reading {
+    Level(#[invariant(self.0 <= 100)] u8),
+    Offline,
+}
+
+#[derive(Constitution)]
+#[constitution(parallel)]
+struct Empty {
+    id: u32,
+}
+
+fn sensor() -> Sensor {
+    Sensor {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: 4,
+        e: 5,
+        f: 6,
+        g: 7,
+        h: 8,
+    }
+}
+
+#[test]
+fn test_parallel_verification_agrees_with_sequential() {
+    let sensor = sensor();
+    assert!(sensor.enforce_law().is_ok());
+    assert_eq!(
+        sensor.verify_all_parallel().is_ok(),
+        sensor.verify_integrity_blocking().is_ok()
+    );
+}
+
+#[test]
+fn test_parallel_verification_with_nested_field() {
+    let rack = Rack {
+        sensor: sensor(),
+        slots: 8,
+    };
+    assert!(rack.verify_all_parallel().is_ok());
+}
+
+#[test]
+fn test_parallel_verification_on_enum_and_empty_struct() {
+    assert!(Reading::Level(50).verify_all_parallel().is_ok());
+    assert!(Reading::Offline.verify_all_parallel().is_ok());
+    assert!(Empty { id: 1 }.verify_all_parallel().is_ok());
+}
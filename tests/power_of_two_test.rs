@@ -0,0 +1,28 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+struct Arena {
+    #[invariant(self.capacity.is_power_of_two())]
+    capacity: usize,
+}
+
+#[test]
+fn test_power_of_two_runtime() {
+    for capacity in [1, 2, 4, 8] {
+        assert!(Arena { capacity }.enforce_law().is_ok(), "{capacity}");
+    }
+    for capacity in [0, 6] {
+        assert!(Arena { capacity }.enforce_law().is_err(), "{capacity}");
+    }
+}
+
+#[test]
+fn test_power_of_two_lowered_for_solver() {
+    assert_eq!(
+        Arena::invariant_expressions(),
+        &["(self.capacity != 0 && (self.capacity & (self.capacity - 1)) == 0)"]
+    );
+}
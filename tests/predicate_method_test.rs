@@ -0,0 +1,93 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Debug, Hash)]
+struct Config {
+    _retries: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Queue {
+    #[invariant(!self.items.is_empty())]
+    items: Vec<u32>,
+    #[invariant(self.name.is_empty() || self.name.len() <= 8)]
+    name: String,
+    #[invariant(self.cfg.is_some())]
+    cfg: Option<Config>,
+    #[invariant(self.fallback.is_none())]
+    fallback: Option<String>,
+}
+
+fn queue() -> Queue {
+    Queue {
+        items: vec![1],
+        name: String::new(),
+        cfg: Some(Config { _retries: 3 }),
+        fallback: None,
+    }
+}
+
+#[test]
+fn test_predicates_runtime() {
+    assert!(queue().enforce_law().is_ok());
+    assert!(
+        Queue {
+            items: Vec::new(),
+            ..queue()
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(
+        Queue {
+            cfg: None,
+            ..queue()
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(
+        Queue {
+            fallback: Some("x".to_string()),
+            ..queue()
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_predicates_lowered_for_solver() {
+    assert_eq!(
+        Queue::invariant_expressions(),
+        &[
+            "! (self.items.len == 0)",
+            "(self.name.len == 0) || self.name.len <= 8",
+            "(self.cfg.is_some == true)",
+            "(self.fallback.is_some == false)",
+        ]
+    );
+    let valid = queue();
+    let provider = valid.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("cfg.is_some"),
+        Ok(FieldValue::Bool(true))
+    );
+    assert_eq!(
+        provider.get_field_value("fallback.is_some"),
+        Ok(FieldValue::Bool(false))
+    );
+    assert!(valid.verify_integrity_blocking().is_ok());
+    assert!(
+        Queue {
+            cfg: None,
+            ..queue()
+        }
+        .verify_integrity_blocking()
+        .is_err()
+    );
+}
@@ -0,0 +1,58 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Gauge {
+    #[invariant(self.percent in 1..=100)]
+    percent: u8,
+    #[invariant(expr = "self.slot in 0..Self::SLOTS", name = "slot in bounds")]
+    slot: u32,
+    #[invariant(self.percent == 100 => self.level in 0..3)]
+    level: i32,
+}
+
+impl Gauge {
+    const SLOTS: u32 = 8;
+}
+
+fn gauge(percent: u8, slot: u32, level: i32) -> Gauge {
+    Gauge {
+        percent,
+        slot,
+        level,
+    }
+}
+
+#[test]
+fn test_membership_checked_at_runtime() {
+    assert!(gauge(1, 0, 5).enforce_law().is_ok());
+    assert!(gauge(100, 7, 2).enforce_law().is_ok());
+    assert!(gauge(0, 0, 0).enforce_law().is_err());
+    assert!(gauge(101, 0, 0).enforce_law().is_err());
+    assert!(gauge(100, 0, 3).enforce_law().is_err());
+    match gauge(50, 8, 0).enforce_law() {
+        Err(ConstitutionError::InvariantViolation {
+            name, expression, ..
+        }) => {
+            assert_eq!(name.as_deref(), Some("slot in bounds"));
+            assert_eq!(expression, "self.slot in 0..Self::SLOTS");
+        }
+        other => panic!("expected a violation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_membership_lowered_to_bounds() {
+    assert_eq!(
+        Gauge::invariant_expressions(),
+        &[
+            "(self.percent >= 1 && self.percent <= 100)",
+            "(self.slot >= 0 && self.slot < self.Self__SLOTS)",
+            "(! (self.percent == 100) || ((self.level >= 0 && self.level < 3)))",
+        ]
+    );
+    assert!(gauge(50, 3, 1).verify_integrity_blocking().is_ok());
+}
@@ -0,0 +1,85 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct View<'a> {
+    #[invariant(self.data.len() > 0)]
+    data: &'a [u8],
+    #[invariant(self.label.len() <= 8)]
+    label: &'a str,
+}
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Window<'a, T> {
+    #[invariant(self.items.len() <= self.max)]
+    items: &'a [T],
+    max: usize,
+}
+
+#[test]
+fn test_reference_fields_runtime() {
+    let bytes = [1, 2, 3];
+    assert!(
+        View {
+            data: &bytes,
+            label: "head"
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        View {
+            data: &[],
+            label: "head"
+        }
+        .enforce_law()
+        .is_err()
+    );
+
+    let names = ["a", "b", "c"];
+    assert!(
+        Window {
+            items: &names,
+            max: 3
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Window {
+            items: &names,
+            max: 2
+        }
+        .enforce_law()
+        .is_err()
+    );
+}
+
+#[test]
+fn test_slice_len_lowered_for_solver() {
+    assert_eq!(
+        View::invariant_expressions(),
+        &["self.data.len > 0", "self.label.len <= 8"]
+    );
+    let bytes = [1, 2, 3];
+    let view = View {
+        data: &bytes,
+        label: "head",
+    };
+    assert_eq!(
+        view.get_field_provider().get_field_value("data.len"),
+        Ok(FieldValue::UInt(3))
+    );
+    assert!(view.verify_integrity_blocking().is_ok());
+
+    let window = Window {
+        items: &bytes[..2],
+        max: 2,
+    };
+    assert!(window.verify_integrity_blocking().is_ok());
+}
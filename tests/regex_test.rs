@@ -0,0 +1,58 @@
+#![cfg(feature = "regex")]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct Currency {
+    #[invariant(matches_regex(self.code, "^[A-Z]{3}$"))]
+    code: String,
+    #[invariant(self.minor_units <= 4)]
+    minor_units: u8,
+}
+
+#[test]
+fn test_regex_match() {
+    let c = Currency {
+        code: "IDR".to_string(),
+        minor_units: 2,
+    };
+    assert!(c.enforce_law().is_ok());
+}
+
+#[test]
+fn test_regex_mismatch() {
+    let c = Currency {
+        code: "idr".to_string(),
+        minor_units: 2,
+    };
+    let err = c.enforce_law().unwrap_err();
+    assert!(err.to_string().contains("matches_regex"));
+}
+
+#[test]
+fn test_regex_is_runtime_only() {
+    let invariants = Currency::invariant_expressions();
+    assert_eq!(invariants.len(), 1);
+    assert!(invariants[0].contains("minor_units"));
+}
+
+#[test]
+fn test_partial_proof_modes() {
+    use praborrow_prover::VerificationMode;
+
+    let c = Currency {
+        code: "usd".to_string(),
+        minor_units: 2,
+    };
+    let proof = c.verify_integrity_partial();
+    assert_eq!(proof.outcomes.len(), 2);
+    assert_eq!(proof.outcomes[0].mode, VerificationMode::Runtime);
+    assert!(proof.outcomes[0].result.is_err());
+    assert_eq!(proof.outcomes[1].mode, VerificationMode::Formal);
+    assert!(proof.outcomes[1].result.is_ok());
+    assert!(!proof.is_verified());
+}
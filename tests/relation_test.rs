@@ -0,0 +1,36 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::{Constitution, verify_relation};
+use praborrow_prover::ProofError;
+
+#[derive(Constitution)]
+struct Inputs {
+    #[invariant(self.total >= 0)]
+    total: i64,
+}
+
+#[derive(Constitution)]
+struct Outputs {
+    #[invariant(self.total >= 0)]
+    total: i64,
+    fee: i64,
+}
+
+#[test]
+fn test_relation_holds() {
+    let inputs = Inputs { total: 100 };
+    let outputs = Outputs { total: 90, fee: 10 };
+    assert!(inputs.enforce_law().is_ok());
+    assert!(verify_relation!(inputs, outputs, "b.total + b.fee <= a.total").is_ok());
+    assert!(verify_relation!(inputs, outputs, b.total <= a.total).is_ok());
+}
+
+#[test]
+fn test_relation_violated() {
+    let inputs = Inputs { total: 100 };
+    let outputs = Outputs { total: 95, fee: 10 };
+    assert!(matches!(
+        verify_relation!(inputs, outputs, "b.total + b.fee <= a.total"),
+        Err(ProofError::InvariantViolated(expression)) if expression == "b.total + b.fee <= a.total"
+    ));
+}
@@ -0,0 +1,40 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(repair)]
+struct Volume {
+    #[invariant(self.level >= 0 && self.level <= 100)]
+    level: i32,
+    #[invariant("self.balance > -10")]
+    balance: i8,
+    #[invariant(self.limit >= self.level)]
+    limit: i32,
+}
+
+#[test]
+fn test_repair_clamps_ranges() {
+    let mut v = Volume {
+        level: 150,
+        balance: -128,
+        limit: 200,
+    };
+    v.repair_into_bounds();
+    assert_eq!(v.level, 100);
+    assert_eq!(v.balance, -9);
+    assert!(v.enforce_law().is_ok());
+}
+
+#[test]
+fn test_repair_leaves_cross_field_invariants_alone() {
+    let mut v = Volume {
+        level: -5,
+        balance: 0,
+        limit: -20,
+    };
+    v.repair_into_bounds();
+    assert_eq!(v.level, 0);
+    assert_eq!(v.limit, -20);
+    assert!(v.enforce_law().is_err());
+}
@@ -0,0 +1,82 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+struct Document {
+    #[invariant(self.pages > 0)]
+    pages: u32,
+    #[constitution(skip_hash)]
+    #[invariant(self.version >= 1)]
+    version: u64,
+}
+
+#[derive(Constitution)]
+struct Counter {
+    #[constitution(skip_hash)]
+    #[invariant(self.ticks < 1000)]
+    ticks: u32,
+}
+
+#[derive(Constitution)]
+enum Revision {
+    Draft {
+        #[invariant(self.words > 0)]
+        words: u32,
+        #[constitution(skip_hash)]
+        edits: u32,
+    },
+}
+
+#[test]
+fn test_skipped_field_excluded_from_hash() {
+    let a = Document {
+        pages: 3,
+        version: 1,
+    };
+    let b = Document {
+        pages: 3,
+        version: 7,
+    };
+    assert_eq!(a.compute_data_hash(), b.compute_data_hash());
+    assert_ne!(
+        a.compute_data_hash(),
+        Document {
+            pages: 4,
+            version: 1
+        }
+        .compute_data_hash()
+    );
+}
+
+#[test]
+fn test_skipped_field_still_checked_and_provided() {
+    let doc = Document {
+        pages: 3,
+        version: 0,
+    };
+    assert!(doc.enforce_law().is_err());
+    assert!(matches!(
+        doc.get_field_provider().get_field_value("version"),
+        Ok(FieldValue::BitVec { bits: 0, .. })
+    ));
+}
+
+#[test]
+fn test_all_fields_skipped_hash_is_stable() {
+    assert_eq!(
+        Counter { ticks: 1 }.compute_data_hash(),
+        Counter { ticks: 2 }.compute_data_hash()
+    );
+    assert!(Counter { ticks: 5000 }.enforce_law().is_err());
+}
+
+#[test]
+fn test_skipped_variant_field_excluded_from_hash() {
+    assert_eq!(
+        Revision::Draft { words: 9, edits: 1 }.compute_data_hash(),
+        Revision::Draft { words: 9, edits: 2 }.compute_data_hash()
+    );
+}
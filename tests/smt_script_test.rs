@@ -0,0 +1,51 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(smt_script)]
+struct Account {
+    #[invariant(self.balance >= 0 && self.balance <= self.limit)]
+    balance: i64,
+    limit: i64,
+    #[invariant(self.owner.len() > 0)]
+    owner: String,
+}
+
+#[derive(Constitution)]
+#[constitution(smt_script)]
+enum Rate {
+    Fixed(#[invariant(self.0 > 1)] u16),
+    Floating,
+}
+
+#[test]
+fn test_smt_script_pins_fields_and_asserts_invariants() {
+    let account = Account {
+        balance: -5,
+        limit: 100,
+        owner: "ann".to_string(),
+    };
+    assert!(account.enforce_law().is_err());
+    assert_eq!(
+        account.smt_script().unwrap(),
+        "(declare-const balance Int)\n\
+         (assert (= balance (- 5)))\n\
+         (declare-const limit Int)\n\
+         (assert (= limit 100))\n\
+         (declare-const |owner.len| Int)\n\
+         (assert (= |owner.len| 3))\n\
+         ; self.balance >= 0 && self.balance <= self.limit\n\
+         (assert (and (>= balance 0) (<= balance limit)))\n\
+         ; self.owner.len > 0\n\
+         (assert (> |owner.len| 0))\n\
+         (check-sat)\n"
+    );
+}
+
+#[test]
+fn test_smt_script_covers_active_variant_only() {
+    let script = Rate::Fixed(3).smt_script().unwrap();
+    assert!(script.contains("(assert (= |0| 3))"), "{script}");
+    assert_eq!(Rate::Floating.smt_script().unwrap(), "(check-sat)\n");
+}
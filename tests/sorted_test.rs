@@ -0,0 +1,55 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+struct Index {
+    #[invariant(is_sorted_unique(self.keys))]
+    keys: [u32; 4],
+    #[invariant(is_sorted(self.ranks))]
+    ranks: [i16; 3],
+    #[invariant(is_sorted(self.log))]
+    log: Vec<u64>,
+}
+
+fn index(keys: [u32; 4], ranks: [i16; 3], log: Vec<u64>) -> Index {
+    Index { keys, ranks, log }
+}
+
+#[test]
+fn test_sortedness_runtime() {
+    assert!(
+        index([1, 2, 5, 9], [1, 1, 2], vec![3, 3, 7])
+            .enforce_law()
+            .is_ok()
+    );
+    // Duplicates are sorted but not unique
+    assert!(
+        index([1, 2, 2, 9], [1, 1, 2], vec![])
+            .enforce_law()
+            .is_err()
+    );
+    assert!(
+        index([1, 2, 5, 9], [2, 1, 2], vec![])
+            .enforce_law()
+            .is_err()
+    );
+    assert!(
+        index([1, 2, 5, 9], [1, 1, 2], vec![7, 3])
+            .enforce_law()
+            .is_err()
+    );
+}
+
+#[test]
+fn test_fixed_arrays_unrolled_for_solver() {
+    // The `Vec` invariant is runtime-only
+    assert_eq!(
+        Index::invariant_expressions(),
+        &[
+            "(self.keys__0 < self.keys__1 && self.keys__1 < self.keys__2 && self.keys__2 < self.keys__3)",
+            "(self.ranks__0 <= self.ranks__1 && self.ranks__1 <= self.ranks__2)",
+        ]
+    );
+}
@@ -0,0 +1,59 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::backend::FieldValue;
+use praborrow_prover::parser::ExpressionParser;
+use praborrow_prover::{ProveInvariant, VerificationMode};
+
+/// Off-diagonal entries may be anything; on-diagonal entries must be nonzero.
+#[derive(Constitution)]
+#[constitution(partial)]
+struct SparseEntry {
+    #[invariant(self.row != self.col || self.value != 0)]
+    row: u32,
+    col: u32,
+    value: i64,
+}
+
+fn entry(row: u32, col: u32, value: i64) -> SparseEntry {
+    SparseEntry { row, col, value }
+}
+
+#[test]
+fn test_mixed_relational_and_bound_runtime() {
+    assert!(entry(0, 1, 0).enforce_law().is_ok());
+    assert!(entry(2, 2, 5).enforce_law().is_ok());
+    assert!(entry(2, 2, 0).enforce_law().is_err());
+}
+
+#[test]
+fn test_mixed_relational_and_bound_formal() {
+    assert_eq!(
+        SparseEntry::invariant_expressions(),
+        &["self.row != self.col || self.value != 0"]
+    );
+    assert!(ExpressionParser::parse(SparseEntry::invariant_expressions()[0]).is_ok());
+
+    let diagonal = entry(3, 3, -7);
+    let provider = diagonal.get_field_provider();
+    let unsigned = |bits| FieldValue::BitVec {
+        bits,
+        width: 32,
+        signed: false,
+    };
+    assert_eq!(provider.get_field_value("row"), Ok(unsigned(3)));
+    assert_eq!(provider.get_field_value("col"), Ok(unsigned(3)));
+    assert_eq!(
+        provider.get_field_value("value"),
+        Ok(FieldValue::BitVec {
+            bits: -7i64 as u128,
+            width: 64,
+            signed: true
+        })
+    );
+
+    let proof = diagonal.verify_integrity_partial();
+    assert_eq!(proof.outcomes.len(), 1);
+    assert_eq!(proof.outcomes[0].mode, VerificationMode::Formal);
+    assert!(proof.is_verified());
+}
@@ -0,0 +1,60 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct User<'a> {
+    #[invariant(self.name.len() > 0 && self.name.len() <= 16)]
+    name: String,
+    #[invariant(self.tag.len() == 3)]
+    tag: &'a str,
+}
+
+#[test]
+fn test_string_len_runtime() {
+    let user = |name: &str, tag| User {
+        name: name.to_string(),
+        tag,
+    };
+    assert!(user("ada", "abc").enforce_law().is_ok());
+    assert!(user("", "abc").enforce_law().is_err());
+    assert!(user("ada", "ab").enforce_law().is_err());
+}
+
+#[test]
+fn test_string_len_lowered_for_solver() {
+    assert_eq!(
+        User::invariant_expressions(),
+        &[
+            "self.name.len > 0 && self.name.len <= 16",
+            "self.tag.len == 3"
+        ]
+    );
+
+    let user = User {
+        name: "grace".to_string(),
+        tag: "xyz",
+    };
+    let provider = user.get_field_provider();
+    assert_eq!(
+        provider.get_field_value("name.len"),
+        Ok(FieldValue::UInt(5))
+    );
+    assert_eq!(provider.get_field_value("tag.len"), Ok(FieldValue::UInt(3)));
+    assert!(user.verify_integrity_partial().is_verified());
+}
+
+#[test]
+fn test_string_contents_hashed() {
+    let user = |name: &str| User {
+        name: name.to_string(),
+        tag: "abc",
+    };
+    assert_ne!(
+        user("ab").compute_data_hash(),
+        user("ba").compute_data_hash()
+    );
+}
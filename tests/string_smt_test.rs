@@ -0,0 +1,52 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(smt_script)]
+struct Endpoint {
+    #[invariant(self.scheme == "https")]
+    scheme: String,
+    #[invariant(self.path.starts_with("/"))]
+    path: String,
+}
+
+#[derive(Constitution, Debug)]
+struct Tag<'a> {
+    #[invariant(self.label != "none")]
+    label: &'a str,
+}
+
+fn endpoint(scheme: &str, path: &str) -> Endpoint {
+    Endpoint {
+        scheme: scheme.to_string(),
+        path: path.to_string(),
+    }
+}
+
+#[test]
+fn test_string_invariants_reach_the_solver() {
+    assert_eq!(
+        Endpoint::INVARIANTS,
+        ["self.scheme == \"https\"", "self.path.starts_with(\"/\")"]
+    );
+    assert_eq!(Tag::INVARIANTS, ["self.label != \"none\""]);
+}
+
+#[test]
+fn test_string_smt_script() {
+    let script = endpoint("https", "/index").smt_script().unwrap();
+    assert!(script.contains("(declare-const scheme String)"));
+    assert!(script.contains("(assert (= scheme \"https\"))"));
+    assert!(script.contains("(assert (= path \"/index\"))"));
+    assert!(script.contains("(str.prefixof \"/\" path)"));
+}
+
+#[test]
+fn test_string_runtime_checks() {
+    assert!(endpoint("https", "/").enforce_law().is_ok());
+    assert!(endpoint("http", "/").enforce_law().is_err());
+    assert!(endpoint("https", "index").enforce_law().is_err());
+    assert!(Tag { label: "x" }.enforce_law().is_ok());
+    assert!(Tag { label: "none" }.enforce_law().is_err());
+}
@@ -0,0 +1,65 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[invariant(self.start <= self.end)]
+struct Interval {
+    #[invariant(self.start >= 0)]
+    start: i32,
+    end: i32,
+}
+
+#[derive(Constitution)]
+#[invariant(self.used <= self.capacity)]
+#[invariant(self.capacity > 0, group = "sizing")]
+struct Buffer {
+    used: u32,
+    capacity: u32,
+}
+
+#[test]
+fn test_struct_invariant_runtime() {
+    assert!(Interval { start: 1, end: 4 }.enforce_law().is_ok());
+    assert!(Interval { start: 5, end: 4 }.enforce_law().is_err());
+}
+
+#[test]
+fn test_struct_invariant_runs_after_field_invariants() {
+    let error = Interval { start: -3, end: -5 }.enforce_law().unwrap_err();
+    assert!(error.to_string().contains("self.start >= 0"), "{error}");
+    assert_eq!(
+        Interval::invariant_expressions(),
+        &["self.start >= 0", "self.start <= self.end"]
+    );
+}
+
+#[test]
+fn test_struct_invariants_only() {
+    assert!(
+        Buffer {
+            used: 2,
+            capacity: 4
+        }
+        .enforce_law()
+        .is_ok()
+    );
+    assert!(
+        Buffer {
+            used: 5,
+            capacity: 4
+        }
+        .enforce_law()
+        .is_err()
+    );
+    assert!(
+        Buffer {
+            used: 0,
+            capacity: 0
+        }
+        .enforce_group("sizing")
+        .is_err()
+    );
+    assert_eq!(Buffer::invariant_expressions().len(), 2);
+}
@@ -0,0 +1,55 @@
+// The fixtures are deliberately tautological
+#![allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
+
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(tautology)]
+struct Pixel {
+    #[invariant(self.red <= 255)]
+    red: u8,
+    #[invariant(self.alpha > 0)]
+    alpha: u8,
+}
+
+#[derive(Constitution)]
+#[constitution(tautology)]
+struct Tagged {
+    #[invariant(self.code >= 1)]
+    code: i64,
+    #[invariant(is_sorted(self.tags))]
+    tags: Vec<u32>,
+}
+
+#[derive(Constitution)]
+#[constitution(tautology)]
+struct Cursor {
+    #[invariant(self.offset <= 4294967295)]
+    offset: usize,
+    #[invariant(self.delta <= 2147483647)]
+    delta: isize,
+}
+
+#[test]
+fn test_pointer_sized_domains() {
+    let tautological = Cursor::tautological_invariants().expect("solver available");
+    assert!(tautological.iter().all(|&index| index < 2));
+}
+
+#[test]
+fn test_tautological_invariants_index_solver_expressions() {
+    let tautological = Pixel::tautological_invariants().expect("solver available");
+    let count = Pixel::invariant_expressions().len();
+    assert!(tautological.iter().all(|&index| index < count));
+}
+
+#[test]
+fn test_runtime_only_invariants_never_reported() {
+    // Only `self.code >= 1` reaches the solver; the sort check is runtime-only
+    assert_eq!(Tagged::invariant_expressions().len(), 1);
+    let tautological = Tagged::tautological_invariants().expect("solver available");
+    assert!(tautological.iter().all(|&index| index == 0));
+}
@@ -0,0 +1,43 @@
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{InvariantStatus, ProofError, SmtContext};
+
+#[derive(Constitution)]
+#[constitution(timeout_ms = 5000, partial, blocking, report)]
+struct Mesh {
+    #[invariant(self.rows * self.cols <= 4096)]
+    rows: u32,
+    cols: u32,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+#[test]
+fn test_timeout_bounded_verification_runs() {
+    let mesh = Mesh { rows: 64, cols: 64 };
+    assert!(mesh.enforce_law().is_ok());
+    assert!(mesh.verify_integrity_blocking().is_ok());
+    assert!(mesh.verify_integrity_partial().is_verified());
+
+    let ctx = SmtContext::new().unwrap();
+    let report = block_on(mesh.verify_report(&ctx));
+    assert!(report.is_verified());
+    assert_eq!(report.results[0].status, InvariantStatus::Proved);
+}
+
+#[test]
+fn test_timeout_error_is_distinguishable() {
+    let error = ProofError::Timeout(5000);
+    assert_ne!(error, ProofError::Unknown);
+    assert_eq!(error.to_string(), "Solver timed out after 5000 ms");
+}
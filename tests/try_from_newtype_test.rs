@@ -0,0 +1,31 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(try_from)]
+struct Port(#[invariant(self.0 > 0)] u16);
+
+#[derive(Constitution, Debug)]
+#[constitution(try_from)]
+struct Percent {
+    #[invariant(self.value <= 100)]
+    value: u8,
+}
+
+#[test]
+fn test_try_from_tuple_newtype() {
+    assert_eq!(Port::try_from(8080u16).unwrap().0, 8080);
+    assert!(matches!(
+        Port::try_from(0),
+        Err(ConstitutionError::InvariantViolation { .. })
+    ));
+}
+
+#[test]
+fn test_try_from_named_newtype() {
+    let percent: Percent = 42u8.try_into().unwrap();
+    assert_eq!(percent.value, 42);
+    assert!(Percent::try_from(101).is_err());
+    assert!(percent.enforce_law().is_ok());
+}
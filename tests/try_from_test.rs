@@ -0,0 +1,95 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+struct Histogram {
+    #[invariant(u8::try_from(self.count).is_ok())]
+    count: i32,
+    #[invariant(i16::try_from(self.offset).is_ok())]
+    offset: i64,
+    #[invariant(u64::try_from(self.total).is_ok())]
+    total: i64,
+}
+
+#[derive(Constitution)]
+struct Offsets {
+    #[invariant(isize::try_from(self.delta).is_ok())]
+    delta: i64,
+    #[invariant(usize::try_from(self.len).is_ok())]
+    len: i64,
+}
+
+#[derive(Constitution)]
+struct Ledger {
+    #[invariant(u32::try_from(self.balance).is_ok())]
+    balance: u128,
+}
+
+fn histogram(count: i32, offset: i64, total: i64) -> Histogram {
+    Histogram {
+        count,
+        offset,
+        total,
+    }
+}
+
+#[test]
+fn test_try_from_runtime() {
+    assert!(histogram(255, -32768, 0).enforce_law().is_ok());
+    assert!(histogram(256, 0, 0).enforce_law().is_err());
+    assert!(histogram(-1, 0, 0).enforce_law().is_err());
+    assert!(histogram(0, 40000, 0).enforce_law().is_err());
+    assert!(histogram(0, 0, -5).enforce_law().is_err());
+}
+
+#[test]
+fn test_try_from_lowered_to_range() {
+    assert_eq!(
+        Histogram::invariant_expressions(),
+        &[
+            "(self.count >= 0 && self.count <= 255)",
+            "(self.offset >= -32768 && self.offset <= 32767)",
+            "(self.total >= 0)",
+        ]
+    );
+}
+
+#[test]
+fn test_pointer_sized_targets_follow_target() {
+    assert_eq!(
+        Offsets::invariant_expressions(),
+        &[
+            "(self.delta >= self.isize__MIN && self.delta <= self.isize__MAX)",
+            "(self.len >= 0 && self.len <= self.usize__MAX)",
+        ]
+    );
+    let offsets = Offsets { delta: -1, len: 1 };
+    let provider = offsets.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("usize__MAX"),
+        Ok(FieldValue::BitVec { bits, width: usize::BITS, signed: false }) if bits == usize::MAX as u128
+    ));
+    assert!(matches!(
+        provider.get_field_value("isize__MIN"),
+        Ok(FieldValue::BitVec {
+            width: usize::BITS,
+            signed: true,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_full_range_target_on_wide_field() {
+    assert_eq!(
+        Ledger::invariant_expressions(),
+        &["(self.balance >= 0 && self.balance <= 4294967295)"]
+    );
+    let ledger = Ledger {
+        balance: u64::MAX as u128 + 1,
+    };
+    assert!(ledger.enforce_law().is_err());
+}
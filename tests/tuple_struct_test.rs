@@ -0,0 +1,57 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct Meters(#[invariant(self.0 >= 0)] i32);
+
+#[derive(Constitution)]
+struct Span(
+    #[invariant(self.0 <= self.1)] u16,
+    #[invariant(self.1 < 1000)] u16,
+    [u8; 2],
+);
+
+#[test]
+fn test_tuple_struct_runtime() {
+    assert!(Meters(0).enforce_law().is_ok());
+    assert!(Meters(-1).enforce_law().is_err());
+    assert!(Span(3, 5, [0, 0]).enforce_law().is_ok());
+    assert!(Span(6, 5, [0, 0]).enforce_law().is_err());
+    assert!(Span(6, 1000, [0, 0]).enforce_law().is_err());
+}
+
+#[test]
+fn test_tuple_struct_expressions_for_solver() {
+    assert_eq!(Meters::invariant_expressions(), &["self.0 >= 0"]);
+    assert_eq!(
+        Span::invariant_expressions(),
+        &["self.0 <= self.1", "self.1 < 1000"]
+    );
+}
+
+#[test]
+fn test_tuple_struct_provider_keys_by_index() {
+    let span = Span(3, 5, [7, 9]);
+    let provider = span.get_field_provider();
+    assert!(matches!(
+        provider.get_field_value("0"),
+        Ok(FieldValue::BitVec { bits: 3, .. })
+    ));
+    assert!(matches!(
+        provider.get_field_value("1"),
+        Ok(FieldValue::BitVec { bits: 5, .. })
+    ));
+    assert!(matches!(
+        provider.get_field_value("_2__1"),
+        Ok(FieldValue::BitVec { bits: 9, .. })
+    ));
+}
+
+#[test]
+fn test_tuple_struct_verifies() {
+    assert!(Meters(4).verify_integrity_partial().is_verified());
+}
@@ -0,0 +1,51 @@
+extern crate alloc;
+use core::marker::PhantomData;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+trait Active {}
+
+struct Open;
+struct Frozen;
+
+impl Active for Open {}
+
+#[derive(Constitution)]
+#[constitution(when(S: Active), invariant("self.balance > 0"))]
+struct Account<S> {
+    #[invariant(self.balance >= 0)]
+    balance: i64,
+    state: PhantomData<S>,
+}
+
+#[test]
+fn test_base_invariants_apply_to_every_state() {
+    let frozen = Account::<Frozen> {
+        balance: 0,
+        state: PhantomData,
+    };
+    assert!(frozen.enforce_law().is_ok());
+
+    let overdrawn = Account::<Frozen> {
+        balance: -1,
+        state: PhantomData,
+    };
+    assert!(overdrawn.enforce_law().is_err());
+}
+
+#[test]
+fn test_state_invariants_apply_when_bound_holds() {
+    let empty = Account::<Open> {
+        balance: 0,
+        state: PhantomData,
+    };
+    assert!(empty.enforce_law().is_ok());
+    let err = empty.enforce_law_when_active().unwrap_err();
+    assert!(err.to_string().contains("self.balance > 0"));
+
+    let funded = Account::<Open> {
+        balance: 10,
+        state: PhantomData,
+    };
+    assert!(funded.enforce_law_when_active().is_ok());
+}
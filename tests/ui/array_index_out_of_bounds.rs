@@ -0,0 +1,9 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Window {
+    #[invariant(self.buf[0] < self.buf[4])]
+    buf: [i32; 4],
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(backend = "yices")]
+struct Quota {
+    #[invariant(self.used <= 10)]
+    used: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Packet {
+    #[constitution(bits = 8)]
+    kind: u8,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(c_export)]
+struct NotReprC {
+    #[invariant(self.value > 0)]
+    value: i32,
+}
+
+fn main() {}
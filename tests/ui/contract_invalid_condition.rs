@@ -0,0 +1,9 @@
+use praborrow_defense::contract;
+
+#[contract]
+#[requires("x")]
+fn positive(x: i32) -> i32 {
+    x
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Hash)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Constitution)]
+struct Light {
+    #[invariant(self.color != Color::Red)]
+    color: Color,
+}
+
+fn main() {}
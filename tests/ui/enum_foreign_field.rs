@@ -0,0 +1,15 @@
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+enum Shape {
+    Circle {
+        #[invariant(self.w > 0.0)]
+        r: f64,
+    },
+    Rect {
+        w: f64,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Sample {
+    #[invariant(self.level.rem_euclid(100) == self.bucket)]
+    level: i8,
+    bucket: i8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(fallback_linear)]
+enum Area {
+    Square(#[invariant(self.0 * self.0 <= 4096)] u32),
+    Empty,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Samples {
+    #[invariant(forall i in 0..5 => self.buf[i] >= 0)]
+    buf: [i32; 4],
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Stat<T: PartialOrd<i32>> {
+    #[invariant(self.value > 0)]
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+struct Handle {
+    fd: i32,
+}
+
+#[derive(Constitution)]
+struct Buffer {
+    #[invariant(self.len > 0)]
+    len: u32,
+    handle: Handle,
+}
+
+fn main() {}
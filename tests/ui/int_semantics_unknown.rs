@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(int_semantics = "wrapping")]
+struct Counter {
+    #[invariant(self.hits + 1 > self.hits)]
+    hits: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Window {
+    #[invariant(self.start > 0 && self.len == [1, 2])]
+    start: u32,
+    len: u32,
+}
+
+fn main() {}
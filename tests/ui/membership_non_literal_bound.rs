@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Window {
+    #[invariant(self.pos in 0..self.len)]
+    pos: u32,
+    len: u32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use praborrow_defense::Constitution;
+
+#[derive(Clone, Copy)]
+enum Status {
+    Open,
+    Closed,
+}
+
+#[derive(Constitution)]
+struct Ticket {
+    #[constitution(repr(f32))]
+    status: Status,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+fn checksum(value: u32) -> u32 {
+    value.rotate_left(3)
+}
+
+#[derive(Constitution)]
+#[constitution(strict_parse)]
+struct Strict {
+    #[invariant(self.crc == checksum(self.value))]
+    crc: u32,
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(timeout_ms = 0)]
+struct Mesh {
+    #[invariant(self.rows > 0)]
+    rows: u32,
+}
+
+fn main() {}
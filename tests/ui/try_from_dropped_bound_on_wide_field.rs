@@ -0,0 +1,9 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Ledger {
+    #[invariant(u64::try_from(self.balance).is_ok())]
+    balance: u128,
+}
+
+fn main() {}
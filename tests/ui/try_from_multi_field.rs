@@ -0,0 +1,11 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(try_from)]
+struct Span {
+    #[invariant(self.start <= self.end)]
+    start: u32,
+    end: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[repr(C)]
+union Payload {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
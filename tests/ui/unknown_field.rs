@@ -0,0 +1,17 @@
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Typo {
+    #[invariant(self.vlaue > 0)]
+    value: i32,
+}
+
+#[derive(Constitution)]
+#[invariant("self.start <= self.ned")]
+struct Window {
+    start: u32,
+    end: u32,
+}
+
+fn main() {}
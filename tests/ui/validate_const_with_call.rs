@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(validate_const)]
+struct Label {
+    #[invariant(self.text.len() <= 8)]
+    text: String,
+}
+
+fn main() {}
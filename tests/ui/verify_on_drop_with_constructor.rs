@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(verify_on_drop, constructor)]
+struct Counter {
+    #[invariant(self.count <= 10)]
+    count: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Packet {
+    #[invariant(when = "self.kind == 2", name = "extended payload")]
+    kind: u8,
+    extra: u32,
+}
+
+fn main() {}
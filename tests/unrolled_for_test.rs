@@ -0,0 +1,40 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+
+#[derive(Constitution)]
+#[constitution(blocking)]
+struct Kernel {
+    #[invariant(self.sorted[i] <= self.sorted[i + 1] for i in 0..self.sorted.len() - 1)]
+    sorted: [i32; 4],
+    #[invariant(expr = "self.weights[i] < 100 for i in 0..=1", name = "weight cap")]
+    weights: [u8; 3],
+}
+
+fn kernel(sorted: [i32; 4], weights: [u8; 3]) -> Kernel {
+    Kernel { sorted, weights }
+}
+
+#[test]
+fn test_unrolled_checked_at_runtime() {
+    assert!(kernel([-3, 0, 0, 7], [1, 2, 200]).enforce_law().is_ok());
+    assert!(kernel([-3, 1, 0, 7], [1, 2, 3]).enforce_law().is_err());
+    assert!(kernel([0, 1, 2, 3], [1, 100, 3]).enforce_law().is_err());
+}
+
+#[test]
+fn test_unrolled_for_solver() {
+    assert_eq!(
+        Kernel::invariant_expressions(),
+        &[
+            "((self.sorted__0 <= self.sorted__1) && (self.sorted__1 <= self.sorted__2) && (self.sorted__2 <= self.sorted__3))",
+            "((self.weights__0 < 100) && (self.weights__1 < 100))",
+        ]
+    );
+    assert!(
+        kernel([0, 1, 2, 3], [1, 2, 3])
+            .verify_integrity_blocking()
+            .is_ok()
+    );
+}
@@ -0,0 +1,53 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+struct Item {
+    _weight: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(partial)]
+struct Basket {
+    #[invariant(self.items.len() <= self.capacity)]
+    items: Vec<Item>,
+    capacity: usize,
+}
+
+fn basket(count: usize, capacity: usize) -> Basket {
+    Basket {
+        items: (0..count).map(|_| Item { _weight: 1 }).collect(),
+        capacity,
+    }
+}
+
+#[test]
+fn test_vec_len_runtime() {
+    assert!(basket(2, 3).enforce_law().is_ok());
+    assert!(basket(4, 3).enforce_law().is_err());
+}
+
+#[test]
+fn test_vec_len_lowered_for_solver() {
+    assert_eq!(
+        Basket::invariant_expressions(),
+        &["self.items.len <= self.capacity"]
+    );
+    let full = basket(3, 3);
+    assert_eq!(
+        full.get_field_provider().get_field_value("items.len"),
+        Ok(FieldValue::UInt(3))
+    );
+    assert!(full.verify_integrity_partial().is_verified());
+}
+
+#[test]
+fn test_vec_length_hashed() {
+    let hash = |basket: &Basket| basket.compute_data_hash();
+    assert_ne!(hash(&basket(1, 3)), hash(&basket(2, 3)));
+    let mut other = basket(2, 3);
+    other.items[0]._weight = 7;
+    assert_eq!(hash(&basket(2, 3)), hash(&other));
+}
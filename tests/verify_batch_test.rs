@@ -0,0 +1,63 @@
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::SmtContext;
+
+#[derive(Constitution)]
+#[constitution(batch)]
+struct Window {
+    #[invariant(self.start <= self.end)]
+    start: u32,
+    end: u32,
+}
+
+#[derive(Constitution)]
+#[constitution(batch)]
+enum Limit {
+    Fixed(#[invariant(self.0 > 0)] u32),
+    Unbounded,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+#[test]
+fn test_batch_reports_each_item_in_order() {
+    let ctx = SmtContext::new().unwrap();
+    let windows = [
+        Window { start: 0, end: 10 },
+        Window { start: 5, end: 1 },
+        Window { start: 3, end: 3 },
+    ];
+    let results = block_on(Window::verify_batch(&ctx, &windows));
+    let proved: Vec<_> = results.iter().map(Result::is_ok).collect();
+    assert_eq!(proved, [true, false, true]);
+    assert!(
+        windows
+            .iter()
+            .all(|w| w.enforce_law().is_ok() == w.is_valid())
+    );
+}
+
+#[test]
+fn test_empty_batch() {
+    let ctx = SmtContext::new().unwrap();
+    assert!(block_on(Window::verify_batch(&ctx, &[])).is_empty());
+}
+
+#[test]
+fn test_batch_on_enum() {
+    let ctx = SmtContext::new().unwrap();
+    let limits = [Limit::Fixed(4), Limit::Unbounded, Limit::Fixed(0)];
+    let results = block_on(Limit::verify_batch(&ctx, &limits));
+    let proved: Vec<_> = results.iter().map(Result::is_ok).collect();
+    assert_eq!(proved, [true, true, false]);
+}
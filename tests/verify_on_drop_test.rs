@@ -0,0 +1,68 @@
+extern crate alloc;
+use core::cell::Cell;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+#[constitution(verify_on_drop)]
+struct Counter {
+    #[invariant(self.count <= 10)]
+    count: u32,
+}
+
+thread_local! {
+    static MANUAL_DROPS: Cell<u32> = const { Cell::new(0) };
+}
+
+#[derive(Constitution)]
+#[constitution(verify_on_drop(manual), constructor)]
+struct Session {
+    #[invariant(self.open <= 1)]
+    open: u8,
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        MANUAL_DROPS.with(|drops| drops.set(drops.get() + 1));
+        self.check_on_drop();
+    }
+}
+
+#[test]
+fn test_valid_value_drops_quietly() {
+    let mut counter = Counter { count: 3 };
+    counter.count += 7;
+    assert!(counter.enforce_law().is_ok());
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "dropped in a violating state")
+)]
+fn test_violating_value_panics_on_drop() {
+    let mut counter = Counter { count: 3 };
+    counter.count += 8;
+}
+
+#[test]
+fn test_drop_check_skipped_while_panicking() {
+    let result = std::panic::catch_unwind(|| {
+        let _counter = Counter { count: 11 };
+        panic!("unrelated");
+    });
+    let message = result.unwrap_err();
+    assert_eq!(message.downcast_ref::<&str>(), Some(&"unrelated"));
+}
+
+#[test]
+fn test_manual_check_from_own_drop() {
+    drop(Session::try_new(1).unwrap());
+    assert_eq!(MANUAL_DROPS.with(Cell::get), 1);
+
+    let result = std::panic::catch_unwind(|| {
+        let mut session = Session::try_new(0).unwrap();
+        session.open = 2;
+    });
+    assert_eq!(result.is_err(), cfg!(debug_assertions));
+}
@@ -0,0 +1,74 @@
+extern crate alloc;
+use core::future::Future;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::{InvariantStatus, SmtContext};
+
+#[derive(Constitution)]
+#[constitution(report)]
+struct Account {
+    #[invariant(self.balance >= 0)]
+    #[invariant(self.balance <= self.limit)]
+    balance: i64,
+    #[invariant(self.limit > 0)]
+    limit: i64,
+}
+
+#[derive(Constitution)]
+#[constitution(report)]
+enum Quota {
+    Fixed(#[invariant(self.0 > 0)] u32),
+    Range {
+        #[invariant(self.lo <= self.hi)]
+        lo: u32,
+        hi: u32,
+    },
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let core::task::Poll::Ready(result) = future.as_mut().poll(&mut cx) {
+            break result;
+        }
+    }
+}
+
+#[test]
+fn test_report_lists_every_invariant() {
+    let ctx = SmtContext::new().unwrap();
+    let account = Account {
+        balance: 10,
+        limit: 100,
+    };
+    let report = block_on(account.verify_report(&ctx));
+    let expressions: Vec<_> = report.iter().map(|result| result.expression).collect();
+    assert_eq!(
+        expressions,
+        [
+            "self.balance >= 0",
+            "self.balance <= self.limit",
+            "self.limit > 0"
+        ]
+    );
+    assert!(
+        (&report)
+            .into_iter()
+            .all(|result| result.status == InvariantStatus::Proved)
+    );
+    assert!(report.is_verified());
+    assert!(report.to_string().contains("proved: self.limit > 0"));
+}
+
+#[test]
+fn test_report_covers_active_variant_only() {
+    let ctx = SmtContext::new().unwrap();
+    let report = block_on(Quota::Range { lo: 1, hi: 5 }.verify_report(&ctx));
+    assert_eq!(report.results.len(), 1);
+    assert_eq!(report.results[0].expression, "self.lo <= self.hi");
+    assert_eq!(
+        block_on(Quota::Fixed(3).verify_report(&ctx)).results.len(),
+        1
+    );
+}
@@ -0,0 +1,67 @@
+extern crate alloc;
+use praborrow_core::{CheckProtocol, ConstitutionError};
+use praborrow_defense::Constitution;
+
+#[derive(Constitution)]
+struct Reading {
+    #[invariant(self.val > 10)]
+    val: i32,
+    #[invariant(self.low <= self.high)]
+    low: u8,
+    high: u8,
+    #[invariant(self.code != 0)]
+    code: u16,
+}
+
+fn values(reading: Reading) -> Vec<(String, String)> {
+    match reading.enforce_law() {
+        Err(ConstitutionError::InvariantViolation { values, .. }) => values.into_iter().collect(),
+        other => panic!("expected a violation, got {other:?}"),
+    }
+}
+
+fn reading() -> Reading {
+    Reading {
+        val: 11,
+        low: 1,
+        high: 2,
+        code: 7,
+    }
+}
+
+#[test]
+fn test_violation_records_referenced_field() {
+    let error = Reading {
+        val: 5,
+        ..reading()
+    }
+    .enforce_law()
+    .unwrap_err();
+    assert!(error.to_string().contains(r#""val": "5""#), "{error}");
+    assert_eq!(
+        values(Reading {
+            val: 5,
+            ..reading()
+        }),
+        [("val".into(), "5".into())]
+    );
+}
+
+#[test]
+fn test_violation_records_every_referenced_field_only() {
+    assert_eq!(
+        values(Reading {
+            low: 9,
+            high: 3,
+            ..reading()
+        }),
+        [("high".into(), "3".into()), ("low".into(), "9".into())]
+    );
+    assert_eq!(
+        values(Reading {
+            code: 0,
+            ..reading()
+        }),
+        [("code".into(), "0".into())]
+    );
+}
@@ -0,0 +1,40 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+use praborrow_prover::ProveInvariant;
+use praborrow_prover::backend::FieldValue;
+
+#[derive(Constitution)]
+#[constitution(int_semantics = "math")]
+struct Ledger {
+    #[invariant(self.big > 0)]
+    big: i128,
+    #[invariant(self.total > 0)]
+    total: u128,
+    #[invariant(self.parts.iter().sum::<u128>() > 0)]
+    parts: [u128; 2],
+}
+
+#[test]
+fn test_wide_fields_not_truncated() {
+    // Both values truncate to 0 (or below) when cast to 64 bits
+    let big = i128::from(u64::MAX) + 1;
+    let total = u128::from(u64::MAX) + 1;
+    let ledger = Ledger {
+        big,
+        total,
+        parts: [total, 1],
+    };
+    assert!(ledger.enforce_law().is_ok());
+
+    let provider = ledger.get_field_provider();
+    assert_eq!(provider.get_field_value("big"), Ok(FieldValue::Int128(big)));
+    assert_eq!(
+        provider.get_field_value("total"),
+        Ok(FieldValue::UInt128(total))
+    );
+    assert_eq!(
+        provider.get_field_value("parts__0"),
+        Ok(FieldValue::UInt128(total))
+    );
+}
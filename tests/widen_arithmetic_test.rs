@@ -0,0 +1,91 @@
+extern crate alloc;
+use praborrow_core::CheckProtocol;
+use praborrow_defense::Constitution;
+
+#[derive(Constitution, Debug)]
+#[constitution(widen_arithmetic)]
+#[invariant(self.a + self.b > 0)]
+#[invariant(self.a * self.b >= self.a - 1)]
+struct Pair {
+    a: i32,
+    b: i32,
+}
+
+#[derive(Constitution, Debug)]
+#[constitution(widen_arithmetic)]
+struct Budget {
+    #[invariant(self.spent - self.refunded <= self.cap)]
+    spent: u32,
+    refunded: u32,
+    cap: u32,
+}
+
+#[derive(Constitution, Debug)]
+#[constitution(widen_arithmetic)]
+struct Area {
+    #[invariant(self.width * self.height <= self.limit)]
+    width: u64,
+    height: u64,
+    limit: u64,
+    #[invariant(self.depth * self.depth >= 0)]
+    depth: i64,
+}
+
+#[test]
+fn test_sum_near_max_does_not_overflow() {
+    let pair = Pair {
+        a: i32::MAX,
+        b: i32::MAX,
+    };
+    assert!(pair.enforce_law().is_ok());
+    let pair = Pair {
+        a: i32::MIN,
+        b: i32::MIN,
+    };
+    assert!(pair.enforce_law().is_err());
+}
+
+#[test]
+fn test_unsigned_subtraction_is_signed() {
+    // Underflows as `u32`; widened, it is simply negative
+    let budget = Budget {
+        spent: 1,
+        refunded: 5,
+        cap: 0,
+    };
+    assert!(budget.enforce_law().is_ok());
+    let budget = Budget {
+        spent: 10,
+        refunded: 5,
+        cap: 4,
+    };
+    assert!(budget.enforce_law().is_err());
+}
+
+#[test]
+fn test_solver_form_unchanged() {
+    assert_eq!(
+        Pair::INVARIANTS,
+        ["self.a + self.b > 0", "self.a * self.b >= self.a - 1"]
+    );
+}
+
+#[test]
+fn test_product_beyond_i128_left_as_written() {
+    // `u64::MAX * u64::MAX` exceeds `i128`, so the product keeps its own type;
+    // `i64::MIN * i64::MIN` fits and is still widened
+    let area = Area {
+        width: 3,
+        height: 4,
+        limit: 12,
+        depth: i64::MIN,
+    };
+    assert!(area.enforce_law().is_ok());
+    let area = Area {
+        width: 3,
+        height: 5,
+        limit: 12,
+        depth: 0,
+    };
+    assert!(area.enforce_law().is_err());
+}